@@ -0,0 +1,138 @@
+/// Every action reachable from the `Ctrl+P` command palette.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Command {
+    StartPauseTimer,
+    ResetTimer,
+    SkipSegment,
+    FinishSession,
+    TakeBreakNow,
+    SwitchToPomodoro,
+    SwitchToShortBreak,
+    SwitchToLongBreak,
+    NewTask,
+    GoToTimer,
+    GoToTaskList,
+    GoToStatistics,
+    ManageProjects,
+    OpenSettings,
+    ZenMode,
+    ToggleMiniTimer,
+    ShowHelp,
+    Save,
+    ReloadConfig,
+    UndoConfigReload,
+    EditState,
+    PrintDailyLog,
+    Quit,
+}
+
+impl Command {
+    pub const ALL: &'static [Command] = &[
+        Command::StartPauseTimer,
+        Command::ResetTimer,
+        Command::SkipSegment,
+        Command::FinishSession,
+        Command::TakeBreakNow,
+        Command::SwitchToPomodoro,
+        Command::SwitchToShortBreak,
+        Command::SwitchToLongBreak,
+        Command::NewTask,
+        Command::GoToTimer,
+        Command::GoToTaskList,
+        Command::GoToStatistics,
+        Command::ManageProjects,
+        Command::OpenSettings,
+        Command::ZenMode,
+        Command::ToggleMiniTimer,
+        Command::ShowHelp,
+        Command::Save,
+        Command::ReloadConfig,
+        Command::UndoConfigReload,
+        Command::EditState,
+        Command::PrintDailyLog,
+        Command::Quit,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Command::StartPauseTimer => "Start / Pause Timer",
+            Command::ResetTimer => "Reset Timer",
+            Command::SkipSegment => "Skip to Next Segment",
+            Command::FinishSession => "Finish Session Now",
+            Command::TakeBreakNow => "Take a Break Now",
+            Command::SwitchToPomodoro => "Switch to Pomodoro",
+            Command::SwitchToShortBreak => "Switch to Short Break",
+            Command::SwitchToLongBreak => "Switch to Long Break",
+            Command::NewTask => "New Task",
+            Command::GoToTimer => "Go to Timer",
+            Command::GoToTaskList => "Go to Task List",
+            Command::GoToStatistics => "Go to Statistics",
+            Command::ManageProjects => "Manage Projects",
+            Command::OpenSettings => "Settings",
+            Command::ZenMode => "Zen Mode",
+            Command::ToggleMiniTimer => "Toggle Mini Timer",
+            Command::ShowHelp => "Show Keybindings",
+            Command::Save => "Save Now",
+            Command::ReloadConfig => "Reload Config",
+            Command::UndoConfigReload => "Undo Config Reload",
+            Command::EditState => "Edit State File in $EDITOR",
+            Command::PrintDailyLog => "Print Today's Work Log to File",
+            Command::Quit => "Quit",
+        }
+    }
+}
+
+/// Lower is better; `None` means the query does not match at all.
+fn score(query: &str, label: &str) -> Option<u8> {
+    let label = label.to_lowercase();
+    if label.starts_with(query) {
+        return Some(0);
+    }
+    let pos = label.find(query)?;
+    // A hit at the start of a word ("timer" in "Reset Timer") beats one mid-word
+    if label[..pos].ends_with([' ', '/']) {
+        Some(1)
+    } else {
+        Some(2)
+    }
+}
+
+/// Case-insensitive substring match, best matches first. Ties keep the order
+/// of `commands`, and an empty query returns everything.
+pub fn fuzzy_match(query: &str, commands: &[Command]) -> Vec<Command> {
+    let query = query.trim().to_lowercase();
+    let mut scored: Vec<(u8, Command)> = commands
+        .iter()
+        .filter_map(|c| score(&query, c.label()).map(|s| (s, *c)))
+        .collect();
+    scored.sort_by_key(|(s, _)| *s);
+    scored.into_iter().map(|(_, c)| c).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_returns_all_commands() {
+        assert_eq!(fuzzy_match("", Command::ALL), Command::ALL.to_vec());
+    }
+
+    #[test]
+    fn prefix_matches_rank_before_word_and_inner_matches() {
+        let matches = fuzzy_match("time", Command::ALL);
+        assert_eq!(matches[0], Command::StartPauseTimer);
+        assert!(matches.contains(&Command::GoToTimer));
+        assert!(matches.contains(&Command::ToggleMiniTimer));
+
+        let matches = fuzzy_match("set", Command::ALL);
+        // "Settings" (prefix) before "Reset Timer" (mid-word)
+        assert_eq!(matches, vec![Command::OpenSettings, Command::ResetTimer]);
+    }
+
+    #[test]
+    fn matching_is_case_insensitive_and_drops_misses() {
+        assert_eq!(fuzzy_match("QUIT", Command::ALL), vec![Command::Quit]);
+        assert!(fuzzy_match("nonexistent", Command::ALL).is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+//! `#[serde(with = "duration_secs")]` — stores a `Duration` as a plain number of seconds.
+//! Deserialization also accepts serde's default `{ "secs": N, "nanos": M }` shape so
+//! state written by older versions keeps loading.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DurationRepr {
+    Secs(u64),
+    Legacy {
+        secs: u64,
+        #[serde(default)]
+        nanos: u32,
+    },
+}
+
+pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_secs())
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    Ok(match DurationRepr::deserialize(deserializer)? {
+        DurationRepr::Secs(secs) => Duration::from_secs(secs),
+        DurationRepr::Legacy { secs, nanos } => Duration::new(secs, nanos),
+    })
+}
+
+/// `#[serde(default, with = "duration_secs::option")]` — the same for `Option<Duration>`.
+pub mod option {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(d) => serializer.serialize_some(&d.as_secs()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+        Ok(Option::<super::DurationRepr>::deserialize(deserializer)?.map(|repr| match repr {
+            super::DurationRepr::Secs(secs) => Duration::from_secs(secs),
+            super::DurationRepr::Legacy { secs, nanos } => Duration::new(secs, nanos),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper {
+        #[serde(with = "super")]
+        d: Duration,
+    }
+
+    #[test]
+    fn serializes_as_seconds() {
+        let json = serde_json::to_string(&Wrapper { d: Duration::from_millis(90_500) }).unwrap();
+        assert_eq!(json, r#"{"d":90}"#);
+    }
+
+    #[test]
+    fn accepts_both_formats() {
+        let new: Wrapper = serde_json::from_str(r#"{"d":90}"#).unwrap();
+        let old: Wrapper = serde_json::from_str(r#"{"d":{"secs":90,"nanos":500}}"#).unwrap();
+        assert_eq!(new.d, Duration::from_secs(90));
+        assert_eq!(old.d, Duration::new(90, 500));
+    }
+}
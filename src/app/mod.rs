@@ -1,61 +1,389 @@
-use crate::settings::Settings;
-use chrono::{DateTime, Utc};
-use directories::ProjectDirs;
+use crate::persistence::{self, StateFormat};
+use crate::settings::{BreakSchedule, Settings, SortCriterion};
+use crate::util::format_hm;
+use chrono::{Datelike, DateTime, Duration as ChronoDuration, Local, NaiveDate, TimeZone, Timelike, Utc, Weekday};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
-use std::time::Duration;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
+/// How long the "Session Complete" popup stays up before the next segment starts.
+pub const SESSION_SUMMARY_DURATION: Duration = Duration::from_secs(3);
+/// How long the timer keeps the outgoing mode's colors after a segment ends.
+pub const MODE_TRANSITION_DURATION: Duration = Duration::from_millis(500);
+
+pub mod command;
+pub mod duration_secs;
 pub mod ui_state;
 pub use ui_state::UiState;
 
-fn project_dirs() -> Option<ProjectDirs> {
-    ProjectDirs::from("", "", "pomodorust")
+/// XDG base directory: the variable's value when it is an absolute path
+/// (the spec says to ignore relative ones), otherwise `fallback` under home.
+fn xdg_base_dir(value: Option<std::ffi::OsString>, home: Option<PathBuf>, fallback: &str) -> Option<PathBuf> {
+    value
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| home.map(|h| h.join(fallback)))
+}
+
+#[allow(deprecated)]
+fn home_dir() -> Option<PathBuf> {
+    std::env::home_dir()
+}
+
+/// `$XDG_DATA_HOME/pomodorust`, by default `~/.local/share/pomodorust`.
+#[cfg(target_os = "linux")]
+pub fn get_data_dir() -> Option<PathBuf> {
+    xdg_base_dir(std::env::var_os("XDG_DATA_HOME"), home_dir(), ".local/share").map(|d| d.join("pomodorust"))
 }
 
-pub fn get_data_path() -> Option<PathBuf> {
-    project_dirs().map(|d| d.data_local_dir().join("state.json"))
+/// The platform's data directory, e.g. `~/Library/Application Support/pomodorust`
+/// on macOS, so existing databases stay where they are.
+#[cfg(not(target_os = "linux"))]
+pub fn get_data_dir() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "pomodorust").map(|d| d.data_local_dir().to_path_buf())
 }
 
 pub fn get_db_path() -> Option<PathBuf> {
-    project_dirs().map(|d| d.data_local_dir().join("pomodorust.db"))
+    get_data_dir().map(|d| d.join("pomodorust.db"))
 }
 
+/// `$XDG_CONFIG_HOME/pomodorust/config.toml`, by default under `~/.config`.
 pub fn get_config_path() -> Option<PathBuf> {
-    #[allow(deprecated)]
-    std::env::home_dir().map(|h| h.join(".config").join("pomodorust").join("config.toml"))
+    xdg_base_dir(std::env::var_os("XDG_CONFIG_HOME"), home_dir(), ".config").map(|d| d.join("pomodorust").join("config.toml"))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
+impl Priority {
+    /// None → Low → Medium → High → None
+    pub fn cycle(current: Option<Priority>) -> Option<Priority> {
+        match current {
+            None => Some(Priority::Low),
+            Some(Priority::Low) => Some(Priority::Medium),
+            Some(Priority::Medium) => Some(Priority::High),
+            Some(Priority::High) => None,
+        }
+    }
+
+    pub fn marker(&self) -> &'static str {
+        match self {
+            Priority::Low => "!",
+            Priority::Medium => "!!",
+            Priority::High => "!!!",
+        }
+    }
+}
+
+/// User-picked label color for a task in the list.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum TaskColor {
+    Red,
+    Green,
+    Blue,
+    Yellow,
+    Magenta,
+    Cyan,
+}
+
+impl TaskColor {
+    pub const ALL: [TaskColor; 6] = [
+        TaskColor::Red,
+        TaskColor::Green,
+        TaskColor::Blue,
+        TaskColor::Yellow,
+        TaskColor::Magenta,
+        TaskColor::Cyan,
+    ];
+
+    /// None → Red → Green → Blue → Yellow → Magenta → Cyan → None
+    pub fn cycle(current: Option<TaskColor>) -> Option<TaskColor> {
+        match current {
+            None => Some(TaskColor::Red),
+            Some(TaskColor::Red) => Some(TaskColor::Green),
+            Some(TaskColor::Green) => Some(TaskColor::Blue),
+            Some(TaskColor::Blue) => Some(TaskColor::Yellow),
+            Some(TaskColor::Yellow) => Some(TaskColor::Magenta),
+            Some(TaskColor::Magenta) => Some(TaskColor::Cyan),
+            Some(TaskColor::Cyan) => None,
+        }
+    }
+}
+
+/// A named group of tasks. Tasks belong to a project through their `@name`
+/// tag (`Task::project`); the project adds a color and a place in the list.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct Project {
+    pub name: String,
+    pub color: TaskColor,
+}
+
+/// A finished segment in `App::session_log`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SessionEntry {
+    pub mode: Mode,
+    /// When the timer first started on the segment, so pauses are included.
+    pub started: DateTime<Utc>,
+    pub finished: DateTime<Utc>,
+    /// `Task::id`s of the focused tasks; empty for breaks.
+    pub task_ids: Vec<u64>,
+}
+
+/// How a task comes back once it is completed.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum RecurrenceRule {
+    Daily,
+    Weekly(Weekday),
+    /// Monday to Friday.
+    Weekdays,
+    /// Every N days.
+    Custom(u64),
+}
+
+impl RecurrenceRule {
+    /// None → Daily → Weekdays → Weekly on `today` → None. `Custom` intervals
+    /// come from editing state.json and go back to None.
+    pub fn cycle(current: Option<RecurrenceRule>, today: Weekday) -> Option<RecurrenceRule> {
+        match current {
+            None => Some(RecurrenceRule::Daily),
+            Some(RecurrenceRule::Daily) => Some(RecurrenceRule::Weekdays),
+            Some(RecurrenceRule::Weekdays) => Some(RecurrenceRule::Weekly(today)),
+            Some(RecurrenceRule::Weekly(_)) | Some(RecurrenceRule::Custom(_)) => None,
+        }
+    }
+
+    /// The first day the task is due again after being completed on `completed`.
+    pub fn next_date(&self, completed: NaiveDate) -> NaiveDate {
+        match self {
+            RecurrenceRule::Daily => completed + ChronoDuration::days(1),
+            RecurrenceRule::Weekly(day) => {
+                let ahead = (7 + day.num_days_from_monday() - completed.weekday().num_days_from_monday()) % 7;
+                completed + ChronoDuration::days(if ahead == 0 { 7 } else { ahead as i64 })
+            }
+            RecurrenceRule::Weekdays => {
+                let mut next = completed + ChronoDuration::days(1);
+                while matches!(next.weekday(), Weekday::Sat | Weekday::Sun) {
+                    next += ChronoDuration::days(1);
+                }
+                next
+            }
+            RecurrenceRule::Custom(days) => completed + ChronoDuration::days((*days).max(1) as i64),
+        }
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecurrenceRule::Daily => write!(f, "Daily"),
+            RecurrenceRule::Weekly(day) => write!(f, "Weekly on {}", day),
+            RecurrenceRule::Weekdays => write!(f, "Weekdays"),
+            RecurrenceRule::Custom(days) => write!(f, "Every {} days", days),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct SubTask {
+    pub name: String,
+    pub completed: bool,
+}
+
+static NEXT_TASK_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
+/// A task id not used by any task created or loaded so far.
+pub fn next_task_id() -> u64 {
+    NEXT_TASK_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Keeps `next_task_id` from handing out `id`, which a loaded task already has.
+pub fn reserve_task_id(id: u64) {
+    NEXT_TASK_ID.fetch_max(id + 1, std::sync::atomic::Ordering::Relaxed);
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Task {
+    /// Saved with the task, so the session log can refer to it and undo can
+    /// find it again after sorts and moves.
+    #[serde(default = "next_task_id")]
+    pub id: u64,
     pub name: String,
     #[serde(default)]
     pub notes: Option<String>,
     #[serde(default)]
     pub project: Option<String>,
+    #[serde(default)]
+    pub priority: Option<Priority>,
+    #[serde(default)]
+    pub subtasks: Vec<SubTask>,
+    /// Tickets, issues or docs related to the task.
+    #[serde(default)]
+    pub links: Vec<String>,
+    #[serde(default)]
+    pub color: Option<TaskColor>,
+    // Defaults below let hand-written or script-generated task lists give just a name
+    #[serde(default)]
     pub completed: bool,
+    #[serde(default)]
     pub pomodoros: u32,
+    #[serde(default, with = "duration_secs")]
     pub time_spent: Duration,
+    #[serde(default = "Utc::now")]
     pub creation_date: DateTime<Utc>,
     pub completion_date: Option<DateTime<Utc>>,
+    /// When the timer first ran on the task.
+    #[serde(default)]
+    pub start_date: Option<DateTime<Utc>>,
+    /// Hidden from the task list until this moment passes.
+    #[serde(default)]
+    pub deferred_until: Option<DateTime<Utc>>,
+    /// Completing the task queues a fresh copy for the next occurrence.
+    #[serde(default)]
+    pub recurrence: Option<RecurrenceRule>,
+    /// Planned pomodoros; the task list shows time spent against this.
+    #[serde(default)]
+    pub estimated_pomodoros: Option<u32>,
+    /// A divider row in the task list rather than a real task.
+    #[serde(default)]
+    pub is_separator: bool,
+    /// Focus time allowed on the task; reaching it pauses the timer.
+    #[serde(default, with = "duration_secs::option")]
+    pub time_budget: Option<Duration>,
+    /// Entries from `Settings::available_labels`, assigned by typing `@label`.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Issue key or URL in an external tracker, e.g. `PROJ-123`; opened with `o` in details.
+    #[serde(default)]
+    pub external_id: Option<String>,
+    /// When the timer last started on the task, for the timer view's session time.
+    #[serde(skip)]
+    pub session_start: Option<Instant>,
 }
 
 impl Task {
+    /// Divider for the task list; it is never selected, timed or completed.
+    /// Carries `project` so it stays in its group when the list is grouped.
+    pub fn separator(project: Option<String>) -> Self {
+        Self { is_separator: true, ..Task::new("---".to_string(), project) }
+    }
+
     pub fn new(name: String, project: Option<String>) -> Self {
         Self {
+            id: next_task_id(),
             name,
             notes: None,
             project,
+            priority: None,
+            subtasks: Vec::new(),
+            links: Vec::new(),
+            color: None,
             completed: false,
             pomodoros: 0,
             time_spent: Duration::from_secs(0),
             creation_date: Utc::now(),
             completion_date: None,
+            start_date: None,
+            deferred_until: None,
+            recurrence: None,
+            estimated_pomodoros: None,
+            is_separator: false,
+            time_budget: None,
+            labels: Vec::new(),
+            external_id: None,
+            session_start: None,
+        }
+    }
+
+    pub fn is_deferred(&self) -> bool {
+        self.deferred_until.is_some_and(|until| Utc::now() <= until)
+    }
+
+    /// Calendar time from first timer run to completion.
+    pub fn time_from_start_to_completion(&self) -> Option<Duration> {
+        let (start, end) = (self.start_date?, self.completion_date?);
+        end.signed_duration_since(start).to_std().ok()
+    }
+
+    /// Time since the timer last started on the task.
+    pub fn session_elapsed(&self) -> Option<Duration> {
+        self.session_start.map(|start| start.elapsed())
+    }
+
+    pub fn notes_word_count(&self) -> usize {
+        self.notes.as_deref().map_or(0, |n| n.split_whitespace().count())
+    }
+
+    pub fn notes_char_count(&self) -> usize {
+        self.notes.as_deref().map_or(0, |n| n.chars().count())
+    }
+
+    /// True only when there is at least one subtask and every one is checked off.
+    pub fn all_subtasks_complete(&self) -> bool {
+        !self.subtasks.is_empty() && self.subtasks.iter().all(|s| s.completed)
+    }
+}
+
+/// One line: `[✓] Fix login bug | 3● | 1h 25m`, with `est:N●` before the
+/// pomodoro count when the task has an estimate.
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {} | ", if self.completed { '✓' } else { ' ' }, self.name)?;
+        if let Some(estimate) = self.estimated_pomodoros {
+            write!(f, "est:{}● | ", estimate)?;
+        }
+        if self.time_spent.as_secs() >= 3600 {
+            write!(f, "{}● | {}", self.pomodoros, format_hm(self.time_spent))
+        } else {
+            write!(f, "{}● | {}m", self.pomodoros, self.time_spent.as_secs() / 60)
         }
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+/// Figures for the Statistics summary, from `App::compute_summary_stats`.
+#[derive(Debug, Default, PartialEq)]
+pub struct SummaryStats {
+    pub total_tasks: usize,
+    pub completed_tasks: usize,
+    /// Percentage of tasks completed; `None` with no tasks.
+    pub completion_rate: Option<f64>,
+    pub avg_pomodoros_per_task: Option<f64>,
+    /// Local hour (0–23) in which the most logged pomodoros started.
+    pub busiest_hour: Option<u32>,
+}
+
+/// Tally from `App::import_from_json`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub merged: usize,
+    /// Tasks whose name is already in the list.
+    pub skipped: usize,
+    /// Entries that are not valid tasks.
+    pub failed: usize,
+}
+
+/// Returned by `App::import_from_json` when the input is not a JSON array.
+#[derive(Debug)]
+pub struct ImportError {
+    pub error: serde_json::Error,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a JSON array of tasks: {}", self.error)
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default, Debug)]
 pub enum Mode {
     #[default]
     Pomodoro,
@@ -72,22 +400,47 @@ impl Mode {
         }
     }
 
-    pub fn title(&self) -> &'static str {
+    fn default_label(&self) -> &'static str {
         match self {
             Mode::Pomodoro => "Pomodoro",
             Mode::ShortBreak => "Short Break",
             Mode::LongBreak => "Long Break",
         }
     }
+
+    /// Name shown for the mode, renamed via `pomodoro_label` etc. in config.toml.
+    pub fn label<'a>(&self, settings: &'a Settings) -> &'a str {
+        let label = match self {
+            Mode::Pomodoro => &settings.pomodoro_label,
+            Mode::ShortBreak => &settings.short_break_label,
+            Mode::LongBreak => &settings.long_break_label,
+        };
+        if label.trim().is_empty() { self.default_label() } else { label }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.default_label())
+    }
 }
 
-#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default, Debug)]
 pub enum TimerState {
     #[default]
     Paused,
     Running,
 }
 
+impl fmt::Display for TimerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TimerState::Running => "Running",
+            TimerState::Paused => "Paused",
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy, Debug)]
 pub enum View {
     Timer,
@@ -96,22 +449,191 @@ pub enum View {
     Statistics,
     Settings,
     TaskDetails,
+    /// Right-click popup over the task list; `anchor` is the clicked cell.
+    #[serde(skip)]
+    ContextMenu {
+        options: &'static [ContextOption],
+        selected: usize,
+        target_task: usize,
+        anchor: (u16, u16),
+    },
+    /// `Ctrl+P` popup; `selected` indexes the currently matched commands.
+    #[serde(skip)]
+    CommandPalette { selected: usize },
+    /// `Ctrl+F` popup; `selected` indexes the current search results.
+    #[serde(skip)]
+    GlobalSearch { selected: usize },
+    /// `P` popup over the task list. Row 0 is "no project"; row `n` is `projects[n - 1]`.
+    #[serde(skip)]
+    ProjectManager { selected: usize },
+    /// `S` popup over the task list; `selected` indexes `SortCriterion::ALL`.
+    #[serde(skip)]
+    SortMenu { selected: usize },
+    /// "Edit Labels" popup over Settings; `selected` indexes `Settings::available_labels`.
+    #[serde(skip)]
+    LabelManager { selected: usize },
+    /// `P` popup over Settings; `selected` indexes `Settings::presets`.
+    #[serde(skip)]
+    PresetPicker { selected: usize },
+}
+
+impl View {
+    /// `Tab` order: Timer → TaskList → Statistics → Timer. Other views stay put.
+    pub fn tab_next(self) -> View {
+        match self {
+            View::Timer => View::TaskList,
+            View::TaskList => View::Statistics,
+            View::Statistics => View::Timer,
+            other => other,
+        }
+    }
+
+    /// `Shift+Tab`: the `tab_next` cycle in reverse.
+    pub fn tab_prev(self) -> View {
+        match self {
+            View::Timer => View::Statistics,
+            View::TaskList => View::Timer,
+            View::Statistics => View::TaskList,
+            other => other,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ContextOption {
+    Complete,
+    Rename,
+    Duplicate,
+    Delete,
+    SetPriority,
+    AddNote,
+}
+
+impl ContextOption {
+    pub const TASK_MENU: &'static [ContextOption] = &[
+        ContextOption::Complete,
+        ContextOption::Rename,
+        ContextOption::Duplicate,
+        ContextOption::Delete,
+        ContextOption::SetPriority,
+        ContextOption::AddNote,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContextOption::Complete => "Complete",
+            ContextOption::Rename => "Rename",
+            ContextOption::Duplicate => "Duplicate",
+            ContextOption::Delete => "Delete",
+            ContextOption::SetPriority => "Set Priority",
+            ContextOption::AddNote => "Add Note",
+        }
+    }
+}
+
+/// Which completed tasks Statistics lists and totals, by local completion day.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum StatisticsFilter {
+    #[default]
+    AllTime,
+    Today,
+    /// Monday of the current week through today.
+    ThisWeek,
+    ThisMonth,
+    /// Inclusive on both ends, picked with `F`.
+    CustomRange(NaiveDate, NaiveDate),
+}
+
+impl StatisticsFilter {
+    /// `f` cycles AllTime → Today → ThisWeek → ThisMonth; a custom range goes back to AllTime.
+    pub fn next(self) -> StatisticsFilter {
+        match self {
+            StatisticsFilter::AllTime => StatisticsFilter::Today,
+            StatisticsFilter::Today => StatisticsFilter::ThisWeek,
+            StatisticsFilter::ThisWeek => StatisticsFilter::ThisMonth,
+            StatisticsFilter::ThisMonth | StatisticsFilter::CustomRange(..) => StatisticsFilter::AllTime,
+        }
+    }
+
+    pub fn includes(self, day: NaiveDate, today: NaiveDate) -> bool {
+        match self {
+            StatisticsFilter::AllTime => true,
+            StatisticsFilter::Today => day == today,
+            StatisticsFilter::ThisWeek => {
+                let monday = today - ChronoDuration::days(today.weekday().num_days_from_monday() as i64);
+                (monday..=today).contains(&day)
+            }
+            StatisticsFilter::ThisMonth => day.year() == today.year() && day.month() == today.month() && day <= today,
+            StatisticsFilter::CustomRange(start, end) => (start..=end).contains(&day),
+        }
+    }
+
+    /// Whether `task` was completed inside the filter's range.
+    pub fn includes_task(self, task: &Task, today: NaiveDate) -> bool {
+        match self {
+            StatisticsFilter::AllTime => true,
+            _ => task.completion_date
+                .is_some_and(|dt| self.includes(dt.with_timezone(&Local).date_naive(), today)),
+        }
+    }
+}
+
+impl fmt::Display for StatisticsFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatisticsFilter::AllTime => f.write_str("All Time"),
+            StatisticsFilter::Today => f.write_str("Today"),
+            StatisticsFilter::ThisWeek => f.write_str("This Week"),
+            StatisticsFilter::ThisMonth => f.write_str("This Month"),
+            StatisticsFilter::CustomRange(start, end) => {
+                write!(f, "{} – {}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"))
+            }
+        }
+    }
+}
+
+/// Sent to the main loop from background threads, e.g. a clicked notification action.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AppMessage {
+    /// Start (un-pause) the segment that is now current.
+    StartTimer,
+    /// The screen locked: stop counting and save.
+    PauseAndSave,
+    /// The screen unlocked.
+    NotifyResumed,
 }
 
-#[derive(Default)]
+#[derive(Default, Debug)]
 pub enum InputMode {
     #[default]
     Normal,
     Editing,
     Filtering,
     EditingNotes,
+    EditingSubtask,
+    EditingLink,
+    /// Naming a new project in the project manager.
+    EditingProject,
+    /// Naming a new label in the label manager.
+    EditingLabel,
+    /// Naming a preset for the current timer settings in the preset picker.
+    EditingPreset,
+    /// `Ctrl+G` in the task list: typing the 1-based number of a task to jump to.
+    GoTo,
+    /// `F` in Statistics: typing the start, then the end date of a custom range.
+    EditingStatsRange,
+    /// `b` in the task list: minutes of focus time allowed on the selected task.
+    EditingBudget,
+    /// `Ctrl+N` from the timer: name a task, then it becomes active and the timer starts.
+    QuickStart,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct App {
     pub mode: Mode,
     pub state: TimerState,
+    #[serde(with = "duration_secs")]
     pub time_remaining: Duration,
     pub pomodoros_completed_total: u32,
     #[serde(skip)]
@@ -119,10 +641,84 @@ pub struct App {
     pub current_view: View,
     pub tasks: Vec<Task>,
     pub active_task_index: Option<usize>,
+    /// Tasks sharing focus time while `settings.parallel_mode` is on.
+    #[serde(default)]
+    pub active_task_indices: HashSet<usize>,
+    /// Task list groups follow this order.
+    #[serde(default)]
+    pub projects: Vec<Project>,
+    /// Every finished segment, oldest first, for the sparkline, the work log and
+    /// the summary. Kept in the database's `sessions` table, not in state files.
+    #[serde(skip)]
+    pub session_log: Vec<SessionEntry>,
+    /// When the timer first started on the current segment; cleared by `reset_timer`.
+    #[serde(skip)]
+    pub segment_started: Option<DateTime<Utc>>,
+    /// Show deferred tasks in the task list instead of hiding them.
+    #[serde(skip)]
+    pub show_deferred: bool,
+    /// Completion period Statistics lists and totals; cycled with `f`.
+    #[serde(skip)]
+    pub stats_filter: StatisticsFilter,
+    /// Something `save` writes changed since the last save. Set by the timer
+    /// and remote commands; autosave catches other edits with `state_digest`.
+    #[serde(skip)]
+    pub dirty: bool,
+    /// Statistics leaves out completed tasks with no pomodoros; toggled with `Ctrl+H`.
+    #[serde(skip)]
+    pub hide_zero_pomodoro_tasks: bool,
+    /// Last task deleted from Statistics and where it was, for `Ctrl+U`.
+    #[serde(skip)]
+    pub recently_deleted_task: Option<(Task, usize)>,
+    /// A segment ran out in the timer view and its summary popup is showing;
+    /// the next segment waits until it is dismissed.
+    #[serde(skip)]
+    pub session_summary_visible: bool,
+    #[serde(skip)]
+    pub session_summary_timer: Option<Instant>,
+    /// Time left on a pomodoro interrupted by `Ctrl+B`; it resumes from here
+    /// when the unscheduled break ends.
+    #[serde(skip)]
+    pub saved_pomodoro_remaining: Option<Duration>,
+    /// Outgoing mode and when it ended, so the timer can flash its color.
+    #[serde(skip)]
+    pub mode_transition: Option<(Mode, Instant)>,
+    /// Task whose details are unfolded under its row in the task list.
+    #[serde(skip)]
+    pub inline_expanded_task: Option<usize>,
     #[serde(skip)]
     pub settings: Settings,
 }
 
+/// The timer and task state that user actions change, in a form that is cheap
+/// to compare in tests and to log. Dates and UI-only fields are left out.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct AppSnapshot {
+    pub mode: Mode,
+    pub state: TimerState,
+    pub time_remaining_secs: u64,
+    pub pomodoros_completed_total: u32,
+    pub active_task_index: Option<usize>,
+    pub tasks: Vec<TaskSnapshot>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TaskSnapshot {
+    pub name: String,
+    pub completed: bool,
+    pub pomodoros: u32,
+    pub time_spent_secs: u64,
+}
+
+/// Pomodoro counts and time spent as they were before `reset_all_task_timers`,
+/// kept so the reset can be undone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskTimers {
+    /// Pomodoros and time spent by `Task::id`.
+    per_task: Vec<(u64, u32, Duration)>,
+    pomodoros_completed_total: u32,
+}
+
 pub(super) fn bump_duration_mins(d: Duration, delta: i64) -> Duration {
     let mins = (d.as_secs() / 60) as i64;
     Duration::from_secs((mins + delta).max(1) as u64 * 60)
@@ -140,6 +736,20 @@ impl Default for App {
             current_view: View::TaskList,
             tasks: vec![],
             active_task_index: None,
+            active_task_indices: HashSet::new(),
+            projects: Vec::new(),
+            session_log: Vec::new(),
+            segment_started: None,
+            show_deferred: false,
+            stats_filter: StatisticsFilter::AllTime,
+            dirty: false,
+            hide_zero_pomodoro_tasks: false,
+            recently_deleted_task: None,
+            session_summary_visible: false,
+            session_summary_timer: None,
+            saved_pomodoro_remaining: None,
+            mode_transition: None,
+            inline_expanded_task: None,
             settings,
         }
     }
@@ -156,14 +766,17 @@ impl App {
                 // One-time migration from legacy JSON on first run
                 if is_new_db {
                     if let Some(legacy) = Self::try_load_json() {
+                        tracing::debug!(tasks = legacy.tasks.len(), "migrating legacy state.json");
                         let _ = crate::db::save_to(&mut conn, &legacy);
                         let mut app = legacy;
+                        app.current_view = settings.start_view;
                         app.settings = settings;
                         app.time_remaining = app.mode.duration(&app.settings);
                         return app;
                     }
                 }
                 let s = crate::db::load_from(&conn);
+                tracing::debug!(path = %db_path.display(), tasks = s.tasks.len(), "loaded state");
                 let time_remaining = s.time_remaining_secs
                     .map(Duration::from_secs)
                     .unwrap_or_else(|| s.mode.duration(&settings));
@@ -173,31 +786,120 @@ impl App {
                     time_remaining,
                     pomodoros_completed_total: s.pomodoros_total,
                     should_quit: false,
-                    current_view: s.current_view,
+                    current_view: settings.start_view,
                     tasks: s.tasks,
                     active_task_index: s.active_task_index,
+                    active_task_indices: s.active_task_indices,
+                    projects: s.projects,
+                    session_log: s.session_log,
+                    segment_started: None,
+                    show_deferred: false,
+                    stats_filter: StatisticsFilter::AllTime,
+                    dirty: false,
+                    hide_zero_pomodoro_tasks: false,
+                    recently_deleted_task: None,
+                    session_summary_visible: false,
+                    session_summary_timer: None,
+                    saved_pomodoro_remaining: None,
+                    mode_transition: None,
+                    inline_expanded_task: None,
                     settings,
                 };
             }
         }
         let mut app = App::default();
+        app.current_view = settings.start_view;
         app.settings = settings;
         app.time_remaining = app.mode.duration(&app.settings);
         app
     }
 
+    /// Reserves the ids of tasks read from a state file, giving a fresh one to
+    /// any id that a hand edit (e.g. a copied task) has repeated.
+    pub fn claim_task_ids(&mut self) {
+        for task in &self.tasks {
+            reserve_task_id(task.id);
+        }
+        let mut seen = HashSet::new();
+        for task in &mut self.tasks {
+            if !seen.insert(task.id) {
+                task.id = next_task_id();
+            }
+        }
+    }
+
     fn try_load_json() -> Option<Self> {
-        let path = get_data_path()?;
-        let data = fs::read_to_string(path).ok()?;
-        serde_json::from_str(&data).ok()
+        let path = persistence::state_path(StateFormat::Json)?;
+        persistence::load_state(&path).ok()
+    }
+
+    /// Appends tasks from a JSON array, skipping any whose name is already in
+    /// the list. Entries that don't parse as tasks are counted, not fatal.
+    pub fn import_from_json(&mut self, json: &str) -> Result<ImportSummary, ImportError> {
+        let entries: Vec<serde_json::Value> = serde_json::from_str(json).map_err(|error| ImportError { error })?;
+        let mut summary = ImportSummary::default();
+        for entry in entries {
+            match serde_json::from_value::<Task>(entry) {
+                Ok(task) if self.tasks.iter().any(|t| t.name == task.name) => summary.skipped += 1,
+                Ok(mut task) => {
+                    // Ids from another list would clash with ours
+                    task.id = next_task_id();
+                    self.tasks.push(task);
+                    summary.merged += 1;
+                }
+                Err(_) => summary.failed += 1,
+            }
+        }
+        tracing::debug!(?summary, "merged tasks");
+        Ok(summary)
+    }
+
+    /// Writes the current state to `state.json` or `state.toml` so it can be edited by hand.
+    pub fn export_state(&self, format: StateFormat) -> io::Result<PathBuf> {
+        let path = persistence::state_path(format)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory"))?;
+        persistence::save_state(self, format, &path)?;
+        Ok(path)
+    }
+
+    /// Stores a hand-edited state file in the database and reloads from it,
+    /// keeping the current view and settings. On error nothing is changed.
+    pub fn import_state(&mut self, path: &Path) -> Result<(), String> {
+        let db_path = get_db_path().ok_or("no home directory")?;
+        let mut edited = persistence::load_state(path).map_err(|e| e.to_string())?;
+        let task_count = edited.tasks.len();
+        edited.active_task_index = edited.active_task_index.filter(|&i| i < task_count);
+        edited.active_task_indices.retain(|&i| i < task_count);
+
+        let mut conn = crate::db::open_and_init(&db_path).map_err(|e| e.to_string())?;
+        crate::db::save_to(&mut conn, &edited).map_err(|e| e.to_string())?;
+        tracing::debug!(path = %path.display(), tasks = task_count, "imported edited state");
+
+        let view = self.current_view;
+        *self = App::load_with_settings(self.settings.clone());
+        self.current_view = view;
+        self.process_recurring_tasks();
+        self.register_task_projects();
+        // The legacy migration would pick a stale copy up if the database is ever removed
+        let _ = fs::remove_file(path);
+        Ok(())
     }
 
-    pub fn save(&self) {
+    pub fn save(&mut self) {
+        self.dirty = false;
         if let Some(db_path) = get_db_path() {
+            tracing::debug!(path = %db_path.display(), tasks = self.tasks.len(), "saving state");
             if let Some(parent) = db_path.parent() {
-                if fs::create_dir_all(parent).is_ok() {
-                    if let Ok(mut conn) = crate::db::open_and_init(&db_path) {
-                        let _ = crate::db::save_to(&mut conn, self);
+                if let Err(e) = fs::create_dir_all(parent) {
+                    tracing::error!(error = %e, "could not create data directory");
+                } else {
+                    match crate::db::open_and_init(&db_path) {
+                        Ok(mut conn) => {
+                            if let Err(e) = crate::db::save_to(&mut conn, self) {
+                                tracing::error!(error = %e, "could not save state");
+                            }
+                        }
+                        Err(e) => tracing::error!(error = %e, "could not open database"),
                     }
                 }
             }
@@ -205,104 +907,1024 @@ impl App {
         self.settings.save();
     }
 
-    pub fn toggle_timer(&mut self) {
-        if let Some(index) = self.active_task_index {
-            if !self.tasks[index].completed {
-                match self.state {
-                    TimerState::Paused => self.state = TimerState::Running,
-                    TimerState::Running => self.state = TimerState::Paused,
-                }
-            }
+    pub fn snapshot(&self) -> AppSnapshot {
+        AppSnapshot {
+            mode: self.mode,
+            state: self.state,
+            time_remaining_secs: self.time_remaining.as_secs(),
+            pomodoros_completed_total: self.pomodoros_completed_total,
+            active_task_index: self.active_task_index,
+            tasks: self.tasks.iter()
+                .map(|t| TaskSnapshot {
+                    name: t.name.clone(),
+                    completed: t.completed,
+                    pomodoros: t.pomodoros,
+                    time_spent_secs: t.time_spent.as_secs(),
+                })
+                .collect(),
         }
     }
 
-    pub fn reset_timer(&mut self) {
-        self.state = TimerState::Paused;
-        self.time_remaining = self.mode.duration(&self.settings);
+    /// Tasks that receive focus time and pomodoros: every parallel task when
+    /// parallel mode is on and some are marked, otherwise just the selected one.
+    pub fn focused_task_indices(&self) -> Vec<usize> {
+        if self.settings.parallel_mode && !self.active_task_indices.is_empty() {
+            let mut indices: Vec<usize> = self.active_task_indices.iter().copied().collect();
+            indices.sort_unstable();
+            indices
+        } else {
+            self.active_task_index.into_iter().collect()
+        }
     }
 
-    pub fn next_mode(&mut self) -> Mode {
-        let previous_mode = self.mode;
-        if self.mode == Mode::Pomodoro {
-            self.pomodoros_completed_total += 1;
-            if let Some(index) = self.active_task_index {
-                if let Some(task) = self.tasks.get_mut(index) {
-                    task.pomodoros += 1;
-                }
-            }
-            let interval = self.settings.long_break_interval.max(1) as u32;
-            if self.pomodoros_completed_total % interval == 0 {
-                self.mode = Mode::LongBreak;
-            } else {
-                self.mode = Mode::ShortBreak;
-            }
-        } else {
-            self.mode = Mode::Pomodoro;
+    /// Visible, uncompleted tasks in list order, grouped by project once any
+    /// projects exist. Untagged tasks come last.
+    pub fn active_tasks(&self) -> Vec<(usize, &Task)> {
+        let mut tasks: Vec<_> = self.tasks.iter().enumerate()
+            .filter(|(_, t)| !t.completed && (self.show_deferred || !t.is_deferred()))
+            .collect();
+        // Stable, so list order still holds within each group
+        if !self.projects.is_empty() {
+            tasks.sort_by_key(|(_, t)| self.project_rank(t));
         }
-        self.reset_timer();
-        if let Some(index) = self.active_task_index {
-            if !self.tasks[index].completed {
-                self.state = TimerState::Running;
-            }
+        tasks
+    }
+
+    /// Zeroes pomodoros and time spent on every task, active and completed, and
+    /// the all-time pomodoro count. Returns what was there for `restore_task_timers`.
+    pub fn reset_all_task_timers(&mut self) -> TaskTimers {
+        let previous = TaskTimers {
+            per_task: self.tasks.iter().map(|t| (t.id, t.pomodoros, t.time_spent)).collect(),
+            pomodoros_completed_total: self.pomodoros_completed_total,
+        };
+        for task in &mut self.tasks {
+            task.pomodoros = 0;
+            task.time_spent = Duration::ZERO;
         }
-        previous_mode
+        self.pomodoros_completed_total = 0;
+        previous
     }
 
-    pub fn skip_segment(&mut self) -> Mode {
-        let previous_mode = self.mode;
-        if self.mode == Mode::Pomodoro {
-            let interval = self.settings.long_break_interval.max(1) as u32;
-            if (self.pomodoros_completed_total + 1) % interval == 0 {
-                self.mode = Mode::LongBreak;
-            } else {
-                self.mode = Mode::ShortBreak;
+    /// Puts back timers saved by `reset_all_task_timers`, matching tasks by id
+    /// so sorting or moving them in between is fine. Tasks deleted since are
+    /// skipped; refused (returns false) when none of them are left.
+    pub fn restore_task_timers(&mut self, timers: TaskTimers) -> bool {
+        let mut restored = false;
+        for (id, pomodoros, time_spent) in timers.per_task {
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                task.pomodoros = pomodoros;
+                task.time_spent = time_spent;
+                restored = true;
             }
-        } else {
-            self.mode = Mode::Pomodoro;
         }
-        self.reset_timer();
-        if let Some(index) = self.active_task_index {
-            if !self.tasks[index].completed {
-                self.state = TimerState::Running;
+        if restored {
+            self.pomodoros_completed_total = timers.pomodoros_completed_total;
+        }
+        restored
+    }
+
+    /// Hash of the state and settings `save` writes, so autosave can tell
+    /// whether anything changed since the last save. Both are streamed into
+    /// the hasher rather than built up as strings.
+    pub fn state_digest(&self) -> u64 {
+        use std::hash::Hasher;
+        use std::io::Write;
+
+        struct HashWriter<H: Hasher>(H);
+        impl<H: Hasher> Write for HashWriter<H> {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.write(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
             }
         }
-        previous_mode
+
+        let mut writer = HashWriter(std::collections::hash_map::DefaultHasher::new());
+        let _ = serde_json::to_writer(&mut writer, self);
+        let _ = write!(writer, "{:?}", self.settings);
+        writer.0.finish()
     }
 
-    pub fn complete_active_task(&mut self) {
-        if let Some(index) = self.active_task_index {
+    /// Adds a tick's worth of focus time to the tasks being timed. If that
+    /// takes one past its time budget the timer is paused and its index returned.
+    pub fn record_focus_time(&mut self, elapsed: Duration) -> Option<usize> {
+        self.dirty = true;
+        let mut exceeded = None;
+        for index in self.focused_task_indices() {
             if let Some(task) = self.tasks.get_mut(index) {
-                task.completed = !task.completed;
-                if task.completed {
-                    task.completion_date = Some(Utc::now());
-                    self.state = TimerState::Paused;
-                    self.reset_timer();
-                    self.active_task_index = self.tasks.iter().enumerate()
-                        .find(|(_, t)| !t.completed)
-                        .map(|(i, _)| i);
-                } else {
-                    task.completion_date = None;
+                let before = task.time_spent;
+                task.time_spent += elapsed;
+                if task.time_budget.is_some_and(|budget| before < budget && task.time_spent >= budget) {
+                    exceeded.get_or_insert(index);
                 }
             }
         }
+        if exceeded.is_some() {
+            self.pause_timer();
+        }
+        exceeded
     }
 
-    pub fn delete_active_task(&mut self) {
-        if let Some(index) = self.active_task_index {
-            self.tasks.remove(index);
-            self.state = TimerState::Paused;
+    /// Sets or, with `None` or zero minutes, clears the active task's time budget.
+    pub fn set_active_task_budget(&mut self, minutes: Option<u64>) {
+        if let Some(task) = self.active_task_index.and_then(|i| self.tasks.get_mut(i)) {
+            task.time_budget = minutes.filter(|&m| m > 0).map(|m| Duration::from_secs(m * 60));
+        }
+    }
+
+    /// Indices of active tasks that have used up their pomodoro estimate.
+    pub fn tasks_with_overdue_estimates(&self) -> Vec<usize> {
+        self.tasks.iter().enumerate()
+            .filter(|(_, t)| !t.completed && t.pomodoros >= t.estimated_pomodoros.unwrap_or(u32::MAX))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Selects the `n`th task (1-based) as listed in the task list under the
+    /// lowercase `/` `filter`, not counting separators. False when there is no such task.
+    pub fn select_task_by_number(&mut self, n: usize, filter: &str) -> bool {
+        let index = n.checked_sub(1).and_then(|n| {
+            self.active_tasks()
+                .into_iter()
+                .filter(|(_, t)| !t.is_separator && (filter.is_empty() || ui_state::task_matches_filter(t, filter)))
+                .nth(n)
+                .map(|(i, _)| i)
+        });
+        if index.is_some() {
+            self.active_task_index = index;
+        }
+        index.is_some()
+    }
+
+    /// Active tasks as a numbered plain-text list, one per line, e.g.
+    /// `1. [ ] Report | est:4● | 3● | 2h 15m`.
+    pub fn active_tasks_text(&self) -> String {
+        self.active_tasks()
+            .into_iter()
+            .filter(|(_, t)| !t.is_separator)
+            .enumerate()
+            .map(|(n, (_, t))| format!("{}. {}", n + 1, t))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Position of the task's project in `projects`; untagged or unknown tags sort last.
+    fn project_rank(&self, task: &Task) -> usize {
+        task.project.as_deref()
+            .and_then(|name| self.projects.iter().position(|p| p.name == name))
+            .unwrap_or(self.projects.len())
+    }
+
+    pub fn project(&self, name: &str) -> Option<&Project> {
+        self.projects.iter().find(|p| p.name == name)
+    }
+
+    /// Adds a project with the next color in rotation. Whitespace becomes `-`
+    /// so the name still works as an `@tag`. Returns false for blank or
+    /// existing names.
+    pub fn add_project(&mut self, name: &str) -> bool {
+        let name = name.trim().trim_start_matches('@').split_whitespace().collect::<Vec<_>>().join("-");
+        if name.is_empty() || self.project(&name).is_some() {
+            return false;
+        }
+        let color = TaskColor::ALL[self.projects.len() % TaskColor::ALL.len()];
+        self.projects.push(Project { name, color });
+        true
+    }
+
+    /// Gives every `@tag` in use a project, e.g. for tags typed in the task input.
+    pub fn register_task_projects(&mut self) {
+        let tags: Vec<String> = self.tasks.iter().filter_map(|t| t.project.clone()).collect();
+        for tag in tags {
+            self.add_project(&tag);
+        }
+    }
+
+    /// Adds a label to the settings' list; false when blank or already there.
+    pub fn add_label(&mut self, name: &str) -> bool {
+        let name = name.trim().trim_start_matches('@').split_whitespace().collect::<Vec<_>>().join("-");
+        if name.is_empty() || self.settings.available_labels.iter().any(|l| l.eq_ignore_ascii_case(&name)) {
+            return false;
+        }
+        self.settings.available_labels.push(name);
+        true
+    }
+
+    /// Removes the label from the list and from every task carrying it.
+    pub fn delete_label(&mut self, index: usize) {
+        if index >= self.settings.available_labels.len() {
+            return;
+        }
+        let label = self.settings.available_labels.remove(index);
+        for task in &mut self.tasks {
+            task.labels.retain(|l| *l != label);
+        }
+    }
+
+    /// Saves the timer settings as preset `name` and writes config.toml; false when blank.
+    pub fn save_settings_preset(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() {
+            return false;
+        }
+        self.settings.save_preset(name);
+        self.settings.save();
+        true
+    }
+
+    /// Switches to the preset's timer settings; the current segment restarts
+    /// with the new length unless the timer is running.
+    pub fn apply_settings_preset(&mut self, name: &str) -> bool {
+        let Some(settings) = self.settings.load_preset(name) else { return false };
+        self.settings = settings;
+        self.settings.save();
+        if self.state == TimerState::Paused {
             self.reset_timer();
-            self.active_task_index = self.tasks.iter().enumerate()
-                .find(|(_, t)| !t.completed)
-                .map(|(i, _)| i);
         }
+        true
+    }
+
+    pub fn delete_settings_preset(&mut self, index: usize) {
+        if index < self.settings.presets.len() {
+            self.settings.presets.remove(index);
+            self.settings.save();
+        }
+    }
+
+    /// Removes the project and untags its tasks.
+    pub fn delete_project(&mut self, index: usize) {
+        if index >= self.projects.len() {
+            return;
+        }
+        let project = self.projects.remove(index);
+        for task in &mut self.tasks {
+            if task.project.as_deref() == Some(project.name.as_str()) {
+                task.project = None;
+            }
+        }
+    }
+
+    pub fn cycle_project_color(&mut self, index: usize) {
+        if let Some(project) = self.projects.get_mut(index) {
+            project.color = TaskColor::cycle(Some(project.color)).unwrap_or(TaskColor::Red);
+        }
+    }
+
+    pub fn set_task_project(&mut self, index: usize, project: Option<String>) {
+        if let Some(task) = self.tasks.get_mut(index) {
+            task.project = project;
+        }
+    }
+
+    /// Pomodoros logged on each project's tasks, in project order.
+    pub fn project_pomodoros(&self) -> Vec<(&Project, u32)> {
+        self.projects.iter()
+            .map(|p| {
+                let total = self.tasks.iter()
+                    .filter(|t| t.project.as_deref() == Some(p.name.as_str()))
+                    .map(|t| t.pomodoros)
+                    .sum();
+                (p, total)
+            })
+            .collect()
+    }
+
+    /// Active and completed tasks whose name, notes or `@project` tag contain
+    /// `query`, case-insensitively. Active tasks come first, each group in list order.
+    pub fn search_tasks(&self, query: &str) -> Vec<(usize, &Task)> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut results: Vec<(usize, &Task)> = self.tasks.iter().enumerate()
+            .filter(|(_, t)| ui_state::task_matches_filter(t, &query))
+            .collect();
+        results.sort_by_key(|(_, t)| t.completed);
+        results
+    }
+
+    pub fn deferred_task_count(&self) -> usize {
+        self.tasks.iter().filter(|t| !t.completed && t.is_deferred()).count()
+    }
+
+    /// Unfolds the selected task's details in the list, or folds them again.
+    pub fn toggle_inline_details(&mut self) {
+        self.inline_expanded_task = if self.inline_expanded_task == self.active_task_index {
+            None
+        } else {
+            self.active_task_index
+        };
+    }
+
+    /// Hides the task for a day, or brings back a task that is already deferred.
+    pub fn toggle_task_deferred(&mut self, index: usize) {
+        let Some(task) = self.tasks.get_mut(index) else { return };
+        if task.is_deferred() {
+            task.deferred_until = None;
+            return;
+        }
+        task.deferred_until = Some(Utc::now() + ChronoDuration::days(1));
+        if !self.show_deferred && self.active_task_index == Some(index) {
+            self.next_task();
+        }
+    }
+
+    pub fn toggle_task_active(&mut self, index: usize) {
+        if index >= self.tasks.len() {
+            return;
+        }
+        if !self.active_task_indices.remove(&index) {
+            self.active_task_indices.insert(index);
+        }
+    }
+
+    /// Keeps `active_task_indices` and `inline_expanded_task` pointing at the same
+    /// tasks after the list is reshuffled; `f` maps an old index to its new one,
+    /// or `None` if it is gone.
+    fn remap_active_indices(&mut self, f: impl Fn(usize) -> Option<usize>) {
+        self.active_task_indices = self.active_task_indices.iter().filter_map(|&i| f(i)).collect();
+        self.inline_expanded_task = self.inline_expanded_task.and_then(&f);
+    }
+
+    /// Sorts the whole list by `criterion`, active tasks before completed ones.
+    /// Separators sink to the bottom of the active tasks. The stable sort keeps
+    /// the current order among ties, and selection follows the same tasks.
+    pub fn reorder_tasks_by(&mut self, criterion: SortCriterion) {
+        let mut order: Vec<usize> = (0..self.tasks.len()).collect();
+        let tasks = &self.tasks;
+        order.sort_by(|&a, &b| {
+            let (x, y) = (&tasks[a], &tasks[b]);
+            (x.completed, x.is_separator).cmp(&(y.completed, y.is_separator)).then_with(|| match criterion {
+                SortCriterion::Name => x.name.to_lowercase().cmp(&y.name.to_lowercase()),
+                // `None` sorts below every priority, so reverse puts it last
+                SortCriterion::Priority => y.priority.cmp(&x.priority),
+                SortCriterion::CreationDate => x.creation_date.cmp(&y.creation_date),
+                SortCriterion::TimeSpent => y.time_spent.cmp(&x.time_spent),
+                SortCriterion::PomodoroCount => y.pomodoros.cmp(&x.pomodoros),
+            })
+        });
+        let mut new_position = vec![0; order.len()];
+        for (new, &old) in order.iter().enumerate() {
+            new_position[old] = new;
+        }
+        let mut slots: Vec<Option<Task>> = self.tasks.drain(..).map(Some).collect();
+        self.tasks = order.iter().filter_map(|&old| slots[old].take()).collect();
+        self.active_task_index = self.active_task_index.map(|i| new_position[i]);
+        self.remap_active_indices(|i| new_position.get(i).copied());
+        self.settings.default_sort = criterion;
+    }
+
+    /// Any deletion makes the previous one no longer undoable.
+    pub fn remove_task(&mut self, index: usize) -> Task {
+        self.recently_deleted_task = None;
+        let task = self.tasks.remove(index);
+        if let Some(active) = self.active_task_index {
+            if active > index {
+                self.active_task_index = Some(active - 1);
+            }
+        }
+        self.remap_active_indices(|i| match i.cmp(&index) {
+            std::cmp::Ordering::Less => Some(i),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Greater => Some(i - 1),
+        });
+        task
+    }
+
+    /// Deletes the task but keeps it for `undo_last_deletion`.
+    pub fn remove_task_undoable(&mut self, index: usize) {
+        let task = self.remove_task(index);
+        self.recently_deleted_task = Some((task, index));
+    }
+
+    /// Puts the last undoable deletion back where it was; false if there is none.
+    pub fn undo_last_deletion(&mut self) -> bool {
+        let Some((task, index)) = self.recently_deleted_task.take() else { return false };
+        let index = index.min(self.tasks.len());
+        self.tasks.insert(index, task);
+        if let Some(active) = self.active_task_index {
+            if active >= index {
+                self.active_task_index = Some(active + 1);
+            }
+        }
+        self.remap_active_indices(|i| Some(if i >= index { i + 1 } else { i }));
+        true
+    }
+
+    fn swap_tasks(&mut self, a: usize, b: usize) {
+        self.tasks.swap(a, b);
+        self.remap_active_indices(|i| Some(if i == a { b } else if i == b { a } else { i }));
+    }
+
+    /// Sets the timer running. During a pomodoro this stamps `start_date` on
+    /// focused tasks the timer hasn't run on before and starts their
+    /// `session_start`. Every way of starting the timer (toggle, quick start,
+    /// auto start, notification, HTTP) comes here.
+    pub fn start_timer(&mut self) {
+        self.state = TimerState::Running;
+        self.segment_started.get_or_insert_with(Utc::now);
+        if self.mode != Mode::Pomodoro {
+            return;
+        }
+        let (now, started) = (Utc::now(), Instant::now());
+        for i in self.focused_task_indices() {
+            if let Some(task) = self.tasks.get_mut(i) {
+                task.start_date.get_or_insert(now);
+                task.session_start.get_or_insert(started);
+            }
+        }
+    }
+
+    /// Pauses the timer and ends every task's session; the counterpart of `start_timer`.
+    pub fn pause_timer(&mut self) {
+        self.state = TimerState::Paused;
+        for task in &mut self.tasks {
+            task.session_start = None;
+        }
+    }
+
+    pub fn toggle_timer(&mut self) {
+        if let Some(index) = self.active_task_index {
+            if !self.tasks[index].completed {
+                match self.state {
+                    TimerState::Paused => self.start_timer(),
+                    TimerState::Running => self.pause_timer(),
+                }
+            }
+        }
+    }
+
+    /// `MM:SS` of the current segment; minutes keep counting past 59.
+    pub fn time_remaining_formatted(&self) -> String {
+        match ChronoDuration::from_std(self.time_remaining) {
+            Ok(t) => format!("{:02}:{:02}", t.num_minutes(), t.num_seconds() % 60),
+            Err(_) => "??:??".to_string(),
+        }
+    }
+
+    /// Whole minutes left, rounded up so `01` stays up until the timer hits zero.
+    pub fn time_remaining_formatted_minutes(&self) -> String {
+        format!("{:02}", self.time_remaining.as_secs().div_ceil(60))
+    }
+
+    /// `HH:MM:SS` of the current segment.
+    pub fn time_remaining_formatted_hms(&self) -> String {
+        match ChronoDuration::from_std(self.time_remaining) {
+            Ok(t) => format!(
+                "{:02}:{:02}:{:02}",
+                t.num_hours(),
+                t.num_minutes() % 60,
+                t.num_seconds() % 60
+            ),
+            Err(_) => "??:??:??".to_string(),
+        }
+    }
+
+    /// Length of one full cycle: `long_break_interval` pomodoros, the short
+    /// breaks between them, and the closing long break.
+    pub fn pomodoro_set_duration(&self) -> Duration {
+        let s = &self.settings;
+        let pomodoros = s.long_break_interval.max(1);
+        s.pomodoro_duration * pomodoros + s.short_break_duration * (pomodoros - 1) + s.long_break_duration
+    }
+
+    /// Time left until the current set's long break is over, counting the
+    /// pomodoros already completed in this set and the running segment.
+    pub fn estimated_time_to_complete_current_set(&self) -> Duration {
+        let s = &self.settings;
+        let interval = s.long_break_interval.max(1);
+        let done = self.pomodoros_completed_total % interval;
+        match self.mode {
+            Mode::LongBreak => self.time_remaining,
+            Mode::Pomodoro => {
+                let after_this = interval - done - 1;
+                self.time_remaining
+                    + (s.pomodoro_duration + s.short_break_duration) * after_this
+                    + s.long_break_duration
+            }
+            Mode::ShortBreak => {
+                let left = interval.saturating_sub(done).max(1);
+                self.time_remaining
+                    + s.pomodoro_duration * left
+                    + s.short_break_duration * (left - 1)
+                    + s.long_break_duration
+            }
+        }
+    }
+
+    /// When every active task should be done: each needs its estimate minus the
+    /// pomodoros already spent (at least one, one if unestimated), with the
+    /// scheduled breaks in between. `None` without active tasks.
+    pub fn estimated_completion_time(&self) -> Option<DateTime<Utc>> {
+        self.estimated_completion_time_from(Utc::now())
+    }
+
+    fn estimated_completion_time_from(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let remaining: u32 = self.tasks.iter()
+            .filter(|t| !t.completed && !t.is_deferred() && !t.is_separator)
+            .map(|t| t.estimated_pomodoros.unwrap_or(1).saturating_sub(t.pomodoros).max(1))
+            .sum();
+        if remaining == 0 {
+            return None;
+        }
+        let s = &self.settings;
+        // The running segment counts with what's left of it
+        let mut total = match self.mode {
+            Mode::Pomodoro => self.time_remaining + s.pomodoro_duration * (remaining - 1),
+            Mode::ShortBreak | Mode::LongBreak => self.time_remaining + s.pomodoro_duration * remaining,
+        };
+        for n in 1..remaining {
+            let in_set = self.pomodoros_in_set(self.pomodoros_completed_total + n);
+            total += s.next_break_mode(in_set).duration(s);
+        }
+        ChronoDuration::from_std(total).ok().map(|d| now + d)
+    }
+
+    /// Puts every setting back to its default, writes the config file and
+    /// restarts the current segment with the default duration.
+    pub fn restore_default_settings(&mut self) {
+        // Labels are still on tasks, so the list survives a reset; so do presets
+        let available_labels = std::mem::take(&mut self.settings.available_labels);
+        let presets = std::mem::take(&mut self.settings.presets);
+        self.settings = Settings { available_labels, presets, ..Settings::default() };
+        self.settings.save();
+        self.reset_timer();
+    }
+
+    pub fn reset_timer(&mut self) {
+        self.pause_timer();
+        self.time_remaining = self.mode.duration(&self.settings);
+        self.segment_started = None;
+    }
+
+    pub fn compute_summary_stats(&self) -> SummaryStats {
+        self.summary_stats_in(&Local)
+    }
+
+    fn summary_stats_in<Tz: TimeZone>(&self, tz: &Tz) -> SummaryStats {
+        let total_tasks = self.tasks.iter().filter(|t| !t.is_separator).count();
+        let completed_tasks = self.tasks.iter().filter(|t| t.completed).count();
+        let total_pomodoros: u32 = self.tasks.iter().map(|t| t.pomodoros).sum();
+        let mut per_hour = [0u32; 24];
+        for entry in self.session_log.iter().filter(|e| e.mode == Mode::Pomodoro) {
+            per_hour[entry.started.with_timezone(tz).hour() as usize] += 1;
+        }
+        // Earliest hour wins a tie
+        let busiest_hour = (0..24u32)
+            .filter(|&h| per_hour[h as usize] > 0)
+            .max_by_key(|&h| (per_hour[h as usize], std::cmp::Reverse(h)));
+        let per_task = |n: f64| (total_tasks > 0).then(|| n / total_tasks as f64);
+        SummaryStats {
+            total_tasks,
+            completed_tasks,
+            completion_rate: per_task(completed_tasks as f64 * 100.0),
+            avg_pomodoros_per_task: per_task(total_pomodoros as f64),
+            busiest_hour,
+        }
+    }
+
+    /// Nudges the pomodoro length by `delta` minutes, within 1–120. A running
+    /// pomodoro keeps the same fraction left; a paused one starts over at the new length.
+    pub fn adjust_pomodoro_duration(&mut self, delta: i64) {
+        let old = self.settings.pomodoro_duration;
+        let mins = (old.as_secs() / 60) as i64 + delta;
+        let new = Duration::from_secs(mins.clamp(1, 120) as u64 * 60);
+        self.settings.pomodoro_duration = new;
+        if self.mode != Mode::Pomodoro {
+            return;
+        }
+        if self.state == TimerState::Running {
+            if !old.is_zero() {
+                self.time_remaining = self.time_remaining.mul_f64(new.as_secs_f64() / old.as_secs_f64());
+            }
+        } else {
+            self.reset_timer();
+        }
+    }
+
+    /// Jumps straight to `mode` with a fresh, paused timer. Doesn't count as a completed segment.
+    pub fn switch_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        self.saved_pomodoro_remaining = None;
+        self.reset_timer();
+    }
+
+    /// Starts a short break mid-pomodoro; the pomodoro picks up where it left
+    /// off once the break ends, without counting as completed.
+    pub fn take_break_now(&mut self) {
+        if self.mode != Mode::Pomodoro {
+            return;
+        }
+        self.saved_pomodoro_remaining = Some(self.time_remaining);
+        self.pause_timer();
+        self.mode = Mode::ShortBreak;
+        self.time_remaining = self.settings.short_break_duration;
+        self.segment_started = None;
+        self.start_timer();
+    }
+
+    /// After `reset_timer` lands back on a pomodoro, resume one interrupted by `take_break_now`.
+    fn resume_interrupted_pomodoro(&mut self) {
+        if self.mode == Mode::Pomodoro {
+            if let Some(remaining) = self.saved_pomodoro_remaining.take() {
+                self.time_remaining = remaining;
+            }
+        }
+    }
+
+    pub fn next_mode(&mut self) -> Mode {
+        self.dirty = true;
+        let previous_mode = self.mode;
+        tracing::debug!(finished = %previous_mode, total = self.pomodoros_completed_total, "segment finished");
+        self.log_session();
+        if self.mode == Mode::Pomodoro {
+            self.pomodoros_completed_total += 1;
+            for index in self.focused_task_indices() {
+                if let Some(task) = self.tasks.get_mut(index) {
+                    task.pomodoros += 1;
+                }
+            }
+            self.mode = self.settings.next_break_mode(self.pomodoros_in_set(self.pomodoros_completed_total));
+        } else {
+            self.mode = Mode::Pomodoro;
+        }
+        self.mode_transition = Some((previous_mode, Instant::now()));
+        self.reset_timer();
+        self.resume_interrupted_pomodoro();
+        self.auto_start_segment();
+        previous_mode
+    }
+
+    fn log_session(&mut self) {
+        let finished = Utc::now();
+        // A segment skipped without ever being started took no time
+        let started = self.segment_started.unwrap_or(finished);
+        let task_ids = if self.mode == Mode::Pomodoro {
+            self.focused_task_indices().into_iter()
+                .filter_map(|i| self.tasks.get(i))
+                .map(|t| t.id)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        self.session_log.push(SessionEntry { mode: self.mode, started, finished, task_ids });
+    }
+
+    /// Pomodoros finished on each of the last `n_days` local days, oldest first.
+    /// Days before the session log starts fall back to the pomodoros of tasks
+    /// completed that day, so history from before the log still shows.
+    pub fn daily_counts(&self, n_days: usize) -> Vec<u64> {
+        let today = Local::now().date_naive();
+        let mut counts = vec![0u64; n_days];
+        let mut add = |day: NaiveDate, n: u64| {
+            let days_ago = (today - day).num_days();
+            if days_ago >= 0 && (days_ago as usize) < n_days {
+                counts[n_days - 1 - days_ago as usize] += n;
+            }
+        };
+        let local_day = |t: DateTime<Utc>| t.with_timezone(&Local).date_naive();
+        let pomodoros = self.session_log.iter().filter(|e| e.mode == Mode::Pomodoro);
+        let log_start = pomodoros.clone().map(|e| local_day(e.finished)).min();
+        for entry in pomodoros {
+            add(local_day(entry.finished), 1);
+        }
+        for task in &self.tasks {
+            if let Some(day) = task.completion_date.map(local_day) {
+                if log_start.is_none_or(|start| day < start) {
+                    add(day, task.pomodoros as u64);
+                }
+            }
+        }
+        counts
+    }
+
+    /// Pomodoros finished today (local time) with `task` among the focused tasks.
+    pub fn pomodoros_today_on(&self, task: &Task) -> u32 {
+        let today = Local::now().date_naive();
+        self.session_log.iter()
+            .filter(|e| e.mode == Mode::Pomodoro && e.finished.with_timezone(&Local).date_naive() == today)
+            .filter(|e| e.task_ids.contains(&task.id))
+            .count() as u32
+    }
+
+    /// Pomodoros finished in the current set, from 0 up to `long_break_interval - 1`.
+    pub fn sessions_in_current_set(&self) -> u32 {
+        self.pomodoros_completed_total % self.settings.long_break_interval.max(1)
+    }
+
+    /// Position of the `total`-th pomodoro within its set, from 1 to `long_break_interval`.
+    fn pomodoros_in_set(&self, total: u32) -> u32 {
+        let interval = self.settings.long_break_interval.max(1);
+        (total.max(1) - 1) % interval + 1
+    }
+
+    // Manual schedules leave breaks paused so the user can pick short or long first
+    fn auto_start_segment(&mut self) {
+        if self.mode != Mode::Pomodoro && self.settings.break_schedule == BreakSchedule::Manual {
+            return;
+        }
+        if let Some(index) = self.active_task_index {
+            if !self.tasks[index].completed {
+                self.start_timer();
+            }
+        }
+    }
+
+    /// Ends the current segment now, counting it as if the timer ran out.
+    /// Unlike `skip_segment`, a finished pomodoro is credited.
+    pub fn force_complete_session(&mut self) -> Mode {
+        self.session_summary_visible = false;
+        self.session_summary_timer = None;
+        self.time_remaining = Duration::ZERO;
+        self.next_mode()
+    }
+
+    /// Holds a finished segment on screen; `force_complete_session` moves on
+    /// once the popup is dismissed or `SESSION_SUMMARY_DURATION` passes.
+    pub fn show_session_summary(&mut self) {
+        self.time_remaining = Duration::ZERO;
+        self.session_summary_visible = true;
+        self.session_summary_timer = Some(Instant::now());
+    }
+
+    pub fn session_summary_expired(&self) -> bool {
+        self.session_summary_timer
+            .is_some_and(|shown| shown.elapsed() >= SESSION_SUMMARY_DURATION)
+    }
+
+    /// The mode that just ended, while its color flash is still showing.
+    pub fn transition_from(&self) -> Option<Mode> {
+        self.mode_transition
+            .filter(|(_, at)| at.elapsed() < MODE_TRANSITION_DURATION)
+            .map(|(mode, _)| mode)
+    }
+
+    pub fn clear_expired_mode_transition(&mut self) {
+        if self.transition_from().is_none() {
+            self.mode_transition = None;
+        }
+    }
+
+    pub fn skip_segment(&mut self) -> Mode {
+        let previous_mode = self.mode;
+        if self.mode == Mode::Pomodoro {
+            self.mode = self.settings.next_break_mode(self.pomodoros_in_set(self.pomodoros_completed_total + 1));
+        } else {
+            self.mode = Mode::Pomodoro;
+        }
+        self.mode_transition = Some((previous_mode, Instant::now()));
+        self.reset_timer();
+        self.resume_interrupted_pomodoro();
+        self.auto_start_segment();
+        previous_mode
+    }
+
+    pub fn complete_active_task(&mut self) {
+        if let Some(index) = self.active_task_index {
+            if let Some(task) = self.tasks.get_mut(index) {
+                task.completed = !task.completed;
+                if task.completed {
+                    task.completion_date = Some(Utc::now());
+                    self.active_task_indices.remove(&index);
+                    self.reset_timer();
+                    self.active_task_index = self.first_selectable_task();
+                    self.spawn_recurrence(index);
+                } else {
+                    task.completion_date = None;
+                    self.retract_recurrence(index);
+                }
+            }
+        }
+    }
+
+    /// `Ctrl+Enter`: marks every unfinished task done, pauses the timer and
+    /// clears the selection. Recurring tasks queue their next copy as usual.
+    /// Returns the completed tasks' ids for `undo_bulk_complete`.
+    pub fn mark_all_active_as_complete(&mut self) -> Vec<u64> {
+        let now = Utc::now();
+        let completed: Vec<u64> = self.tasks.iter()
+            .filter(|t| !t.completed && !t.is_separator)
+            .map(|t| t.id)
+            .collect();
+        self.active_task_indices.clear();
+        self.pause_timer();
+        self.active_task_index = None;
+        // By id, since each queued recurrence shifts the indices below it
+        for &id in &completed {
+            let Some(index) = self.tasks.iter().position(|t| t.id == id) else { continue };
+            self.tasks[index].completed = true;
+            self.tasks[index].completion_date = Some(now);
+            self.spawn_recurrence(index);
+        }
+        completed
+    }
+
+    /// Reopens the tasks completed by `mark_all_active_as_complete` that are
+    /// still in the list and still done, removing their queued recurrences.
+    /// Returns false when none are left to reopen.
+    pub fn undo_bulk_complete(&mut self, ids: &[u64]) -> bool {
+        let mut reopened = false;
+        for &id in ids {
+            let Some(index) = self.tasks.iter().position(|t| t.id == id && t.completed) else { continue };
+            self.tasks[index].completed = false;
+            self.tasks[index].completion_date = None;
+            self.retract_recurrence(index);
+            reopened = true;
+        }
+        if reopened {
+            self.active_task_index = self.first_selectable_task();
+        }
+        reopened
+    }
+
+    /// Marks the selected task done without crediting a pomodoro, for work
+    /// finished outside the app. Unlike `complete_active_task` it never
+    /// un-completes, and the timer is left alone.
+    pub fn instant_complete_active_task(&mut self) {
+        let Some(index) = self.active_task_index else { return };
+        let Some(task) = self.tasks.get_mut(index) else { return };
+        if task.completed || task.is_separator {
+            return;
+        }
+        task.completed = true;
+        task.completion_date = Some(Utc::now());
+        self.active_task_indices.remove(&index);
+        self.active_task_index = self.first_selectable_task();
+        self.spawn_recurrence(index);
+    }
+
+    /// Deletes the completed tasks at `indices` (the Statistics list as
+    /// currently filtered); returns how many went.
+    pub fn delete_completed_tasks(&mut self, indices: &[usize]) -> usize {
+        let mut completed: Vec<usize> = indices.iter()
+            .copied()
+            .filter(|&i| self.tasks.get(i).is_some_and(|t| t.completed))
+            .collect();
+        completed.sort_unstable();
+        completed.dedup();
+        for &index in completed.iter().rev() {
+            if self.active_task_index == Some(index) {
+                self.active_task_index = None;
+            }
+            self.remove_task(index);
+        }
+        completed.len()
+    }
+
+    fn first_selectable_task(&self) -> Option<usize> {
+        self.active_tasks().into_iter().find(|(_, t)| !t.is_separator).map(|(i, _)| i)
+    }
+
+    /// Inserts a separator right under the selected task, or removes the one already there.
+    pub fn toggle_separator_below_active(&mut self) {
+        let Some(index) = self.active_task_index else { return };
+        let below = {
+            let rows = self.active_tasks();
+            rows.iter()
+                .position(|(i, _)| *i == index)
+                .and_then(|pos| rows.get(pos + 1))
+                .filter(|(_, t)| t.is_separator)
+                .map(|(i, _)| *i)
+        };
+        if let Some(separator) = below {
+            self.remove_task(separator);
+            return;
+        }
+        let project = self.tasks[index].project.clone();
+        self.tasks.insert(index + 1, Task::separator(project));
+        self.remap_active_indices(|i| Some(if i > index { i + 1 } else { i }));
+    }
+
+    pub fn delete_active_task(&mut self) {
+        if let Some(index) = self.active_task_index {
+            self.remove_task(index);
+            self.reset_timer();
+            self.active_task_index = self.first_selectable_task();
+        }
+    }
+
+    /// Inserts a fresh copy (no tracked time) of the task right below it.
+    pub fn duplicate_task(&mut self, index: usize) {
+        if let Some(task) = self.tasks.get(index) {
+            let mut copy = Task::new(task.name.clone(), task.project.clone());
+            copy.notes = task.notes.clone();
+            copy.links = task.links.clone();
+            copy.estimated_pomodoros = task.estimated_pomodoros;
+            copy.priority = task.priority;
+            copy.color = task.color;
+            copy.subtasks = task.subtasks.iter()
+                .map(|s| SubTask { name: s.name.clone(), completed: false })
+                .collect();
+            self.tasks.insert(index + 1, copy);
+            if let Some(active) = self.active_task_index {
+                if active > index {
+                    self.active_task_index = Some(active + 1);
+                }
+            }
+            self.remap_active_indices(|i| Some(if i > index { i + 1 } else { i }));
+        }
+    }
+
+    /// Puts the next occurrence of a completed recurring task at its list
+    /// position, deferred until it is due. The rule moves to the new task, so
+    /// the completed one stays behind as plain history.
+    fn spawn_recurrence(&mut self, index: usize) {
+        let Some(task) = self.tasks.get_mut(index) else { return };
+        let Some(rule) = task.recurrence.take() else { return };
+        let completed_on = task.completion_date.unwrap_or_else(Utc::now).with_timezone(&Local).date_naive();
+        let due = rule.next_date(completed_on);
+
+        let mut next = Task::new(task.name.clone(), task.project.clone());
+        next.notes = task.notes.clone();
+        next.links = task.links.clone();
+        next.estimated_pomodoros = task.estimated_pomodoros;
+        next.priority = task.priority;
+        next.color = task.color;
+        next.subtasks = task.subtasks.iter()
+            .map(|s| SubTask { name: s.name.clone(), completed: false })
+            .collect();
+        next.recurrence = Some(rule);
+        next.deferred_until = due.and_hms_opt(0, 0, 0)
+            .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+            .map(|d| d.with_timezone(&Utc));
+
+        self.tasks.insert(index, next);
+        if let Some(active) = self.active_task_index {
+            if active >= index {
+                self.active_task_index = Some(active + 1);
+            }
+        }
+        self.remap_active_indices(|i| Some(if i >= index { i + 1 } else { i }));
     }
 
-    pub fn next_task(&mut self) {
-        let indices: Vec<usize> = self.tasks.iter().enumerate()
-            .filter(|(_, t)| !t.completed)
+    /// Undoes `spawn_recurrence` when a completed task is reopened: the queued
+    /// copy is removed, provided nothing has been tracked on it, and its rule
+    /// moves back, so completing the task again doesn't queue a second one.
+    fn retract_recurrence(&mut self, index: usize) {
+        let Some(task) = self.tasks.get(index) else { return };
+        let pending = self.tasks.iter().position(|t| {
+            !t.completed
+                && t.recurrence.is_some()
+                && t.name == task.name
+                && t.project == task.project
+                && t.pomodoros == 0
+                && t.time_spent.is_zero()
+        });
+        let Some(pending) = pending else { return };
+        let copy = self.remove_task(pending);
+        let index = if pending < index { index - 1 } else { index };
+        self.tasks[index].recurrence = copy.recurrence;
+    }
+
+    /// Queues the next occurrence of every completed task that still holds a
+    /// recurrence rule, e.g. one completed before recurrence existed or
+    /// edited in by hand. Run on startup.
+    pub fn process_recurring_tasks(&mut self) {
+        let pending: Vec<usize> = self.tasks.iter().enumerate()
+            .filter(|(_, t)| t.completed && t.recurrence.is_some())
             .map(|(i, _)| i)
             .collect();
+        // Back to front so each insert leaves the remaining indices valid
+        for index in pending.into_iter().rev() {
+            self.spawn_recurrence(index);
+        }
+    }
+
+    pub fn cycle_task_recurrence(&mut self, index: usize) {
+        if let Some(task) = self.tasks.get_mut(index) {
+            task.recurrence = RecurrenceRule::cycle(task.recurrence, Local::now().weekday());
+        }
+    }
+
+    /// Raises or lowers the estimate by `delta` pomodoros; going below one clears it.
+    pub fn adjust_task_estimate(&mut self, index: usize, delta: i32) {
+        if let Some(task) = self.tasks.get_mut(index) {
+            let estimate = task.estimated_pomodoros.unwrap_or(0) as i32 + delta;
+            task.estimated_pomodoros = (estimate > 0).then_some(estimate as u32);
+        }
+    }
+
+    /// Time the estimate amounts to at the current pomodoro length.
+    pub fn estimated_duration(&self, task: &Task) -> Option<Duration> {
+        task.estimated_pomodoros.map(|n| self.settings.pomodoro_duration * n)
+    }
+
+    pub fn cycle_task_color(&mut self, index: usize) {
+        if let Some(task) = self.tasks.get_mut(index) {
+            task.color = TaskColor::cycle(task.color);
+        }
+    }
+
+    pub fn cycle_task_priority(&mut self, index: usize) {
+        if let Some(task) = self.tasks.get_mut(index) {
+            task.priority = Priority::cycle(task.priority);
+        }
+    }
+
+    pub fn next_task(&mut self) {
+        let indices = self.selectable_task_indices();
         if indices.is_empty() { self.active_task_index = None; return; }
         let cur = self.active_task_index.unwrap_or(0);
         let next = indices.iter().position(|&i| i == cur)
@@ -311,10 +1933,7 @@ impl App {
     }
 
     pub fn previous_task(&mut self) {
-        let indices: Vec<usize> = self.tasks.iter().enumerate()
-            .filter(|(_, t)| !t.completed)
-            .map(|(i, _)| i)
-            .collect();
+        let indices = self.selectable_task_indices();
         if indices.is_empty() { self.active_task_index = None; return; }
         let cur = self.active_task_index.unwrap_or(0);
         let pos = indices.iter().position(|&i| i == cur).unwrap_or(0);
@@ -322,21 +1941,1094 @@ impl App {
         self.active_task_index = Some(indices[prev]);
     }
 
+    /// `PgDn` in the task list: `n` rows down, stopping at the last task
+    /// rather than wrapping like `next_task`.
+    pub fn next_task_by(&mut self, n: usize) {
+        let indices = self.selectable_task_indices();
+        self.page_selection(&indices, n, true);
+    }
+
+    /// `PgUp` in the task list: `n` rows up, stopping at the first task.
+    pub fn previous_task_by(&mut self, n: usize) {
+        let indices = self.selectable_task_indices();
+        self.page_selection(&indices, n, false);
+    }
+
+    fn selectable_task_indices(&self) -> Vec<usize> {
+        self.active_tasks().into_iter()
+            .filter(|(_, t)| !t.is_separator)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Moves the selection `n` places through `indices`, clamped to its ends.
+    /// Nothing selected (or the selection not listed) starts from the top.
+    fn page_selection(&mut self, indices: &[usize], n: usize, down: bool) {
+        if indices.is_empty() { return; }
+        let pos = self.active_task_index.and_then(|cur| indices.iter().position(|&i| i == cur));
+        let target = match pos {
+            Some(p) if down => (p + n).min(indices.len() - 1),
+            Some(p) => p.saturating_sub(n),
+            None => 0,
+        };
+        self.active_task_index = Some(indices[target]);
+    }
+
     pub fn move_active_task_up(&mut self) {
-        if let Some(index) = self.active_task_index {
-            if index > 0 {
-                self.tasks.swap(index, index - 1);
-                self.active_task_index = Some(index - 1);
-            }
-        }
+        self.move_active_task(false);
     }
 
     pub fn move_active_task_down(&mut self) {
-        if let Some(index) = self.active_task_index {
-            if index < self.tasks.len() - 1 {
-                self.tasks.swap(index, index + 1);
-                self.active_task_index = Some(index + 1);
-            }
+        self.move_active_task(true);
+    }
+
+    /// Swaps the active task with its neighbour as `active_tasks` lists them,
+    /// so hidden tasks in between are skipped. A task stays inside its project
+    /// group, where swapping with the next group would change nothing on screen.
+    fn move_active_task(&mut self, down: bool) {
+        let Some(index) = self.active_task_index else { return };
+        let shown = self.active_tasks();
+        let Some(pos) = shown.iter().position(|(i, _)| *i == index) else { return };
+        let neighbour = if down { shown.get(pos + 1) } else { pos.checked_sub(1).and_then(|p| shown.get(p)) };
+        let Some(&(other, other_task)) = neighbour else { return };
+        if self.project_rank(other_task) != self.project_rank(&self.tasks[index]) {
+            return;
+        }
+        self.swap_tasks(index, other);
+        self.active_task_index = Some(other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_with_remaining(secs: u64) -> App {
+        App { time_remaining: Duration::from_secs(secs), ..App::default() }
+    }
+
+    #[test]
+    fn time_remaining_formatted() {
+        assert_eq!(app_with_remaining(0).time_remaining_formatted(), "00:00");
+        assert_eq!(app_with_remaining(59).time_remaining_formatted(), "00:59");
+        assert_eq!(app_with_remaining(60).time_remaining_formatted(), "01:00");
+        assert_eq!(app_with_remaining(3600).time_remaining_formatted(), "60:00");
+        assert_eq!(app_with_remaining(u64::MAX).time_remaining_formatted(), "??:??");
+    }
+
+    #[test]
+    fn time_remaining_formatted_minutes() {
+        assert_eq!(app_with_remaining(0).time_remaining_formatted_minutes(), "00");
+        assert_eq!(app_with_remaining(1).time_remaining_formatted_minutes(), "01");
+        assert_eq!(app_with_remaining(60).time_remaining_formatted_minutes(), "01");
+        assert_eq!(app_with_remaining(1499).time_remaining_formatted_minutes(), "25");
+        assert_eq!(app_with_remaining(1500).time_remaining_formatted_minutes(), "25");
+    }
+
+    #[test]
+    fn time_remaining_formatted_hms() {
+        assert_eq!(app_with_remaining(0).time_remaining_formatted_hms(), "00:00:00");
+        assert_eq!(app_with_remaining(59).time_remaining_formatted_hms(), "00:00:59");
+        assert_eq!(app_with_remaining(60).time_remaining_formatted_hms(), "00:01:00");
+        assert_eq!(app_with_remaining(3600).time_remaining_formatted_hms(), "01:00:00");
+        assert_eq!(app_with_remaining(u64::MAX).time_remaining_formatted_hms(), "??:??:??");
+    }
+
+    #[test]
+    fn all_subtasks_complete_requires_at_least_one_subtask() {
+        let mut task = Task::new("Write report".to_string(), None);
+        assert!(!task.all_subtasks_complete());
+        task.subtasks.push(SubTask { name: "Outline".to_string(), completed: true });
+        task.subtasks.push(SubTask { name: "Draft".to_string(), completed: false });
+        assert!(!task.all_subtasks_complete());
+        task.subtasks[1].completed = true;
+        assert!(task.all_subtasks_complete());
+    }
+
+    fn app_with_tasks(names: &[&str]) -> App {
+        App {
+            tasks: names.iter().map(|n| Task::new(n.to_string(), None)).collect(),
+            ..App::default()
+        }
+    }
+
+    #[test]
+    fn focused_tasks_follow_parallel_mode() {
+        let mut app = app_with_tasks(&["a", "b", "c"]);
+        app.active_task_index = Some(0);
+        app.toggle_task_active(1);
+        app.toggle_task_active(2);
+        assert_eq!(app.focused_task_indices(), vec![0]);
+        app.settings.parallel_mode = true;
+        assert_eq!(app.focused_task_indices(), vec![1, 2]);
+        app.toggle_task_active(1);
+        assert_eq!(app.focused_task_indices(), vec![2]);
+    }
+
+    #[test]
+    fn parallel_set_tracks_tasks_across_removal_and_moves() {
+        let mut app = app_with_tasks(&["a", "b", "c", "d"]);
+        app.settings.parallel_mode = true;
+        app.toggle_task_active(1);
+        app.toggle_task_active(3);
+        app.remove_task(1);
+        assert_eq!(app.focused_task_indices(), vec![2]);
+        app.active_task_index = Some(2);
+        app.move_active_task_up();
+        assert_eq!(app.focused_task_indices(), vec![1]);
+        assert_eq!(app.tasks[1].name, "d");
+    }
+
+    #[test]
+    fn pomodoro_set_duration_uses_interval() {
+        // 4 × 25m + 3 × 5m + 15m
+        assert_eq!(App::default().pomodoro_set_duration(), Duration::from_secs(130 * 60));
+    }
+
+    #[test]
+    fn time_to_complete_set_counts_finished_pomodoros() {
+        let mut app = App::default();
+        assert_eq!(app.estimated_time_to_complete_current_set(), app.pomodoro_set_duration());
+
+        // Two pomodoros done, sitting in the following short break with 3m left
+        app.pomodoros_completed_total = 2;
+        app.mode = Mode::ShortBreak;
+        app.time_remaining = Duration::from_secs(3 * 60);
+        assert_eq!(app.estimated_time_to_complete_current_set(), Duration::from_secs((3 + 50 + 5 + 15) * 60));
+
+        app.pomodoros_completed_total = 4;
+        app.mode = Mode::LongBreak;
+        app.time_remaining = Duration::from_secs(60);
+        assert_eq!(app.estimated_time_to_complete_current_set(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn deferred_tasks_are_hidden_until_shown() {
+        let mut app = app_with_tasks(&["a", "b"]);
+        app.active_task_index = Some(0);
+        app.toggle_task_deferred(0);
+        assert_eq!(app.active_task_index, Some(1));
+        assert_eq!(app.deferred_task_count(), 1);
+        assert_eq!(app.active_tasks().len(), 1);
+
+        app.show_deferred = true;
+        assert_eq!(app.active_tasks().len(), 2);
+
+        app.tasks[0].deferred_until = Some(Utc::now() - ChronoDuration::minutes(1));
+        app.show_deferred = false;
+        assert_eq!(app.deferred_task_count(), 0);
+        assert_eq!(app.active_tasks().len(), 2);
+    }
+
+    #[test]
+    fn display_impls() {
+        assert_eq!(Mode::ShortBreak.to_string(), "Short Break");
+        assert_eq!(TimerState::Running.to_string(), "Running");
+        assert_eq!(TimerState::Paused.to_string(), "Paused");
+    }
+
+    #[test]
+    fn search_tasks_matches_name_notes_and_project() {
+        let mut app = app_with_tasks(&["Write report", "Review PR", "Groceries"]);
+        app.tasks[0].completed = true;
+        app.tasks[1].project = Some("work".to_string());
+        app.tasks[2].notes = Some("Milk, eggs, REPORT paper".to_string());
+
+        let found: Vec<usize> = app.search_tasks("report").iter().map(|(i, _)| *i).collect();
+        // Active matches before completed ones
+        assert_eq!(found, vec![2, 0]);
+        assert_eq!(app.search_tasks("@WORK").len(), 1);
+        assert!(app.search_tasks("  ").is_empty());
+    }
+
+    #[test]
+    fn recurrence_next_dates() {
+        // 2024-03-08 is a Friday
+        let friday = NaiveDate::from_ymd_opt(2024, 3, 8).unwrap();
+        let date = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        assert_eq!(RecurrenceRule::Daily.next_date(friday), date(9));
+        assert_eq!(RecurrenceRule::Weekdays.next_date(friday), date(11));
+        assert_eq!(RecurrenceRule::Weekly(Weekday::Fri).next_date(friday), date(15));
+        assert_eq!(RecurrenceRule::Weekly(Weekday::Tue).next_date(friday), date(12));
+        assert_eq!(RecurrenceRule::Custom(3).next_date(friday), date(11));
+        assert_eq!(RecurrenceRule::Custom(0).next_date(friday), date(9));
+    }
+
+    #[test]
+    fn completing_recurring_task_queues_next_occurrence_in_place() {
+        let mut app = app_with_tasks(&["a", "standup", "b"]);
+        app.tasks[1].notes = Some("agenda".to_string());
+        app.tasks[1].recurrence = Some(RecurrenceRule::Daily);
+        app.active_task_index = Some(1);
+        app.complete_active_task();
+
+        assert_eq!(app.tasks.len(), 4);
+        let next = &app.tasks[1];
+        assert_eq!(next.name, "standup");
+        assert_eq!(next.notes.as_deref(), Some("agenda"));
+        assert_eq!(next.recurrence, Some(RecurrenceRule::Daily));
+        assert!(!next.completed && next.is_deferred());
+        assert!(app.tasks[2].completed);
+        assert_eq!(app.tasks[2].recurrence, None);
+        assert_eq!(app.active_task_index, Some(0));
+    }
+
+    #[test]
+    fn reopening_recurring_task_removes_its_queued_copy() {
+        let mut app = app_with_tasks(&["a", "standup"]);
+        app.tasks[1].recurrence = Some(RecurrenceRule::Daily);
+        app.active_task_index = Some(1);
+        app.complete_active_task();
+        assert_eq!(app.tasks.len(), 3);
+
+        app.active_task_index = Some(2);
+        app.complete_active_task();
+        assert_eq!(app.tasks.len(), 2);
+        assert!(!app.tasks[1].completed);
+        assert_eq!(app.tasks[1].recurrence, Some(RecurrenceRule::Daily));
+        assert_eq!(app.active_task_index, Some(1));
+
+        app.complete_active_task();
+        assert_eq!(app.tasks.iter().filter(|t| t.name == "standup").count(), 2);
+    }
+
+    #[test]
+    fn startup_recreates_completed_recurring_tasks_once() {
+        let mut app = app_with_tasks(&["a", "b"]);
+        for task in &mut app.tasks {
+            task.completed = true;
+            task.completion_date = Some(Utc::now());
+            task.recurrence = Some(RecurrenceRule::Weekdays);
+        }
+        app.process_recurring_tasks();
+        let names: Vec<_> = app.tasks.iter().map(|t| (t.name.as_str(), t.completed)).collect();
+        assert_eq!(names, vec![("a", false), ("a", true), ("b", false), ("b", true)]);
+
+        app.process_recurring_tasks();
+        assert_eq!(app.tasks.len(), 4);
+    }
+
+    #[test]
+    fn force_complete_credits_the_session() {
+        let mut app = app_with_tasks(&["a"]);
+        app.active_task_index = Some(0);
+        app.state = TimerState::Running;
+        assert_eq!(app.force_complete_session(), Mode::Pomodoro);
+        assert_eq!(app.pomodoros_completed_total, 1);
+        assert_eq!(app.tasks[0].pomodoros, 1);
+        assert_eq!(app.mode, Mode::ShortBreak);
+        assert_eq!(app.time_remaining, app.settings.short_break_duration);
+    }
+
+    #[test]
+    fn session_summary_holds_the_segment_until_completed() {
+        let mut app = app_with_remaining(1);
+        app.state = TimerState::Running;
+        app.show_session_summary();
+        assert!(app.session_summary_visible);
+        assert!(!app.session_summary_expired());
+        assert_eq!(app.mode, Mode::Pomodoro);
+        assert_eq!(app.pomodoros_completed_total, 0);
+
+        app.session_summary_timer = Some(Instant::now() - SESSION_SUMMARY_DURATION);
+        assert!(app.session_summary_expired());
+        app.force_complete_session();
+        assert!(!app.session_summary_visible && app.session_summary_timer.is_none());
+        assert_eq!(app.pomodoros_completed_total, 1);
+        assert_eq!(app.mode, Mode::ShortBreak);
+    }
+
+    #[test]
+    fn adjusting_pomodoro_duration_clamps_and_scales_remaining() {
+        let mut app = App::default();
+        app.adjust_pomodoro_duration(1);
+        assert_eq!(app.settings.pomodoro_duration, Duration::from_secs(26 * 60));
+        assert_eq!(app.time_remaining, Duration::from_secs(26 * 60));
+
+        app.settings.pomodoro_duration = Duration::from_secs(20 * 60);
+        app.time_remaining = Duration::from_secs(10 * 60);
+        app.state = TimerState::Running;
+        app.adjust_pomodoro_duration(20);
+        assert_eq!(app.time_remaining, Duration::from_secs(20 * 60));
+
+        app.adjust_pomodoro_duration(500);
+        assert_eq!(app.settings.pomodoro_duration, Duration::from_secs(120 * 60));
+        app.adjust_pomodoro_duration(-500);
+        assert_eq!(app.settings.pomodoro_duration, Duration::from_secs(60));
+
+        app.switch_mode(Mode::ShortBreak);
+        let remaining = app.time_remaining;
+        app.adjust_pomodoro_duration(5);
+        assert_eq!(app.time_remaining, remaining);
+    }
+
+    #[test]
+    fn import_from_json_merges_new_names_only() {
+        let mut app = app_with_tasks(&["Existing"]);
+        let json = r#"[
+            {"name": "Existing"},
+            {"name": "Fresh", "project": "work", "pomodoros": 2},
+            {"name": "Fresh"},
+            {"notes": "no name"}
+        ]"#;
+        let summary = app.import_from_json(json).unwrap();
+        assert_eq!(summary, ImportSummary { merged: 1, skipped: 2, failed: 1 });
+        assert_eq!(app.tasks.len(), 2);
+        assert_eq!(app.tasks[1].project.as_deref(), Some("work"));
+        assert_eq!(app.tasks[1].pomodoros, 2);
+        assert!(!app.tasks[1].completed);
+
+        assert!(app.import_from_json(r#"{"name": "not a list"}"#).is_err());
+    }
+
+    #[test]
+    fn delete_completed_keeps_active_indices_on_their_tasks() {
+        let mut app = app_with_tasks(&["done 1", "keep", "done 2", "also keep", "hidden done"]);
+        app.tasks[0].completed = true;
+        app.tasks[2].completed = true;
+        app.tasks[4].completed = true;
+        app.active_task_index = Some(3);
+        app.active_task_indices = HashSet::from([1, 3]);
+
+        assert_eq!(app.delete_completed_tasks(&[2, 0, 1]), 2);
+        let names: Vec<_> = app.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["keep", "also keep", "hidden done"]);
+        assert_eq!(app.active_task_index, Some(1));
+        assert_eq!(app.active_task_indices, HashSet::from([0, 1]));
+    }
+
+    #[test]
+    fn tab_cycles_main_views_both_ways() {
+        for view in [View::Timer, View::TaskList, View::Statistics] {
+            assert_eq!(view.tab_next().tab_prev(), view);
+            assert_ne!(view.tab_next(), view);
+        }
+        assert_eq!(View::Timer.tab_next(), View::TaskList);
+        assert_eq!(View::Timer.tab_prev(), View::Statistics);
+        assert_eq!(View::Settings.tab_next(), View::Settings);
+        assert_eq!(View::TaskDetails.tab_prev(), View::TaskDetails);
+    }
+
+    #[test]
+    fn summary_stats() {
+        assert_eq!(App::default().compute_summary_stats(), SummaryStats::default());
+
+        let mut app = app_with_tasks(&["a", "b", "c", "d"]);
+        let pomodoros = [2, 5, 3, 3];
+        for (task, n) in app.tasks.iter_mut().zip(pomodoros) {
+            task.pomodoros = n;
+        }
+        for i in 0..3 {
+            app.tasks[i].completed = true;
+        }
+        // Breaks don't count towards the busiest hour
+        for (mode, hour) in [(Mode::Pomodoro, 14), (Mode::Pomodoro, 9), (Mode::Pomodoro, 14), (Mode::ShortBreak, 9), (Mode::ShortBreak, 9)] {
+            let started = Utc.with_ymd_and_hms(2024, 3, 1, hour, 30, 0).unwrap();
+            app.session_log.push(SessionEntry { mode, started, finished: started, task_ids: Vec::new() });
+        }
+        let stats = app.summary_stats_in(&Utc);
+        assert_eq!(stats.completed_tasks, 3);
+        assert_eq!(stats.total_tasks, 4);
+        assert_eq!(stats.completion_rate, Some(75.0));
+        assert_eq!(stats.avg_pomodoros_per_task, Some(3.25));
+        assert_eq!(stats.busiest_hour, Some(14));
+    }
+
+    #[test]
+    fn break_now_resumes_the_interrupted_pomodoro() {
+        let mut app = app_with_remaining(600);
+        app.state = TimerState::Running;
+        app.take_break_now();
+        assert_eq!(app.mode, Mode::ShortBreak);
+        assert_eq!(app.time_remaining, app.settings.short_break_duration);
+        assert_eq!(app.state, TimerState::Running);
+
+        app.next_mode();
+        assert_eq!(app.mode, Mode::Pomodoro);
+        assert_eq!(app.time_remaining, Duration::from_secs(600));
+        assert_eq!(app.pomodoros_completed_total, 0);
+        assert_eq!(app.saved_pomodoro_remaining, None);
+
+        // Only a pomodoro can be interrupted
+        app.switch_mode(Mode::LongBreak);
+        app.take_break_now();
+        assert_eq!(app.mode, Mode::LongBreak);
+        assert_eq!(app.saved_pomodoro_remaining, None);
+    }
+
+    #[test]
+    fn mode_transition_remembers_the_outgoing_mode_briefly() {
+        let mut app = App::default();
+        assert_eq!(app.transition_from(), None);
+        app.next_mode();
+        assert_eq!(app.transition_from(), Some(Mode::Pomodoro));
+
+        app.mode_transition = Some((Mode::ShortBreak, Instant::now() - MODE_TRANSITION_DURATION));
+        assert_eq!(app.transition_from(), None);
+        app.clear_expired_mode_transition();
+        assert!(app.mode_transition.is_none());
+    }
+
+    #[test]
+    fn task_estimate_adjusts_and_clears_below_one() {
+        let mut app = App { tasks: vec![Task::new("Write".to_string(), None)], ..App::default() };
+        app.adjust_task_estimate(0, 1);
+        app.adjust_task_estimate(0, 1);
+        assert_eq!(app.tasks[0].estimated_pomodoros, Some(2));
+        assert_eq!(app.estimated_duration(&app.tasks[0]), Some(app.settings.pomodoro_duration * 2));
+        app.adjust_task_estimate(0, -1);
+        app.adjust_task_estimate(0, -1);
+        assert_eq!(app.tasks[0].estimated_pomodoros, None);
+        app.adjust_task_estimate(0, -1);
+        assert_eq!(app.tasks[0].estimated_pomodoros, None);
+    }
+
+    #[test]
+    fn projects_group_active_tasks_and_untag_on_delete() {
+        let mut app = App {
+            tasks: vec![
+                Task::new("Inbox".to_string(), None),
+                Task::new("Report".to_string(), Some("work".to_string())),
+                Task::new("Groceries".to_string(), Some("home".to_string())),
+                Task::new("Bug".to_string(), Some("work".to_string())),
+            ],
+            ..App::default()
+        };
+        let order = |app: &App| app.active_tasks().iter().map(|(i, _)| *i).collect::<Vec<_>>();
+        assert_eq!(order(&app), vec![0, 1, 2, 3]);
+
+        app.register_task_projects();
+        let names: Vec<_> = app.projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["work", "home"]);
+        assert_ne!(app.projects[0].color, app.projects[1].color);
+        assert_eq!(order(&app), vec![1, 3, 2, 0]);
+
+        assert!(!app.add_project("@work"));
+        assert!(app.add_project(" deep work "));
+        assert_eq!(app.projects[2].name, "deep-work");
+
+        app.delete_project(0);
+        assert_eq!(app.tasks[1].project, None);
+        assert_eq!(app.tasks[3].project, None);
+        assert_eq!(order(&app), vec![2, 0, 1, 3]);
+    }
+
+    #[test]
+    fn moving_a_task_follows_the_grouped_order() {
+        let mut app = App {
+            tasks: vec![
+                Task::new("Report".to_string(), Some("work".to_string())),
+                Task::new("Groceries".to_string(), Some("home".to_string())),
+                Task::new("Bug".to_string(), Some("work".to_string())),
+            ],
+            ..App::default()
+        };
+        app.register_task_projects();
+        let names = |app: &App| app.active_tasks().iter().map(|(_, t)| t.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&app), vec!["Report", "Bug", "Groceries"]);
+
+        app.active_task_index = Some(2);
+        app.move_active_task_up();
+        assert_eq!(names(&app), vec!["Bug", "Report", "Groceries"]);
+        assert_eq!(app.tasks[app.active_task_index.unwrap()].name, "Bug");
+
+        // Already first in its group, and the last work task can't drop into "home"
+        app.move_active_task_up();
+        assert_eq!(names(&app), vec!["Bug", "Report", "Groceries"]);
+        app.active_task_index = app.tasks.iter().position(|t| t.name == "Report");
+        app.move_active_task_down();
+        assert_eq!(names(&app), vec!["Bug", "Report", "Groceries"]);
+    }
+
+    #[test]
+    fn estimated_completion_adds_remaining_pomodoros_and_breaks() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap();
+        let mut app = App::default();
+        assert_eq!(app.estimated_completion_time_from(now), None);
+
+        let mut report = Task::new("Report".to_string(), None);
+        report.estimated_pomodoros = Some(3);
+        report.pomodoros = 1;
+        app.tasks = vec![report, Task::new("Email".to_string(), None)];
+        // 3 pomodoros (2 + 1) with two short breaks between them
+        assert_eq!(
+            app.estimated_completion_time_from(now),
+            Some(now + ChronoDuration::minutes(25 * 3 + 5 * 2))
+        );
+
+        // The fourth pomodoro of a set is followed by the long break
+        app.pomodoros_completed_total = 2;
+        app.time_remaining = Duration::from_secs(10 * 60);
+        assert_eq!(
+            app.estimated_completion_time_from(now),
+            Some(now + ChronoDuration::minutes(10 + 25 * 2 + 5 + 15))
+        );
+    }
+
+    #[test]
+    fn separator_toggles_below_task_and_is_skipped() {
+        let mut app = App {
+            tasks: vec![Task::new("A".to_string(), None), Task::new("B".to_string(), None)],
+            active_task_index: Some(0),
+            ..App::default()
+        };
+
+        app.toggle_separator_below_active();
+        assert!(app.tasks[1].is_separator);
+        assert_eq!(app.tasks.len(), 3);
+
+        app.next_task();
+        assert_eq!(app.active_task_index, Some(2));
+        app.previous_task();
+        assert_eq!(app.active_task_index, Some(0));
+
+        app.toggle_separator_below_active();
+        assert_eq!(app.tasks.len(), 2);
+        assert!(app.tasks.iter().all(|t| !t.is_separator));
+    }
+
+    #[test]
+    fn paging_stops_at_the_ends_and_skips_separators() {
+        let mut app = app_with_tasks(&["a", "b", "c", "d"]);
+        app.tasks.insert(2, Task::separator(None));
+        app.active_task_index = Some(0);
+
+        app.next_task_by(2);
+        assert_eq!(app.active_task_index, Some(3));
+        app.next_task_by(10);
+        assert_eq!(app.active_task_index, Some(4));
+        app.previous_task_by(1);
+        assert_eq!(app.active_task_index, Some(3));
+        app.previous_task_by(10);
+        assert_eq!(app.active_task_index, Some(0));
+    }
+
+    #[test]
+    fn active_tasks_text_numbers_tasks_with_totals() {
+        let mut app = App::default();
+        let mut report = Task::new("Report".to_string(), None);
+        report.pomodoros = 3;
+        report.time_spent = Duration::from_secs(135 * 60);
+        let mut email = Task::new("Email".to_string(), None);
+        email.pomodoros = 1;
+        let mut done = Task::new("Done".to_string(), None);
+        done.completed = true;
+        app.tasks = vec![report, Task::separator(None), email, done];
+        assert_eq!(
+            app.active_tasks_text(),
+            "1. [ ] Report | 3● | 2h 15m\n2. [ ] Email | 1● | 0m"
+        );
+    }
+
+    #[test]
+    fn task_display_is_a_one_line_summary() {
+        let mut done = Task::new("Fix login bug".to_string(), Some("work".to_string()));
+        done.completed = true;
+        done.pomodoros = 3;
+        done.estimated_pomodoros = Some(4);
+        done.time_spent = Duration::from_secs(85 * 60);
+        assert_eq!(done.to_string(), "[✓] Fix login bug | est:4● | 3● | 1h 25m");
+
+        let mut active = Task::new("Add tests".to_string(), None);
+        active.time_spent = Duration::from_secs(23 * 60 + 40);
+        assert_eq!(active.to_string(), "[ ] Add tests | 0● | 23m");
+    }
+
+    #[test]
+    fn reorder_tasks_keeps_completed_last_and_tracks_selection() {
+        let mut app = App::default();
+        let task = |name: &str, pomodoros: u32, completed: bool| {
+            let mut t = Task::new(name.to_string(), None);
+            t.pomodoros = pomodoros;
+            t.completed = completed;
+            t
+        };
+        app.tasks = vec![task("b", 1, false), task("z", 9, true), task("C", 3, false), task("a", 2, false)];
+        app.active_task_index = Some(2);
+        let names = |app: &App| app.tasks.iter().map(|t| t.name.clone()).collect::<Vec<_>>();
+
+        app.reorder_tasks_by(SortCriterion::Name);
+        assert_eq!(names(&app), vec!["a", "b", "C", "z"]);
+        assert_eq!(app.active_task_index, Some(2));
+
+        app.reorder_tasks_by(SortCriterion::PomodoroCount);
+        assert_eq!(names(&app), vec!["C", "a", "b", "z"]);
+        assert_eq!(app.active_task_index, Some(0));
+        assert_eq!(app.settings.default_sort, SortCriterion::PomodoroCount);
+    }
+
+    #[test]
+    fn inline_details_toggle_and_follow_the_task() {
+        let mut app = App {
+            tasks: vec![Task::new("A".to_string(), None), Task::new("B".to_string(), None)],
+            active_task_index: Some(1),
+            ..App::default()
+        };
+        app.toggle_inline_details();
+        assert_eq!(app.inline_expanded_task, Some(1));
+
+        app.remove_task(0);
+        assert_eq!(app.inline_expanded_task, Some(0));
+        app.toggle_inline_details();
+        assert_eq!(app.inline_expanded_task, None);
+    }
+
+    #[test]
+    fn statistics_filter_ranges() {
+        // A Thursday
+        let today = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        assert!(StatisticsFilter::Today.includes(today, today));
+        assert!(!StatisticsFilter::Today.includes(day(13), today));
+        assert!(StatisticsFilter::ThisWeek.includes(day(11), today));
+        assert!(!StatisticsFilter::ThisWeek.includes(day(10), today));
+        assert!(StatisticsFilter::ThisMonth.includes(day(1), today));
+        assert!(!StatisticsFilter::ThisMonth.includes(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(), today));
+        assert!(StatisticsFilter::CustomRange(day(1), day(3)).includes(day(3), today));
+        assert!(!StatisticsFilter::CustomRange(day(1), day(3)).includes(day(4), today));
+
+        let mut task = Task::new("Report".to_string(), None);
+        assert!(StatisticsFilter::AllTime.includes_task(&task, today));
+        assert!(!StatisticsFilter::Today.includes_task(&task, today));
+        task.completion_date = Some(Utc::now());
+        assert!(StatisticsFilter::Today.includes_task(&task, Local::now().date_naive()));
+
+        assert_eq!(StatisticsFilter::ThisMonth.next(), StatisticsFilter::AllTime);
+    }
+
+    #[test]
+    fn fourth_pomodoro_is_followed_by_a_long_break() {
+        let mut app = App {
+            tasks: vec![Task::new("Report".to_string(), None)],
+            active_task_index: Some(0),
+            ..App::default()
+        };
+        for _ in 0..3 {
+            app.next_mode();
+            assert_eq!(app.mode, Mode::ShortBreak);
+            app.next_mode();
+        }
+        app.next_mode();
+
+        let expected = AppSnapshot {
+            mode: Mode::LongBreak,
+            state: TimerState::Running,
+            time_remaining_secs: 15 * 60,
+            pomodoros_completed_total: 4,
+            active_task_index: Some(0),
+            tasks: vec![TaskSnapshot {
+                name: "Report".to_string(),
+                completed: false,
+                pomodoros: 4,
+                time_spent_secs: 0,
+            }],
+        };
+        assert_eq!(app.snapshot(), expected);
+        assert_eq!(app.clone().snapshot(), expected);
+    }
+
+    #[test]
+    fn overdue_tasks_have_used_up_their_estimate() {
+        let mut app = App::default();
+        let task = |pomodoros, estimate| {
+            let mut t = Task::new("T".to_string(), None);
+            t.pomodoros = pomodoros;
+            t.estimated_pomodoros = estimate;
+            t
+        };
+        let mut done = task(5, Some(2));
+        done.completed = true;
+        app.tasks = vec![task(3, Some(3)), task(1, Some(2)), task(9, None), done, task(4, Some(2))];
+        assert_eq!(app.tasks_with_overdue_estimates(), vec![0, 4]);
+    }
+
+    #[test]
+    fn task_timer_reset_can_be_undone() {
+        let mut app = App::default();
+        let mut report = Task::new("Report".to_string(), None);
+        report.pomodoros = 3;
+        report.time_spent = Duration::from_secs(75 * 60);
+        report.completed = true;
+        app.tasks = vec![report, Task::new("Email".to_string(), None)];
+        app.pomodoros_completed_total = 7;
+        let before = app.snapshot();
+
+        let timers = app.reset_all_task_timers();
+        assert_eq!(app.tasks[0].pomodoros, 0);
+        assert_eq!(app.tasks[0].time_spent, Duration::ZERO);
+        assert_eq!(app.pomodoros_completed_total, 0);
+
+        assert!(app.restore_task_timers(timers.clone()));
+        assert_eq!(app.snapshot(), before);
+
+        app.reset_all_task_timers();
+        app.tasks.swap(0, 1);
+        assert!(app.restore_task_timers(timers.clone()));
+        assert_eq!(app.tasks[1].pomodoros, 3);
+        assert_eq!(app.tasks[0].pomodoros, 0);
+
+        app.tasks.clear();
+        assert!(!app.restore_task_timers(timers));
+    }
+
+    #[test]
+    fn state_digest_ignores_unsaved_fields() {
+        let mut app = app_with_tasks(&["a"]);
+        let before = app.state_digest();
+        app.inline_expanded_task = Some(0);
+        app.show_deferred = true;
+        assert_eq!(app.state_digest(), before);
+
+        app.tasks[0].name = "b".to_string();
+        assert_ne!(app.state_digest(), before);
+        let renamed = app.state_digest();
+        app.settings.countdown_beeps = !app.settings.countdown_beeps;
+        assert_ne!(app.state_digest(), renamed);
+    }
+
+    #[test]
+    fn time_budget_pauses_once_when_reached() {
+        let mut app = App {
+            tasks: vec![Task::new("Review".to_string(), None)],
+            active_task_index: Some(0),
+            ..App::default()
+        };
+        app.set_active_task_budget(Some(1));
+        app.state = TimerState::Running;
+
+        assert_eq!(app.record_focus_time(Duration::from_secs(59)), None);
+        assert_eq!(app.state, TimerState::Running);
+        assert_eq!(app.record_focus_time(Duration::from_secs(1)), Some(0));
+        assert_eq!(app.state, TimerState::Paused);
+
+        // Resuming past the budget doesn't pause again
+        app.state = TimerState::Running;
+        assert_eq!(app.record_focus_time(Duration::from_secs(30)), None);
+
+        app.set_active_task_budget(Some(0));
+        assert_eq!(app.tasks[0].time_budget, None);
+    }
+
+    #[test]
+    fn trailing_external_id_is_split_from_new_task_names() {
+        let mut app = App::default();
+        let mut ui = UiState { current_input: "Fix login @work ##PROJ-123".to_string(), ..UiState::default() };
+        ui.submit_task(&mut app);
+        let task = &app.tasks[0];
+        assert_eq!(task.name, "Fix login");
+        assert_eq!(task.project.as_deref(), Some("work"));
+        assert_eq!(task.external_id.as_deref(), Some("PROJ-123"));
+
+        assert_eq!(ui_state::parse_external_id("Use C## notes"), ("Use C## notes".to_string(), None));
+        assert_eq!(ui_state::parse_external_id("##PROJ-1"), ("##PROJ-1".to_string(), None));
+    }
+
+    #[test]
+    fn first_timer_start_is_recorded_once() {
+        let mut app = App {
+            tasks: vec![Task::new("Draft".to_string(), None)],
+            active_task_index: Some(0),
+            ..App::default()
+        };
+        app.state = TimerState::Paused;
+
+        app.toggle_timer();
+        let first = app.tasks[0].start_date.expect("start recorded");
+        app.toggle_timer();
+        app.toggle_timer();
+        assert_eq!(app.tasks[0].start_date, Some(first));
+
+        app.tasks[0].completion_date = Some(first + ChronoDuration::hours(3));
+        assert_eq!(app.tasks[0].time_from_start_to_completion(), Some(Duration::from_secs(3 * 3600)));
+
+        // Auto start and the notification / HTTP paths go through start_timer too
+        app.tasks.push(Task::new("Review".to_string(), None));
+        app.active_task_index = Some(1);
+        app.state = TimerState::Paused;
+        app.start_timer();
+        assert!(app.tasks[1].start_date.is_some());
+    }
+
+    #[test]
+    fn instant_complete_leaves_timer_and_totals_alone() {
+        let mut app = App {
+            tasks: vec![Task::new("Call bank".to_string(), None), Task::new("Email".to_string(), None)],
+            active_task_index: Some(0),
+            ..App::default()
+        };
+        app.state = TimerState::Running;
+        app.time_remaining = Duration::from_secs(600);
+        app.pomodoros_completed_total = 2;
+
+        app.instant_complete_active_task();
+        assert!(app.tasks[0].completed);
+        assert!(app.tasks[0].completion_date.is_some());
+        assert_eq!(app.tasks[0].pomodoros, 0);
+        assert_eq!(app.active_task_index, Some(1));
+        assert_eq!(app.state, TimerState::Running);
+        assert_eq!(app.time_remaining, Duration::from_secs(600));
+        assert_eq!(app.pomodoros_completed_total, 2);
+    }
+
+    #[test]
+    fn known_labels_are_taken_from_task_input() {
+        let mut app = App::default();
+        assert!(app.add_label("UI"));
+        assert!(!app.add_label("@ui"));
+        let mut ui = UiState { current_input: "Fix crash @ui @work".to_string(), ..UiState::default() };
+        ui.submit_task(&mut app);
+        assert_eq!(app.tasks[0].name, "Fix crash");
+        assert_eq!(app.tasks[0].labels, vec!["UI".to_string()]);
+        assert_eq!(app.tasks[0].project.as_deref(), Some("work"));
+        assert!(ui_state::task_matches_filter(&app.tasks[0], "@ui"));
+
+        app.delete_label(0);
+        assert!(app.settings.available_labels.is_empty());
+        assert!(app.tasks[0].labels.is_empty());
+    }
+
+    #[test]
+    fn task_numbers_skip_completed_tasks_and_separators() {
+        let mut app = App::default();
+        let mut done = Task::new("Done".to_string(), None);
+        done.completed = true;
+        app.tasks = vec![
+            Task::new("First".to_string(), None),
+            done,
+            Task::separator(None),
+            Task::new("Second".to_string(), None),
+        ];
+        assert!(app.select_task_by_number(2, ""));
+        assert_eq!(app.active_task_index, Some(3));
+        assert!(!app.select_task_by_number(0, ""));
+        assert!(!app.select_task_by_number(3, ""));
+        assert_eq!(app.active_task_index, Some(3));
+
+        // Numbers follow the filtered list, so a hidden task can't be picked
+        assert!(app.select_task_by_number(1, "second"));
+        assert_eq!(app.active_task_index, Some(3));
+        assert!(!app.select_task_by_number(2, "second"));
+    }
+
+    #[test]
+    fn set_progress_wraps_at_long_break_interval() {
+        let mut app = App::default();
+        app.settings.long_break_interval = 4;
+        app.pomodoros_completed_total = 47;
+        assert_eq!(app.sessions_in_current_set(), 3);
+        app.pomodoros_completed_total = 48;
+        assert_eq!(app.sessions_in_current_set(), 0);
+    }
+
+    #[test]
+    fn zero_pomodoro_tasks_can_be_hidden_from_statistics() {
+        let mut app = App::default();
+        for (name, pomodoros) in [("Tracked", 2), ("Quick", 0)] {
+            let mut task = Task::new(name.to_string(), None);
+            task.pomodoros = pomodoros;
+            task.completed = true;
+            task.completion_date = Some(Utc::now());
+            app.tasks.push(task);
         }
+        assert_eq!(ui_state::completed_task_indices(&app, "").len(), 2);
+        assert_eq!(ui_state::hidden_zero_pomodoro_count(&app, ""), 0);
+
+        app.hide_zero_pomodoro_tasks = true;
+        assert_eq!(ui_state::completed_task_indices(&app, ""), vec![0]);
+        assert_eq!(ui_state::hidden_zero_pomodoro_count(&app, ""), 1);
+    }
+
+    #[test]
+    fn deleted_task_is_restored_at_its_index() {
+        let mut app = App {
+            tasks: ["One", "Two", "Three"].iter().map(|n| Task::new(n.to_string(), None)).collect(),
+            active_task_index: Some(2),
+            ..App::default()
+        };
+
+        app.remove_task_undoable(1);
+        assert_eq!(app.active_task_index, Some(1));
+        assert!(app.undo_last_deletion());
+        assert_eq!(app.tasks[1].name, "Two");
+        assert_eq!(app.active_task_index, Some(2));
+        assert!(!app.undo_last_deletion());
+
+        // Another deletion replaces what can be undone
+        app.remove_task_undoable(0);
+        app.remove_task(0);
+        assert!(!app.undo_last_deletion());
+    }
+
+    #[test]
+    fn daily_counts_groups_session_log_by_day() {
+        let mut app = App::default();
+        let now = Utc::now();
+        let entry = |mode, days_ago| {
+            let finished = now - ChronoDuration::days(days_ago);
+            SessionEntry { mode, started: finished, finished, task_ids: Vec::new() }
+        };
+        app.session_log = vec![
+            entry(Mode::Pomodoro, 0),
+            entry(Mode::Pomodoro, 0),
+            entry(Mode::ShortBreak, 0),
+            entry(Mode::Pomodoro, 2),
+            entry(Mode::Pomodoro, 9),
+        ];
+
+        assert_eq!(app.daily_counts(7), vec![0, 0, 0, 0, 1, 0, 2]);
+
+        app.mode = Mode::Pomodoro;
+        app.next_mode();
+        assert_eq!(app.daily_counts(1), vec![3]);
+    }
+
+    #[test]
+    fn daily_counts_falls_back_to_completed_tasks_before_the_log() {
+        let mut app = app_with_tasks(&["old", "logged"]);
+        let now = Utc::now();
+        app.tasks[0].completed = true;
+        app.tasks[0].pomodoros = 4;
+        app.tasks[0].completion_date = Some(now - ChronoDuration::days(3));
+        app.tasks[1].completed = true;
+        app.tasks[1].pomodoros = 9;
+        app.tasks[1].completion_date = Some(now);
+        assert_eq!(app.daily_counts(4), vec![4, 0, 0, 9]);
+
+        app.session_log.push(SessionEntry { mode: Mode::Pomodoro, started: now, finished: now, task_ids: Vec::new() });
+        assert_eq!(app.daily_counts(4), vec![4, 0, 0, 1]);
+    }
+
+    #[test]
+    fn pomodoros_today_counts_only_today_and_this_task() {
+        let mut app = app_with_tasks(&["a", "b"]);
+        app.tasks[0].pomodoros = 7;
+        app.active_task_index = Some(0);
+        app.mode = Mode::Pomodoro;
+        app.next_mode();
+        let yesterday = Utc::now() - ChronoDuration::days(1);
+        app.session_log.push(SessionEntry {
+            mode: Mode::Pomodoro,
+            started: yesterday,
+            finished: yesterday,
+            task_ids: vec![app.tasks[0].id],
+        });
+
+        assert_eq!(app.pomodoros_today_on(&app.tasks[0]), 1);
+        assert_eq!(app.pomodoros_today_on(&app.tasks[1]), 0);
+    }
+
+    #[test]
+    fn xdg_base_dir_prefers_absolute_env_value() {
+        let home = Some(PathBuf::from("/home/me"));
+        assert_eq!(
+            xdg_base_dir(Some("/data".into()), home.clone(), ".local/share"),
+            Some(PathBuf::from("/data"))
+        );
+        assert_eq!(
+            xdg_base_dir(Some("relative".into()), home.clone(), ".local/share"),
+            Some(PathBuf::from("/home/me/.local/share"))
+        );
+        assert_eq!(xdg_base_dir(None, home, ".config"), Some(PathBuf::from("/home/me/.config")));
+        assert_eq!(xdg_base_dir(None, None, ".config"), None);
+    }
+
+    #[test]
+    fn bulk_complete_can_be_undone() {
+        let mut app = App::default();
+        let mut done = Task::new("Done".to_string(), None);
+        done.completed = true;
+        app.tasks = vec![Task::new("A".to_string(), None), done, Task::new("B".to_string(), None)];
+        app.active_task_index = Some(0);
+        app.state = TimerState::Running;
+
+        let ids: Vec<u64> = app.tasks.iter().map(|t| t.id).collect();
+
+        let completed = app.mark_all_active_as_complete();
+        assert_eq!(completed, vec![ids[0], ids[2]]);
+        assert!(app.tasks.iter().all(|t| t.completed));
+        assert!(app.tasks[2].completion_date.is_some());
+        assert_eq!(app.state, TimerState::Paused);
+        assert_eq!(app.active_task_index, None);
+
+        // Moving tasks around doesn't send the undo to the wrong ones
+        app.tasks.swap(0, 1);
+        assert!(app.undo_bulk_complete(&completed));
+        assert!(!app.tasks[1].completed && !app.tasks[2].completed);
+        assert!(app.tasks[0].completed);
+        assert!(!app.undo_bulk_complete(&completed));
+    }
+
+    #[test]
+    fn bulk_complete_queues_and_undo_removes_recurrences() {
+        let mut app = app_with_tasks(&["standup", "email"]);
+        app.tasks[0].recurrence = Some(RecurrenceRule::Daily);
+
+        let completed = app.mark_all_active_as_complete();
+        assert_eq!(app.tasks.len(), 3);
+        assert!(app.tasks.iter().any(|t| t.name == "standup" && !t.completed && t.is_deferred()));
+
+        assert!(app.undo_bulk_complete(&completed));
+        assert_eq!(app.tasks.len(), 2);
+        assert!(app.tasks.iter().all(|t| !t.completed));
+        assert_eq!(app.tasks[0].recurrence, Some(RecurrenceRule::Daily));
+    }
+
+    #[test]
+    fn notes_counts_words_and_chars() {
+        let mut task = Task::new("Write".to_string(), None);
+        assert_eq!((task.notes_word_count(), task.notes_char_count()), (0, 0));
+        task.notes = Some("Draft intro\n  then édit".to_string());
+        assert_eq!(task.notes_word_count(), 4);
+        assert_eq!(task.notes_char_count(), 23);
+    }
+
+    #[test]
+    fn starting_the_timer_starts_a_task_session() {
+        let mut app = App {
+            tasks: vec![Task::new("Focus".to_string(), None)],
+            active_task_index: Some(0),
+            ..App::default()
+        };
+        assert!(app.tasks[0].session_elapsed().is_none());
+
+        app.toggle_timer();
+        assert_eq!(app.state, TimerState::Running);
+        assert!(app.tasks[0].session_elapsed().unwrap() < Duration::from_secs(1));
+
+        app.toggle_timer();
+        assert!(app.tasks[0].session_elapsed().is_none());
+
+        // A new segment starts a new session; breaks don't get one
+        app.start_timer();
+        app.next_mode();
+        assert_ne!(app.mode, Mode::Pomodoro);
+        assert!(app.tasks[0].session_elapsed().is_none());
+    }
+
+    #[test]
+    fn logged_session_starts_when_the_timer_first_ran() {
+        let mut app = app_with_tasks(&["a", "b"]);
+        app.active_task_index = Some(1);
+        app.start_timer();
+        let started = app.segment_started.unwrap();
+        app.pause_timer();
+        app.start_timer();
+        assert_eq!(app.segment_started, Some(started));
+
+        app.next_mode();
+        let entry = app.session_log.last().unwrap();
+        assert_eq!(entry.started, started);
+        assert_eq!(entry.task_ids, vec![app.tasks[1].id]);
+    }
+
+    #[test]
+    fn claimed_task_ids_are_unique_and_reserved() {
+        let mut app = app_with_tasks(&["a", "b", "c"]);
+        app.tasks[0].id = 1_000_000;
+        app.tasks[1].id = 1_000_000;
+        app.claim_task_ids();
+        assert_eq!(app.tasks[0].id, 1_000_000);
+        assert_ne!(app.tasks[1].id, 1_000_000);
+        assert!(next_task_id() > 1_000_000);
     }
 }
@@ -1,7 +1,13 @@
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use chrono::{Local, NaiveDate};
+use ratatui::layout::Rect;
 use ratatui_textarea::TextArea;
 
-use super::{App, InputMode, Task, TimerState, View, bump_duration_mins};
-use crate::settings::ColorTheme;
+use super::command::{fuzzy_match, Command};
+use super::{App, AppMessage, ContextOption, InputMode, Mode, StatisticsFilter, SubTask, Task, TaskTimers, TimerState, View, bump_duration_mins};
+use crate::settings::{BreakSchedule, BuiltinSound, ColorTheme, Settings};
 
 /// Splits `"Buy milk @work"` → `("Buy milk", Some("work"))`.
 /// The `@tag` can appear anywhere; it is stripped from the name.
@@ -21,16 +27,124 @@ pub fn parse_project(input: &str) -> (String, Option<String>) {
     (input.trim().to_string(), None)
 }
 
+/// Pulls `@word`s that name one of `available` labels out of the input:
+/// `"Fix crash @ui @work"` with label `UI` → `("Fix crash @work", ["UI"])`.
+/// Other `@word`s are left for `parse_project`.
+pub fn parse_labels(input: &str, available: &[String]) -> (String, Vec<String>) {
+    let mut labels: Vec<String> = Vec::new();
+    let mut rest = Vec::new();
+    for word in input.split_whitespace() {
+        let label = word.strip_prefix('@')
+            .and_then(|name| available.iter().find(|l| l.eq_ignore_ascii_case(name)));
+        match label {
+            Some(label) if !labels.contains(label) => labels.push(label.clone()),
+            Some(_) => {}
+            None => rest.push(word),
+        }
+    }
+    if labels.is_empty() {
+        (input.to_string(), labels)
+    } else {
+        (rest.join(" "), labels)
+    }
+}
+
+/// Splits a trailing `##PROJ-123` off a task name: `("Fix login", Some("PROJ-123"))`.
+pub fn parse_external_id(name: &str) -> (String, Option<String>) {
+    if let Some(at) = name.rfind("##") {
+        let id = &name[at + 2..];
+        let rest = name[..at].trim();
+        if !id.is_empty() && !id.contains(char::is_whitespace) && !rest.is_empty() {
+            return (rest.to_string(), Some(id.to_string()));
+        }
+    }
+    (name.to_string(), None)
+}
+
 pub fn task_matches_filter(task: &Task, filter: &str) -> bool {
+    if task.is_separator {
+        return false;
+    }
     task.name.to_lowercase().contains(filter)
-        || task.notes.as_deref().map_or(false, |n| n.to_lowercase().contains(filter))
-        || task.project.as_deref().map_or(false, |p| {
+        || task.labels.iter().any(|l| format!("@{}", l.to_lowercase()).contains(filter))
+        || task.notes.as_deref().is_some_and(|n| n.to_lowercase().contains(filter))
+        || task.project.as_deref().is_some_and(|p| {
             let tag = format!("@{}", p.to_lowercase());
             tag.contains(filter) || p.to_lowercase().contains(filter)
         })
 }
 
-const SETTINGS_ROW_COUNT: usize = 6;
+/// Indices into `app.tasks` of completed tasks matching `filter` and completed
+/// inside `stats_filter`, most recently completed first. This is the Statistics
+/// list order; `completed_task_list_state` indexes it.
+pub fn completed_task_indices(app: &App, filter: &str) -> Vec<usize> {
+    let mut indices: Vec<usize> = completed_matching(app, filter)
+        .filter(|(_, t)| !app.hide_zero_pomodoro_tasks || t.pomodoros > 0)
+        .map(|(i, _)| i)
+        .collect();
+    indices.sort_by(|&a, &b| app.tasks[b].completion_date.cmp(&app.tasks[a].completion_date));
+    indices
+}
+
+/// Completed tasks passing the text filter and the period filter.
+fn completed_matching<'a>(app: &'a App, filter: &'a str) -> impl Iterator<Item = (usize, &'a Task)> + 'a {
+    let today = Local::now().date_naive();
+    app.tasks.iter().enumerate()
+        .filter(move |(_, t)| t.completed && (filter.is_empty() || task_matches_filter(t, filter)))
+        .filter(move |(_, t)| app.stats_filter.includes_task(t, today))
+}
+
+/// How many tasks `completed_task_indices` leaves out for having no pomodoros.
+pub fn hidden_zero_pomodoro_count(app: &App, filter: &str) -> usize {
+    if !app.hide_zero_pomodoro_tasks {
+        return 0;
+    }
+    completed_matching(app, filter).filter(|(_, t)| t.pomodoros == 0).count()
+}
+
+/// Row before the last: "Edit Labels", which opens the label manager.
+const EDIT_LABELS_ROW: usize = 18;
+/// Last row of the settings table: "Reset to Defaults", activated with `Enter`.
+const RESET_SETTINGS_ROW: usize = 19;
+const SETTINGS_ROW_COUNT: usize = RESET_SETTINGS_ROW + 1;
+/// Choices for the Autosave setting besides Off.
+const AUTOSAVE_INTERVALS_SECS: [u64; 4] = [30, 60, 120, 300];
+pub const SCREENSAVER_TEXT: &str = "POMODORUST";
+const DOUBLE_CLICK_WINDOW: Duration = Duration::from_millis(400);
+/// Extra rows around a task that still hit it in accessibility mode.
+const ACCESSIBLE_HIT_SLOP_ROWS: u16 = 1;
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+const INPUT_HISTORY_LIMIT: usize = 20;
+/// Longest task name the input accepts, in characters.
+pub const MAX_TASK_NAME_LEN: usize = 200;
+
+fn rect_contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.x + area.width && row >= area.y && row < area.y + area.height
+}
+
+/// A bulk task-list action `Ctrl+Z` can take back, most recent last.
+#[derive(Debug)]
+pub enum TaskListUndo {
+    TimerReset(TaskTimers),
+    /// Ids of the tasks marked done by `Ctrl+Enter`.
+    BulkCompleted(Vec<u64>),
+}
+
+/// Which part of the screen the mouse pointer was last seen over.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MouseArea {
+    TaskList,
+    CompletedList,
+    Timer,
+    Other,
+}
+
+/// List selection captured when opening the Settings popup, restored on close.
+#[derive(Clone, Copy, Debug)]
+pub struct ScrollState {
+    pub active_task_index: Option<usize>,
+    pub completed_task_list_state: Option<usize>,
+}
 
 pub struct UiState {
     pub settings_selection: usize,
@@ -42,6 +156,61 @@ pub struct UiState {
     pub editing_task_index: Option<usize>,
     pub notes_textarea: Option<TextArea<'static>>,
     pub editing_notes_task_index: Option<usize>,
+    pub saved_scroll_state: Option<ScrollState>,
+    // Mouse hit-testing, refreshed by `draw_task_list` on every frame
+    pub task_row_map: Vec<(usize, u16)>,
+    pub task_list_area: Rect,
+    pub task_input_area: Rect,
+    pub completed_list_area: Rect,
+    pub last_click: Option<(Instant, usize)>,
+    pub mouse_area: Option<MouseArea>,
+    pub show_help_overlay: bool,
+    pub help_scroll: u16,
+    pub zen_mode: bool,
+    pub last_interaction: Instant,
+    /// Bouncing screensaver text: (x, y, dx, dy)
+    pub screensaver_pos: (i16, i16, i16, i16),
+    /// Mode switch awaiting [y/n] because the timer was running when it was requested.
+    pub pending_mode_switch: Option<Mode>,
+    pub toast: Option<(String, Instant)>,
+    /// Settings in effect before the last Ctrl+R reload, restorable with Ctrl+Z.
+    pub previous_settings: Option<Settings>,
+    pub show_mini_timer: bool,
+    /// Index into `app.tasks` of the task shown in the TaskDetails view.
+    pub details_task: Option<usize>,
+    pub subtask_selection: Option<usize>,
+    pub palette_query: String,
+    /// View the command palette was opened over, restored when it closes.
+    pub palette_return_view: View,
+    pub search_query: String,
+    /// View the global search was opened over, restored on `Esc`.
+    pub search_return_view: View,
+    /// Set by `Ctrl+L`; the main loop clears the terminal before the next frame.
+    pub force_redraw: bool,
+    /// Set by `Ctrl+E`; the main loop suspends the TUI and opens the state file in the editor.
+    pub edit_state_requested: bool,
+    /// Render task links as clickable OSC 8 hyperlinks.
+    pub hyperlinks: bool,
+    /// `Ctrl+A` in Statistics: every completed task is selected for deletion.
+    pub select_all_completed: bool,
+    /// Deleting every completed task awaits [y/n].
+    pub confirm_delete_all_completed: bool,
+    /// Restoring default settings awaits [y/n].
+    pub confirm_restore_defaults: bool,
+    /// `Ctrl+R` in the task list: resetting every task timer awaits [y/n].
+    pub confirm_reset_task_timers: bool,
+    /// `Ctrl+Enter` in the task list: completing every active task awaits [y/n].
+    pub confirm_complete_all: bool,
+    /// Bulk actions restorable with `Ctrl+Z` in the task list.
+    pub task_list_undo: Vec<TaskListUndo>,
+    /// Handed to notification threads so their button clicks reach the main loop.
+    pub app_messages: Option<Sender<AppMessage>>,
+    /// `--native-notif`: on macOS, notify through osascript instead of notify-rust.
+    pub native_notifications: bool,
+    /// Task input snapshots taken before each destructive edit, undone with `Ctrl+Z`.
+    pub input_history: Vec<String>,
+    /// Start date already entered in the Statistics custom range input.
+    pub range_start: Option<NaiveDate>,
 }
 
 impl Default for UiState {
@@ -56,11 +225,401 @@ impl Default for UiState {
             editing_task_index: None,
             notes_textarea: None,
             editing_notes_task_index: None,
+            saved_scroll_state: None,
+            task_row_map: Vec::new(),
+            task_list_area: Rect::default(),
+            task_input_area: Rect::default(),
+            completed_list_area: Rect::default(),
+            last_click: None,
+            mouse_area: None,
+            show_help_overlay: false,
+            help_scroll: 0,
+            zen_mode: false,
+            last_interaction: Instant::now(),
+            screensaver_pos: (0, 0, 1, 1),
+            pending_mode_switch: None,
+            toast: None,
+            previous_settings: None,
+            show_mini_timer: false,
+            details_task: None,
+            subtask_selection: None,
+            palette_query: String::new(),
+            palette_return_view: View::TaskList,
+            search_query: String::new(),
+            search_return_view: View::TaskList,
+            force_redraw: false,
+            edit_state_requested: false,
+            hyperlinks: crate::util::terminal_supports_hyperlinks(),
+            select_all_completed: false,
+            confirm_delete_all_completed: false,
+            confirm_restore_defaults: false,
+            confirm_reset_task_timers: false,
+            confirm_complete_all: false,
+            task_list_undo: Vec::new(),
+            app_messages: None,
+            native_notifications: false,
+            input_history: Vec::new(),
+            range_start: None,
         }
     }
 }
 
 impl UiState {
+    /// `Ctrl+V` in a single-line input: appends the clipboard text, cut off at
+    /// `MAX_TASK_NAME_LEN` while typing a task name.
+    pub fn paste_into_input(&mut self) {
+        match crate::util::clipboard_line() {
+            Ok(text) => {
+                for c in text.chars() {
+                    if !self.push_input_char(c) {
+                        break;
+                    }
+                }
+            }
+            Err(e) => self.show_toast(format!("Clipboard unavailable: {}", e)),
+        }
+    }
+
+    /// `Ctrl+C` in the task list: copies the active tasks as a numbered list.
+    pub fn copy_task_list(&mut self, app: &App) {
+        match crate::util::copy_to_clipboard(&app.active_tasks_text()) {
+            Ok(()) => self.show_toast("Copied task list"),
+            Err(e) => self.show_toast(format!("Clipboard unavailable: {}", e)),
+        }
+    }
+
+    /// Appends `c` unless a task name being typed is already `MAX_TASK_NAME_LEN`
+    /// long, in which case a toast says so. Returns whether it was added.
+    pub fn push_input_char(&mut self, c: char) -> bool {
+        let limited = matches!(self.input_mode, InputMode::Editing | InputMode::QuickStart);
+        if limited && self.current_input.chars().count() >= MAX_TASK_NAME_LEN {
+            self.show_toast(format!("Task names are limited to {} characters", MAX_TASK_NAME_LEN));
+            return false;
+        }
+        self.current_input.push(c);
+        true
+    }
+
+    fn snapshot_input(&mut self) {
+        if self.input_history.len() == INPUT_HISTORY_LIMIT {
+            self.input_history.remove(0);
+        }
+        self.input_history.push(self.current_input.clone());
+    }
+
+    pub fn input_backspace(&mut self) {
+        if !self.current_input.is_empty() {
+            self.snapshot_input();
+            self.current_input.pop();
+        }
+    }
+
+    /// `Ctrl+W`: drops the last word along with any trailing whitespace.
+    pub fn delete_input_word(&mut self) {
+        if self.current_input.is_empty() {
+            return;
+        }
+        self.snapshot_input();
+        let trimmed = self.current_input.trim_end();
+        let cut = trimmed.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        self.current_input.truncate(cut);
+    }
+
+    /// `Ctrl+U`: clears the whole input.
+    pub fn clear_input_line(&mut self) {
+        if !self.current_input.is_empty() {
+            self.snapshot_input();
+            self.current_input.clear();
+        }
+    }
+
+    /// `Ctrl+Z`: restores the input as it was before the last destructive edit.
+    pub fn undo_input(&mut self) {
+        if let Some(previous) = self.input_history.pop() {
+            self.current_input = previous;
+        }
+    }
+
+    pub fn show_toast(&mut self, message: impl Into<String>) {
+        self.toast = Some((message.into(), Instant::now()));
+    }
+
+    pub fn active_toast(&self) -> Option<&str> {
+        self.toast.as_ref()
+            .filter(|(_, shown_at)| shown_at.elapsed() < TOAST_DURATION)
+            .map(|(message, _)| message.as_str())
+    }
+
+    pub fn reload_settings(&mut self, app: &mut App) {
+        match Settings::load() {
+            Ok(settings) => {
+                self.previous_settings = Some(std::mem::replace(&mut app.settings, settings));
+                if app.state == TimerState::Paused {
+                    app.reset_timer();
+                }
+                self.show_toast("Config reloaded");
+            }
+            Err(e) => self.show_toast(format!("Config reload failed: {}", e)),
+        }
+    }
+
+    pub fn undo_settings_reload(&mut self, app: &mut App) {
+        if let Some(previous) = self.previous_settings.take() {
+            app.settings = previous;
+            if app.state == TimerState::Paused {
+                app.reset_timer();
+            }
+            self.show_toast("Config reload undone");
+        }
+    }
+
+    pub fn open_settings(&mut self, app: &mut App) {
+        self.saved_scroll_state = Some(ScrollState {
+            active_task_index: app.active_task_index,
+            completed_task_list_state: self.completed_task_list_state,
+        });
+        self.previous_view = app.current_view;
+        app.current_view = View::Settings;
+    }
+
+    pub fn open_command_palette(&mut self, app: &mut App) {
+        self.palette_query.clear();
+        self.palette_return_view = app.current_view;
+        app.current_view = View::CommandPalette { selected: 0 };
+    }
+
+    pub fn close_command_palette(&mut self, app: &mut App) {
+        self.palette_query.clear();
+        app.current_view = self.palette_return_view;
+    }
+
+    pub fn palette_matches(&self) -> Vec<Command> {
+        fuzzy_match(&self.palette_query, Command::ALL)
+    }
+
+    pub fn open_global_search(&mut self, app: &mut App) {
+        self.search_query.clear();
+        self.search_return_view = app.current_view;
+        app.current_view = View::GlobalSearch { selected: 0 };
+    }
+
+    /// Opens the project popup over the task list with the active task's project selected.
+    pub fn open_project_manager(&mut self, app: &mut App) {
+        let selected = app.active_task_index
+            .and_then(|i| app.tasks.get(i))
+            .and_then(|t| t.project.as_deref())
+            .and_then(|name| app.projects.iter().position(|p| p.name == name))
+            .map_or(0, |pos| pos + 1);
+        app.current_view = View::ProjectManager { selected };
+    }
+
+    /// Puts the active task in the project on row `selected` (row 0 clears it) and closes the popup.
+    pub fn pick_project(&mut self, app: &mut App, selected: usize) {
+        if let Some(idx) = app.active_task_index {
+            let project = selected.checked_sub(1)
+                .and_then(|i| app.projects.get(i))
+                .map(|p| p.name.clone());
+            app.set_task_project(idx, project);
+        }
+        app.current_view = View::TaskList;
+    }
+
+    pub fn submit_project(&mut self, app: &mut App) {
+        if app.add_project(&self.current_input) {
+            app.current_view = View::ProjectManager { selected: app.projects.len() };
+        } else if !self.current_input.trim().is_empty() {
+            self.show_toast("Project already exists");
+        }
+        self.current_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn close_global_search(&mut self, app: &mut App) {
+        self.search_query.clear();
+        app.current_view = self.search_return_view;
+    }
+
+    /// Jumps to `index`: active tasks are selected in the task list, completed
+    /// ones in the statistics list. Clears any list filter that would hide it.
+    pub fn go_to_task(&mut self, app: &mut App, index: usize) {
+        let Some(task) = app.tasks.get(index) else { return };
+        self.search_query.clear();
+        self.filter_input.clear();
+        self.previous_view = self.search_return_view;
+        if task.completed {
+            app.stats_filter = StatisticsFilter::AllTime;
+            self.completed_task_list_state = completed_task_indices(app, "").iter().position(|&i| i == index);
+            app.current_view = View::Statistics;
+        } else {
+            if task.is_deferred() {
+                app.show_deferred = true;
+            }
+            app.active_task_index = Some(index);
+            app.current_view = View::TaskList;
+        }
+    }
+
+    pub fn open_data_dir(&mut self) {
+        let result = match super::get_data_dir() {
+            Some(dir) => std::fs::create_dir_all(&dir).and_then(|_| crate::util::open_in_file_manager(&dir)),
+            None => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "no home directory")),
+        };
+        if let Err(e) = result {
+            self.show_toast(format!("Could not open data directory: {}", e));
+        }
+    }
+
+    /// `o` in task details: opens the task's external ID in the browser when it's a URL.
+    pub fn open_external_id(&mut self, app: &App) {
+        let Some(id) = self.details_task.and_then(|i| app.tasks.get(i)).and_then(|t| t.external_id.clone()) else {
+            return;
+        };
+        if !crate::util::is_web_url(&id) {
+            self.show_toast(format!("{} is not a URL", id));
+        } else if let Err(e) = crate::util::open_external(&id) {
+            self.show_toast(format!("Could not open {}: {}", id, e));
+        }
+    }
+
+    pub fn request_state_edit(&mut self) {
+        if crate::util::editor_from_env().is_some() {
+            self.edit_state_requested = true;
+        } else {
+            self.show_toast("Set $EDITOR to edit the state file");
+        }
+    }
+
+    pub fn close_settings(&mut self, app: &mut App) {
+        if let Some(saved) = self.saved_scroll_state.take() {
+            if saved.active_task_index.is_none_or(|i| i < app.tasks.len()) {
+                app.active_task_index = saved.active_task_index;
+            }
+            self.completed_task_list_state = saved.completed_task_list_state;
+        }
+        app.current_view = self.previous_view;
+    }
+
+    /// Task drawn on screen row `row`. Accessibility mode widens the target
+    /// to the lines just above and below a task when no task is on `row`.
+    fn task_at_row(&self, app: &App, row: u16) -> Option<usize> {
+        let at = |r: u16| self.task_row_map.iter().find(|(_, y)| *y == r).map(|&(idx, _)| idx);
+        at(row).or_else(|| {
+            if !app.settings.accessibility_mode {
+                return None;
+            }
+            (1..=ACCESSIBLE_HIT_SLOP_ROWS).find_map(|d| at(row.saturating_sub(d)).or_else(|| at(row.saturating_add(d))))
+        })
+    }
+
+    /// Left click in the TaskList view: select a task, toggle completion on
+    /// double click, or focus the "New Task" input box.
+    pub fn click_task_list(&mut self, app: &mut App, column: u16, row: u16) {
+        if rect_contains(self.task_input_area, column, row) {
+            self.input_mode = InputMode::Editing;
+            return;
+        }
+        if !rect_contains(self.task_list_area, column, row) {
+            return;
+        }
+        let Some(idx) = self.task_at_row(app, row) else {
+            return;
+        };
+        let double = self.last_click
+            .is_some_and(|(at, i)| i == idx && at.elapsed() <= DOUBLE_CLICK_WINDOW);
+        app.active_task_index = Some(idx);
+        if double {
+            app.complete_active_task();
+            self.last_click = None;
+        } else {
+            self.last_click = Some((Instant::now(), idx));
+        }
+    }
+
+    pub fn update_mouse_area(&mut self, app: &App, column: u16, row: u16) {
+        self.mouse_area = Some(match app.current_view {
+            View::TaskList if rect_contains(self.task_list_area, column, row) => MouseArea::TaskList,
+            View::Statistics if rect_contains(self.completed_list_area, column, row) => {
+                MouseArea::CompletedList
+            }
+            View::Timer => MouseArea::Timer,
+            _ => MouseArea::Other,
+        });
+    }
+
+    /// Scroll wheel moves the selection of whichever list is under the pointer.
+    pub fn scroll_hovered_list(&mut self, app: &mut App, down: bool) {
+        match (self.mouse_area, down) {
+            (Some(MouseArea::TaskList), true) => self.next_filtered_task(app),
+            (Some(MouseArea::TaskList), false) => self.previous_filtered_task(app),
+            (Some(MouseArea::CompletedList), true) => self.next_completed_task(app),
+            (Some(MouseArea::CompletedList), false) => self.previous_completed_task(app),
+            _ => {}
+        }
+    }
+
+    /// Right click in the TaskList view opens the context menu for the task under the cursor.
+    pub fn open_context_menu(&mut self, app: &mut App, column: u16, row: u16) {
+        if !rect_contains(self.task_list_area, column, row) {
+            return;
+        }
+        if let Some(idx) = self.task_at_row(app, row) {
+            app.active_task_index = Some(idx);
+            self.previous_view = app.current_view;
+            app.current_view = View::ContextMenu {
+                options: ContextOption::TASK_MENU,
+                selected: 0,
+                target_task: idx,
+                anchor: (column, row),
+            };
+        }
+    }
+
+    pub fn run_context_action(&mut self, app: &mut App, option: ContextOption, target: usize) {
+        if target >= app.tasks.len() {
+            return;
+        }
+        app.active_task_index = Some(target);
+        match option {
+            ContextOption::Complete => app.complete_active_task(),
+            ContextOption::Rename => self.start_rename(app),
+            ContextOption::Duplicate => app.duplicate_task(target),
+            ContextOption::Delete => app.delete_active_task(),
+            ContextOption::SetPriority => app.cycle_task_priority(target),
+            ContextOption::AddNote => self.start_edit_notes_active(app),
+        }
+    }
+
+    pub fn screensaver_active(&self, app: &App) -> bool {
+        app.current_view == View::Timer
+            && app.state == TimerState::Paused
+            && app.settings.screensaver_timeout
+                .is_some_and(|t| self.last_interaction.elapsed() > t)
+    }
+
+    /// Moves the screensaver text one step, bouncing off the edges of a `width`×`height` screen.
+    pub fn advance_screensaver(&mut self, width: u16, height: u16) {
+        let (mut x, mut y, mut dx, mut dy) = self.screensaver_pos;
+        let max_x = (width as i16 - SCREENSAVER_TEXT.len() as i16).max(0);
+        let max_y = (height as i16 - 1).max(0);
+        if x + dx < 0 || x + dx > max_x { dx = -dx; }
+        if y + dy < 0 || y + dy > max_y { dy = -dy; }
+        x = (x + dx).clamp(0, max_x);
+        y = (y + dy).clamp(0, max_y);
+        self.screensaver_pos = (x, y, dx, dy);
+    }
+
+    /// Switches immediately when paused; asks for confirmation first when running.
+    pub fn request_mode_switch(&mut self, app: &mut App, mode: Mode) {
+        if app.mode == mode {
+            return;
+        }
+        if app.state == TimerState::Running {
+            self.pending_mode_switch = Some(mode);
+        } else {
+            app.switch_mode(mode);
+        }
+    }
+
     pub fn next_setting(&mut self) {
         self.settings_selection = (self.settings_selection + 1) % SETTINGS_ROW_COUNT;
     }
@@ -73,6 +632,87 @@ impl UiState {
         }
     }
 
+    /// `Enter` in Settings; only the reset row reacts, by asking for confirmation.
+    pub fn activate_setting(&mut self, app: &mut App) {
+        match self.settings_selection {
+            EDIT_LABELS_ROW => app.current_view = View::LabelManager { selected: 0 },
+            RESET_SETTINGS_ROW => self.confirm_restore_defaults = true,
+            _ => {}
+        }
+    }
+
+    /// `S` in Settings: the preset picker, already asking for a name.
+    pub fn start_preset_save(&mut self, app: &mut App) {
+        app.current_view = View::PresetPicker { selected: 0 };
+        self.current_input.clear();
+        self.input_mode = InputMode::EditingPreset;
+    }
+
+    pub fn submit_preset(&mut self, app: &mut App) {
+        let name = self.current_input.trim().to_string();
+        if app.save_settings_preset(&name) {
+            let index = app.settings.presets.iter().position(|p| p.name == name).unwrap_or(0);
+            app.current_view = View::PresetPicker { selected: index };
+            self.show_toast(format!("Saved preset \"{}\"", name));
+        }
+        self.current_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn submit_label(&mut self, app: &mut App) {
+        if app.add_label(&self.current_input) {
+            app.current_view = View::LabelManager { selected: app.settings.available_labels.len() - 1 };
+        } else if !self.current_input.trim().is_empty() {
+            self.show_toast("Label already exists");
+        }
+        self.current_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Answer to the "reset all task timers?" prompt.
+    pub fn resolve_reset_task_timers(&mut self, app: &mut App, confirmed: bool) {
+        self.confirm_reset_task_timers = false;
+        if confirmed {
+            let timers = app.reset_all_task_timers();
+            self.task_list_undo.push(TaskListUndo::TimerReset(timers));
+            self.show_toast("Task timers reset ([Ctrl+Z] to undo)");
+        }
+    }
+
+    /// Answer to the "complete all active tasks?" prompt.
+    pub fn resolve_complete_all(&mut self, app: &mut App, confirmed: bool) {
+        self.confirm_complete_all = false;
+        if !confirmed {
+            return;
+        }
+        let completed = app.mark_all_active_as_complete();
+        if completed.is_empty() {
+            return;
+        }
+        let count = completed.len();
+        self.task_list_undo.push(TaskListUndo::BulkCompleted(completed));
+        self.show_toast(format!("Completed {} task{} ([Ctrl+Z] to undo)", count, if count == 1 { "" } else { "s" }));
+    }
+
+    /// `Ctrl+Z` in the task list: takes back the latest bulk action.
+    pub fn undo_task_list_action(&mut self, app: &mut App) {
+        let restored = match self.task_list_undo.pop() {
+            Some(TaskListUndo::TimerReset(timers)) => app.restore_task_timers(timers).then_some("Task timer reset undone"),
+            Some(TaskListUndo::BulkCompleted(ids)) => app.undo_bulk_complete(&ids).then_some("Tasks marked active again"),
+            None => return,
+        };
+        self.show_toast(restored.unwrap_or("Can't undo: the task list has changed"));
+    }
+
+    /// Answer to the "restore default settings?" prompt.
+    pub fn resolve_restore_defaults(&mut self, app: &mut App, confirmed: bool) {
+        self.confirm_restore_defaults = false;
+        if confirmed {
+            app.restore_default_settings();
+            self.show_toast("Settings restored to defaults");
+        }
+    }
+
     pub fn modify_setting(&mut self, app: &mut App, increase: bool) {
         let delta: i64 = if increase { 1 } else { -1 };
         match self.settings_selection {
@@ -101,6 +741,59 @@ impl UiState {
                 let current = app.settings.long_break_interval as i64;
                 app.settings.long_break_interval = (current + delta).max(1) as u32;
             }
+            6 => {
+                let current = app.settings.screensaver_timeout.map_or(0, |d| d.as_secs() / 60) as i64;
+                let mins = (current + delta).max(0) as u64;
+                app.settings.screensaver_timeout = if mins == 0 {
+                    None
+                } else {
+                    Some(std::time::Duration::from_secs(mins * 60))
+                };
+            }
+            7 => app.settings.parallel_mode = !app.settings.parallel_mode,
+            // Warning can't exceed danger, and danger can't drop below warning
+            8 => {
+                let days = (app.settings.task_age_warning_days as i64 + delta).max(1) as u64;
+                app.settings.task_age_warning_days = days.min(app.settings.task_age_danger_days);
+            }
+            9 => {
+                let days = (app.settings.task_age_danger_days as i64 + delta).max(1) as u64;
+                app.settings.task_age_danger_days = days.max(app.settings.task_age_warning_days);
+            }
+            10 => {
+                let all = BreakSchedule::ALL;
+                let cur = all.iter().position(|s| *s == app.settings.break_schedule).unwrap_or(0);
+                let next = (cur as i64 + delta).rem_euclid(all.len() as i64) as usize;
+                app.settings.break_schedule = all[next];
+            }
+            11 => {
+                let views = [View::Timer, View::TaskList, View::Statistics];
+                let cur = views.iter().position(|v| *v == app.settings.start_view).unwrap_or(1);
+                let next = (cur as i64 + delta).rem_euclid(views.len() as i64) as usize;
+                app.settings.start_view = views[next];
+            }
+            12 => app.settings.show_seconds = !app.settings.show_seconds,
+            13 => app.settings.countdown_beeps = !app.settings.countdown_beeps,
+            14 | 15 => {
+                let sound = if self.settings_selection == 14 {
+                    &mut app.settings.pomodoro_end_sound
+                } else {
+                    &mut app.settings.break_end_sound
+                };
+                let all = BuiltinSound::ALL;
+                let cur = all.iter().position(|s| s == sound).unwrap_or(0);
+                *sound = all[(cur as i64 + delta).rem_euclid(all.len() as i64) as usize];
+            }
+            16 => {
+                // Off, then each of the preset intervals
+                let steps: Vec<Option<u64>> =
+                    std::iter::once(None).chain(AUTOSAVE_INTERVALS_SECS.map(Some)).collect();
+                let current = app.settings.autosave_interval.map(|d| d.as_secs());
+                let cur = steps.iter().position(|s| *s == current).unwrap_or(0);
+                let next = (cur as i64 + delta).rem_euclid(steps.len() as i64) as usize;
+                app.settings.autosave_interval = steps[next].map(std::time::Duration::from_secs);
+            }
+            17 => app.settings.accessibility_mode = !app.settings.accessibility_mode,
             _ => {}
         }
         if app.state == TimerState::Paused {
@@ -109,10 +802,13 @@ impl UiState {
     }
 
     fn filtered_completed_count(&self, app: &App) -> usize {
-        let filter = self.filter_input.to_lowercase();
-        app.tasks.iter()
-            .filter(|t| t.completed && (filter.is_empty() || task_matches_filter(t, &filter)))
-            .count()
+        completed_task_indices(app, &self.filter_input.to_lowercase()).len()
+    }
+
+    /// Index into `app.tasks` of the task selected in the Statistics list.
+    pub fn selected_completed_task_index(&self, app: &App) -> Option<usize> {
+        let selected = self.completed_task_list_state?;
+        completed_task_indices(app, &self.filter_input.to_lowercase()).get(selected).copied()
     }
 
     pub fn next_completed_task(&mut self, app: &App) {
@@ -122,6 +818,21 @@ impl UiState {
         self.completed_task_list_state = Some(i);
     }
 
+    /// `PgDn` in Statistics: a page down, stopping at the last task.
+    pub fn next_completed_task_page(&mut self, app: &App) {
+        let count = self.filtered_completed_count(app);
+        if count == 0 { return; }
+        let page = Self::page_size(self.completed_list_area);
+        self.completed_task_list_state = Some(self.completed_task_list_state.map_or(0, |i| (i + page).min(count - 1)));
+    }
+
+    /// `PgUp` in Statistics: a page up, stopping at the first task.
+    pub fn previous_completed_task_page(&mut self, app: &App) {
+        if self.filtered_completed_count(app) == 0 { return; }
+        let page = Self::page_size(self.completed_list_area);
+        self.completed_task_list_state = Some(self.completed_task_list_state.map_or(0, |i| i.saturating_sub(page)));
+    }
+
     pub fn previous_completed_task(&mut self, app: &App) {
         let count = self.filtered_completed_count(app);
         if count == 0 { return; }
@@ -131,25 +842,89 @@ impl UiState {
         self.completed_task_list_state = Some(i);
     }
 
-    pub fn delete_selected_completed_task(&mut self, app: &mut App) {
-        if let Some(selected) = self.completed_task_list_state {
-            let filter = self.filter_input.to_lowercase();
-            let completed_indices: Vec<usize> = app.tasks.iter().enumerate()
-                .filter(|(_, t)| t.completed && (filter.is_empty() || task_matches_filter(t, &filter)))
-                .map(|(i, _)| i)
-                .collect();
-            if let Some(&idx) = completed_indices.get(selected) {
-                app.tasks.remove(idx);
-                if let Some(active) = app.active_task_index {
-                    if active > idx {
-                        app.active_task_index = Some(active - 1);
-                    }
-                }
-                self.completed_task_list_state = None;
+    /// `f` in Statistics: all time → today → this week → this month.
+    pub fn cycle_stats_filter(&mut self, app: &mut App) {
+        self.set_stats_filter(app, app.stats_filter.next());
+    }
+
+    fn set_stats_filter(&mut self, app: &mut App, filter: StatisticsFilter) {
+        app.stats_filter = filter;
+        self.completed_task_list_state = None;
+    }
+
+    /// `Ctrl+H` in Statistics: hides or shows completed tasks without pomodoros.
+    pub fn toggle_hide_zero_pomodoro_tasks(&mut self, app: &mut App) {
+        app.hide_zero_pomodoro_tasks = !app.hide_zero_pomodoro_tasks;
+        self.completed_task_list_state = None;
+    }
+
+    /// `F` in Statistics: asks for the start, then the end of a custom range.
+    pub fn start_stats_range_input(&mut self) {
+        self.range_start = None;
+        self.current_input.clear();
+        self.input_mode = InputMode::EditingStatsRange;
+    }
+
+    /// `Enter` in the range input: keeps the start date, or applies the range
+    /// once the end is in. Dates out of order are swapped.
+    pub fn submit_stats_range(&mut self, app: &mut App) {
+        let Ok(date) = NaiveDate::parse_from_str(self.current_input.trim(), "%Y-%m-%d") else {
+            self.show_toast("Dates look like 2024-03-01");
+            return;
+        };
+        self.current_input.clear();
+        match self.range_start.take() {
+            None => self.range_start = Some(date),
+            Some(start) => {
+                self.set_stats_filter(app, StatisticsFilter::CustomRange(start.min(date), start.max(date)));
+                self.input_mode = InputMode::Normal;
             }
         }
     }
 
+    pub fn cancel_stats_range(&mut self) {
+        self.range_start = None;
+        self.current_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Drops the completed-task cursor and any select-all.
+    pub fn clear_completed_selection(&mut self) {
+        self.completed_task_list_state = None;
+        self.select_all_completed = false;
+    }
+
+    pub fn delete_selected_completed_task(&mut self, app: &mut App) {
+        if self.select_all_completed {
+            self.confirm_delete_all_completed = app.tasks.iter().any(|t| t.completed);
+            return;
+        }
+        if let Some(idx) = self.selected_completed_task_index(app) {
+            app.remove_task_undoable(idx);
+            self.completed_task_list_state = None;
+            self.show_toast("Task deleted ([Ctrl+U] to undo)");
+        }
+    }
+
+    pub fn undo_completed_task_deletion(&mut self, app: &mut App) {
+        if app.undo_last_deletion() {
+            self.completed_task_list_state = None;
+            self.show_toast("Task restored");
+        }
+    }
+
+    /// Answer to the "delete all completed tasks?" prompt.
+    pub fn resolve_delete_all_completed(&mut self, app: &mut App, confirmed: bool) {
+        self.confirm_delete_all_completed = false;
+        if confirmed {
+            let visible = completed_task_indices(app, &self.filter_input.to_lowercase());
+            let removed = app.delete_completed_tasks(&visible);
+            self.select_all_completed = false;
+            self.completed_task_list_state = None;
+            self.show_toast(format!("Deleted {} completed task{}", removed, if removed == 1 { "" } else { "s" }));
+        }
+    }
+
     fn open_notes_for_task(&mut self, idx: usize, app: &App) {
         if let Some(task) = app.tasks.get(idx) {
             let lines: Vec<String> = task.notes.as_deref()
@@ -169,20 +944,67 @@ impl UiState {
         }
     }
 
-    // Open notes editor for the selected completed task (called from TaskDetails)
+    // Open notes editor for the task shown in TaskDetails
     pub fn start_edit_notes(&mut self, app: &App) {
-        if let Some(selected) = self.completed_task_list_state {
-            let filter = self.filter_input.to_lowercase();
-            if let Some(idx) = app.tasks.iter().enumerate()
-                .filter(|(_, t)| t.completed && (filter.is_empty() || task_matches_filter(t, &filter)))
-                .nth(selected)
-                .map(|(i, _)| i)
-            {
-                self.open_notes_for_task(idx, app);
+        if let Some(idx) = self.details_task {
+            self.open_notes_for_task(idx, app);
+        }
+    }
+
+    pub fn open_task_details(&mut self, app: &mut App, idx: usize) {
+        self.details_task = Some(idx);
+        self.subtask_selection = app.tasks.get(idx)
+            .filter(|t| !t.subtasks.is_empty())
+            .map(|_| 0);
+        self.previous_view = app.current_view;
+        app.current_view = View::TaskDetails;
+    }
+
+    pub fn next_subtask(&mut self, app: &App) {
+        let count = self.details_task.and_then(|i| app.tasks.get(i)).map_or(0, |t| t.subtasks.len());
+        if count == 0 { return; }
+        self.subtask_selection = Some(self.subtask_selection.map_or(0, |i| (i + 1) % count));
+    }
+
+    pub fn previous_subtask(&mut self, app: &App) {
+        let count = self.details_task.and_then(|i| app.tasks.get(i)).map_or(0, |t| t.subtasks.len());
+        if count == 0 { return; }
+        self.subtask_selection = Some(self.subtask_selection.map_or(0, |i| {
+            if i == 0 { count - 1 } else { i - 1 }
+        }));
+    }
+
+    pub fn toggle_selected_subtask(&mut self, app: &mut App) {
+        if let (Some(task_idx), Some(sub_idx)) = (self.details_task, self.subtask_selection) {
+            if let Some(sub) = app.tasks.get_mut(task_idx).and_then(|t| t.subtasks.get_mut(sub_idx)) {
+                sub.completed = !sub.completed;
             }
         }
     }
 
+    pub fn submit_subtask(&mut self, app: &mut App) {
+        let name = self.current_input.trim().to_string();
+        if !name.is_empty() {
+            if let Some(task) = self.details_task.and_then(|i| app.tasks.get_mut(i)) {
+                task.subtasks.push(SubTask { name, completed: false });
+                self.subtask_selection = Some(task.subtasks.len() - 1);
+            }
+        }
+        self.current_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn submit_link(&mut self, app: &mut App) {
+        let url = self.current_input.trim().to_string();
+        if !url.is_empty() {
+            if let Some(task) = self.details_task.and_then(|i| app.tasks.get_mut(i)) {
+                task.links.push(url);
+            }
+        }
+        self.current_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
     // Open notes editor for the active task (called from TaskList)
     pub fn start_edit_notes_active(&mut self, app: &App) {
         if let Some(idx) = app.active_task_index {
@@ -209,8 +1031,8 @@ impl UiState {
     pub fn next_filtered_task(&mut self, app: &mut App) {
         let filter = self.filter_input.to_lowercase();
         if filter.is_empty() { app.next_task(); return; }
-        let indices: Vec<usize> = app.tasks.iter().enumerate()
-            .filter(|(_, t)| !t.completed && task_matches_filter(t, &filter))
+        let indices: Vec<usize> = app.active_tasks().into_iter()
+            .filter(|(_, t)| task_matches_filter(t, &filter))
             .map(|(i, _)| i)
             .collect();
         if indices.is_empty() { return; }
@@ -220,11 +1042,39 @@ impl UiState {
         app.active_task_index = Some(indices[next]);
     }
 
+    /// Rows moved by `PgUp`/`PgDn`: the list's inner height, less its borders.
+    pub fn page_size(area: Rect) -> usize {
+        (area.height.saturating_sub(2) as usize).max(1)
+    }
+
+    pub fn next_filtered_task_page(&mut self, app: &mut App) {
+        self.page_filtered_tasks(app, true);
+    }
+
+    pub fn previous_filtered_task_page(&mut self, app: &mut App) {
+        self.page_filtered_tasks(app, false);
+    }
+
+    // A page through the tasks matching the filter, clamped at either end
+    fn page_filtered_tasks(&mut self, app: &mut App, down: bool) {
+        let n = Self::page_size(self.task_list_area);
+        let filter = self.filter_input.to_lowercase();
+        if filter.is_empty() {
+            if down { app.next_task_by(n) } else { app.previous_task_by(n) }
+            return;
+        }
+        let indices: Vec<usize> = app.active_tasks().into_iter()
+            .filter(|(_, t)| task_matches_filter(t, &filter))
+            .map(|(i, _)| i)
+            .collect();
+        app.page_selection(&indices, n, down);
+    }
+
     pub fn previous_filtered_task(&mut self, app: &mut App) {
         let filter = self.filter_input.to_lowercase();
         if filter.is_empty() { app.previous_task(); return; }
-        let indices: Vec<usize> = app.tasks.iter().enumerate()
-            .filter(|(_, t)| !t.completed && task_matches_filter(t, &filter))
+        let indices: Vec<usize> = app.active_tasks().into_iter()
+            .filter(|(_, t)| task_matches_filter(t, &filter))
             .map(|(i, _)| i)
             .collect();
         if indices.is_empty() { return; }
@@ -234,42 +1084,202 @@ impl UiState {
         app.active_task_index = Some(indices[prev]);
     }
 
+    /// `b` in the task list: asks for the selected task's time budget in minutes,
+    /// prefilled with the current one.
+    pub fn start_budget_input(&mut self, app: &App) {
+        if let Some(task) = app.active_task_index.and_then(|i| app.tasks.get(i)) {
+            if !task.completed {
+                self.current_input = task.time_budget.map_or(String::new(), |d| (d.as_secs() / 60).to_string());
+                self.input_mode = InputMode::EditingBudget;
+            }
+        }
+    }
+
+    /// Empty input clears the budget; anything but a whole number of minutes is rejected.
+    pub fn submit_budget(&mut self, app: &mut App) {
+        let input = self.current_input.trim();
+        if input.is_empty() {
+            app.set_active_task_budget(None);
+        } else if let Ok(minutes) = input.parse::<u64>() {
+            app.set_active_task_budget(Some(minutes));
+        } else {
+            self.show_toast("Budget must be a whole number of minutes");
+            return;
+        }
+        self.current_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn submit_go_to(&mut self, app: &mut App) {
+        let filter = self.filter_input.to_lowercase();
+        let input = self.current_input.trim();
+        if !input.is_empty() && !input.parse().is_ok_and(|n| app.select_task_by_number(n, &filter)) {
+            self.show_toast(format!("No task #{}", input));
+        }
+        self.current_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
     pub fn start_rename(&mut self, app: &App) {
         if let Some(idx) = app.active_task_index {
             if let Some(task) = app.tasks.get(idx) {
                 if !task.completed {
                     self.editing_task_index = Some(idx);
-                    self.current_input = match &task.project {
-                        Some(p) => format!("{} @{}", task.name, p),
-                        None => task.name.clone(),
-                    };
+                    self.current_input = task.name.clone();
+                    for label in &task.labels {
+                        self.current_input.push_str(&format!(" @{}", label));
+                    }
+                    if let Some(p) = &task.project {
+                        self.current_input.push_str(&format!(" @{}", p));
+                    }
                     self.input_mode = InputMode::Editing;
                 }
             }
         }
     }
 
+    pub fn submit_quick_start(&mut self, app: &mut App) {
+        let input = self.current_input.trim();
+        let (name, project) = if input.is_empty() {
+            ("Unnamed task".to_string(), None)
+        } else {
+            parse_project(input)
+        };
+        app.tasks.push(Task::new(name, project));
+        app.register_task_projects();
+        app.active_task_index = Some(app.tasks.len() - 1);
+        if app.mode != Mode::Pomodoro {
+            app.switch_mode(Mode::Pomodoro);
+        }
+        app.start_timer();
+        self.current_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
     pub fn submit_task(&mut self, app: &mut App) {
         if let Some(idx) = self.editing_task_index.take() {
             if !self.current_input.is_empty() {
-                let (name, project) = parse_project(&self.current_input);
+                let (input, labels) = parse_labels(&self.current_input, &app.settings.available_labels);
+                let (name, project) = parse_project(&input);
+                let (name, external_id) = parse_external_id(&name);
                 if let Some(task) = app.tasks.get_mut(idx) {
                     task.name = name;
                     task.project = project;
+                    task.labels = labels;
+                    if external_id.is_some() {
+                        task.external_id = external_id;
+                    }
                 }
+                app.register_task_projects();
             }
             self.current_input.clear();
             self.input_mode = InputMode::Normal;
         } else {
             if !self.current_input.is_empty() {
-                let (name, project) = parse_project(&self.current_input);
-                app.tasks.push(Task::new(name, project));
+                let (input, labels) = parse_labels(&self.current_input, &app.settings.available_labels);
+                let (name, project) = parse_project(&input);
+                let (name, external_id) = parse_external_id(&name);
+                app.tasks.push(Task { labels, external_id, ..Task::new(name, project) });
+                app.register_task_projects();
                 self.current_input.clear();
-                if app.tasks.len() == 1 {
-                    app.active_task_index = Some(0);
+                // Tasks added from the timer view become the one being timed
+                if app.tasks.len() == 1 || app.current_view == View::Timer {
+                    app.active_task_index = Some(app.tasks.len() - 1);
                 }
             }
             self.input_mode = InputMode::Normal;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ui_with_input(input: &str) -> UiState {
+        UiState { current_input: input.to_string(), ..UiState::default() }
+    }
+
+    #[test]
+    fn delete_input_word_stops_at_word_boundaries() {
+        let mut ui = ui_with_input("write the docs  ");
+        ui.delete_input_word();
+        assert_eq!(ui.current_input, "write the ");
+        ui.delete_input_word();
+        assert_eq!(ui.current_input, "write ");
+        ui.delete_input_word();
+        assert_eq!(ui.current_input, "");
+
+        ui.delete_input_word();
+        assert_eq!(ui.input_history.len(), 3, "an empty input records no undo step");
+
+        let mut ui = ui_with_input("   ");
+        ui.delete_input_word();
+        assert_eq!(ui.current_input, "");
+    }
+
+    #[test]
+    fn clear_input_line_empties_the_input() {
+        let mut ui = ui_with_input("half-typed task");
+        ui.clear_input_line();
+        assert_eq!(ui.current_input, "");
+        ui.clear_input_line();
+        assert_eq!(ui.input_history, vec!["half-typed task".to_string()]);
+    }
+
+    #[test]
+    fn undo_input_walks_back_through_edits() {
+        let mut ui = ui_with_input("fix login bug");
+        ui.input_backspace();
+        ui.delete_input_word();
+        ui.clear_input_line();
+        assert_eq!(ui.current_input, "");
+
+        ui.undo_input();
+        assert_eq!(ui.current_input, "fix login ");
+        ui.undo_input();
+        assert_eq!(ui.current_input, "fix login bu");
+        ui.undo_input();
+        assert_eq!(ui.current_input, "fix login bug");
+        ui.undo_input();
+        assert_eq!(ui.current_input, "fix login bug");
+    }
+
+    #[test]
+    fn input_history_keeps_only_the_latest_steps() {
+        let mut ui = ui_with_input(&"x".repeat(INPUT_HISTORY_LIMIT + 5));
+        for _ in 0..INPUT_HISTORY_LIMIT + 5 {
+            ui.input_backspace();
+        }
+        assert_eq!(ui.input_history.len(), INPUT_HISTORY_LIMIT);
+        for _ in 0..INPUT_HISTORY_LIMIT {
+            ui.undo_input();
+        }
+        assert_eq!(ui.current_input, "x".repeat(INPUT_HISTORY_LIMIT));
+    }
+
+    #[test]
+    fn completed_paging_stops_at_the_ends() {
+        let mut app = App::default();
+        for name in ["a", "b", "c", "d", "e"] {
+            let mut task = Task::new(name.to_string(), None);
+            task.completed = true;
+            app.tasks.push(task);
+        }
+        // Two rows inside the borders
+        let mut ui = UiState { completed_list_area: Rect::new(0, 0, 20, 4), ..UiState::default() };
+
+        ui.next_completed_task_page(&app);
+        assert_eq!(ui.completed_task_list_state, Some(0));
+        ui.next_completed_task_page(&app);
+        ui.next_completed_task_page(&app);
+        assert_eq!(ui.completed_task_list_state, Some(4));
+        ui.next_completed_task_page(&app);
+        assert_eq!(ui.completed_task_list_state, Some(4));
+        ui.previous_completed_task_page(&app);
+        assert_eq!(ui.completed_task_list_state, Some(2));
+        ui.previous_completed_task_page(&app);
+        ui.previous_completed_task_page(&app);
+        assert_eq!(ui.completed_task_list_state, Some(0));
+    }
+}
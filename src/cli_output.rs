@@ -0,0 +1,83 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::app::Task;
+
+/// How `--list-tasks` and `--list-completed` print tasks.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// A numbered list, one task per line in `Task`'s `Display` form.
+    #[default]
+    Text,
+    /// A JSON array of `{name, pomodoros, time_spent_secs}`.
+    Json,
+}
+
+#[derive(Serialize)]
+struct TaskEntry<'a> {
+    name: &'a str,
+    pomodoros: u32,
+    time_spent_secs: u64,
+}
+
+pub fn print_task_list(tasks: &[&Task], format: OutputFormat) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    writeln!(out, "{}", render_task_list(tasks, format)?)
+}
+
+fn render_task_list(tasks: &[&Task], format: OutputFormat) -> io::Result<String> {
+    match format {
+        OutputFormat::Text => Ok(tasks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| format!("{}. {}", i + 1, t))
+            .collect::<Vec<_>>()
+            .join("\n")),
+        OutputFormat::Json => {
+            let entries: Vec<TaskEntry> = tasks
+                .iter()
+                .map(|t| TaskEntry {
+                    name: &t.name,
+                    pomodoros: t.pomodoros,
+                    time_spent_secs: t.time_spent.as_secs(),
+                })
+                .collect();
+            serde_json::to_string(&entries).map_err(io::Error::other)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn tasks() -> Vec<Task> {
+        let mut docs = Task::new("Write docs".to_string(), Some("work".to_string()));
+        docs.pomodoros = 3;
+        docs.time_spent = Duration::from_secs(4500);
+        vec![docs, Task::new("Call \"Bob\"".to_string(), None)]
+    }
+
+    #[test]
+    fn text_is_a_numbered_list() {
+        let tasks = tasks();
+        let refs: Vec<&Task> = tasks.iter().collect();
+        assert_eq!(
+            render_task_list(&refs, OutputFormat::Text).unwrap(),
+            "1. [ ] Write docs | 3● | 1h 15m\n2. [ ] Call \"Bob\" | 0● | 0m"
+        );
+    }
+
+    #[test]
+    fn json_has_name_pomodoros_and_time() {
+        let tasks = tasks();
+        let refs: Vec<&Task> = tasks.iter().collect();
+        assert_eq!(
+            render_task_list(&refs, OutputFormat::Json).unwrap(),
+            r#"[{"name":"Write docs","pomodoros":3,"time_spent_secs":4500},{"name":"Call \"Bob\"","pomodoros":0,"time_spent_secs":0}]"#
+        );
+    }
+}
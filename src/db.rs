@@ -1,15 +1,35 @@
+use std::collections::HashSet;
 use std::path::Path;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result};
+use serde::Deserialize;
 
-use crate::app::{App, Mode, Task, View};
+use crate::app::{reserve_task_id, App, Mode, Priority, Project, SessionEntry, Task, TaskColor};
+
+// Task columns added after the initial schema. Existing databases get them
+// via ALTER TABLE on open; new databases go through the same path.
+const TASK_COLUMN_MIGRATIONS: &[(&str, &str)] = &[
+    ("priority", "TEXT"),
+    ("subtasks", "TEXT"),
+    ("deferred_until", "TEXT"),
+    ("color", "TEXT"),
+    ("recurrence", "TEXT"),
+    ("links", "TEXT"),
+    ("estimated_pomodoros", "INTEGER"),
+    ("is_separator", "INTEGER"),
+    ("time_budget_secs", "INTEGER"),
+    ("external_id", "TEXT"),
+    ("start_date", "TEXT"),
+    ("labels", "TEXT"),
+];
 
 pub fn open_and_init(path: &Path) -> Result<Connection> {
     let conn = Connection::open(path)?;
     conn.execute_batch("PRAGMA journal_mode=WAL;")?;
     init_schema(&conn)?;
+    migrate_schema(&conn)?;
     Ok(conn)
 }
 
@@ -30,10 +50,89 @@ fn init_schema(conn: &Connection) -> Result<()> {
         CREATE TABLE IF NOT EXISTS app_state (
             key   TEXT PRIMARY KEY,
             value TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS sessions (
+            id       INTEGER PRIMARY KEY AUTOINCREMENT,
+            mode     TEXT NOT NULL,
+            started  TEXT NOT NULL,
+            finished TEXT NOT NULL,
+            task_ids TEXT NOT NULL
         );",
     )
 }
 
+fn migrate_schema(conn: &Connection) -> Result<()> {
+    let existing: Vec<String> = {
+        let mut stmt = conn.prepare("PRAGMA table_info(tasks)")?;
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>>>()?;
+        names
+    };
+    for (name, decl) in TASK_COLUMN_MIGRATIONS {
+        if !existing.iter().any(|c| c == name) {
+            conn.execute_batch(&format!("ALTER TABLE tasks ADD COLUMN {} {};", name, decl))?;
+        }
+    }
+    Ok(())
+}
+
+fn priority_to_str(p: Priority) -> &'static str {
+    match p {
+        Priority::Low => "Low",
+        Priority::Medium => "Medium",
+        Priority::High => "High",
+    }
+}
+
+fn priority_from_str(s: &str) -> Option<Priority> {
+    match s {
+        "Low" => Some(Priority::Low),
+        "Medium" => Some(Priority::Medium),
+        "High" => Some(Priority::High),
+        _ => None,
+    }
+}
+
+fn color_to_str(c: TaskColor) -> &'static str {
+    match c {
+        TaskColor::Red => "Red",
+        TaskColor::Green => "Green",
+        TaskColor::Blue => "Blue",
+        TaskColor::Yellow => "Yellow",
+        TaskColor::Magenta => "Magenta",
+        TaskColor::Cyan => "Cyan",
+    }
+}
+
+fn color_from_str(s: &str) -> Option<TaskColor> {
+    match s {
+        "Red" => Some(TaskColor::Red),
+        "Green" => Some(TaskColor::Green),
+        "Blue" => Some(TaskColor::Blue),
+        "Yellow" => Some(TaskColor::Yellow),
+        "Magenta" => Some(TaskColor::Magenta),
+        "Cyan" => Some(TaskColor::Cyan),
+        _ => None,
+    }
+}
+
+fn mode_to_str(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Pomodoro => "Pomodoro",
+        Mode::ShortBreak => "ShortBreak",
+        Mode::LongBreak => "LongBreak",
+    }
+}
+
+fn mode_from_str(s: &str) -> Mode {
+    match s {
+        "ShortBreak" => Mode::ShortBreak,
+        "LongBreak" => Mode::LongBreak,
+        _ => Mode::Pomodoro,
+    }
+}
+
 fn get_state(conn: &Connection, key: &str) -> Option<String> {
     conn.query_row(
         "SELECT value FROM app_state WHERE key = ?1",
@@ -47,51 +146,81 @@ pub struct LoadedState {
     pub tasks: Vec<Task>,
     pub mode: Mode,
     pub pomodoros_total: u32,
-    pub current_view: View,
     pub active_task_index: Option<usize>,
+    pub active_task_indices: HashSet<usize>,
     pub time_remaining_secs: Option<u64>,
+    pub projects: Vec<Project>,
+    pub session_log: Vec<SessionEntry>,
 }
 
 pub fn load_from(conn: &Connection) -> LoadedState {
     let tasks = load_tasks(conn).unwrap_or_default();
     let mode = get_state(conn, "mode")
-        .and_then(|s| match s.as_str() {
-            "ShortBreak" => Some(Mode::ShortBreak),
-            "LongBreak" => Some(Mode::LongBreak),
-            _ => Some(Mode::Pomodoro),
-        })
+        .map(|s| mode_from_str(&s))
         .unwrap_or_default();
     let pomodoros_total: u32 = get_state(conn, "pomodoros_total")
         .and_then(|s| s.parse().ok())
         .unwrap_or(0);
-    let current_view = get_state(conn, "current_view")
-        .and_then(|s| match s.as_str() {
-            "Timer" => Some(View::Timer),
-            "Statistics" => Some(View::Statistics),
-            _ => Some(View::TaskList),
-        })
-        .unwrap_or_default();
     let active_task_index = get_state(conn, "active_task_index")
         .and_then(|s| s.parse::<usize>().ok())
         .filter(|&i| i < tasks.len());
+    let active_task_indices = get_state(conn, "active_task_indices")
+        .and_then(|s| serde_json::from_str::<HashSet<usize>>(&s).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|&i| i < tasks.len())
+        .collect();
     let time_remaining_secs = get_state(conn, "time_remaining_secs")
         .and_then(|s| s.parse::<u64>().ok());
-    LoadedState { tasks, mode, pomodoros_total, current_view, active_task_index, time_remaining_secs }
+    let projects = get_state(conn, "projects")
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    let _ = migrate_session_log(conn, &tasks);
+    let session_log = load_sessions(conn).unwrap_or_default();
+    LoadedState {
+        tasks,
+        mode,
+        pomodoros_total,
+        active_task_index,
+        active_task_indices,
+        time_remaining_secs,
+        projects,
+        session_log,
+    }
 }
 
 fn load_tasks(conn: &Connection) -> Result<Vec<Task>> {
     let mut stmt = conn.prepare(
-        "SELECT name, notes, project, completed, pomodoros, time_spent_secs, creation_date, completion_date
+        "SELECT name, notes, project, completed, pomodoros, time_spent_secs, creation_date, completion_date,
+                priority, subtasks, deferred_until, color, recurrence, links, estimated_pomodoros, is_separator,
+                time_budget_secs, external_id, start_date, labels, id
          FROM tasks ORDER BY sort_order ASC",
     )?;
     let tasks = stmt
         .query_map([], |row| {
             let creation_str: String = row.get(6)?;
             let completion_str: Option<String> = row.get(7)?;
+            let priority_str: Option<String> = row.get(8)?;
+            let subtasks_json: Option<String> = row.get(9)?;
+            let deferred_str: Option<String> = row.get(10)?;
+            let color_str: Option<String> = row.get(11)?;
+            let recurrence_json: Option<String> = row.get(12)?;
+            let links_json: Option<String> = row.get(13)?;
+            let id = row.get::<_, i64>(20)? as u64;
+            reserve_task_id(id);
             Ok(Task {
+                id,
                 name: row.get(0)?,
                 notes: row.get(1)?,
                 project: row.get(2)?,
+                priority: priority_str.as_deref().and_then(priority_from_str),
+                subtasks: subtasks_json
+                    .and_then(|s| serde_json::from_str(&s).ok())
+                    .unwrap_or_default(),
+                links: links_json
+                    .and_then(|s| serde_json::from_str(&s).ok())
+                    .unwrap_or_default(),
+                color: color_str.as_deref().and_then(color_from_str),
                 completed: row.get::<_, i64>(3)? != 0,
                 pomodoros: row.get::<_, i64>(4)? as u32,
                 time_spent: Duration::from_secs(row.get::<_, i64>(5)? as u64),
@@ -99,6 +228,17 @@ fn load_tasks(conn: &Connection) -> Result<Vec<Task>> {
                     .parse::<DateTime<Utc>>()
                     .unwrap_or_else(|_| Utc::now()),
                 completion_date: completion_str.and_then(|s| s.parse::<DateTime<Utc>>().ok()),
+                start_date: row.get::<_, Option<String>>(18)?.and_then(|s| s.parse::<DateTime<Utc>>().ok()),
+                deferred_until: deferred_str.and_then(|s| s.parse::<DateTime<Utc>>().ok()),
+                recurrence: recurrence_json.and_then(|s| serde_json::from_str(&s).ok()),
+                estimated_pomodoros: row.get::<_, Option<i64>>(14)?.map(|n| n as u32),
+                is_separator: row.get::<_, Option<i64>>(15)?.is_some_and(|v| v != 0),
+                time_budget: row.get::<_, Option<i64>>(16)?.map(|s| Duration::from_secs(s as u64)),
+                labels: row.get::<_, Option<String>>(19)?
+                    .and_then(|s| serde_json::from_str(&s).ok())
+                    .unwrap_or_default(),
+                external_id: row.get(17)?,
+                session_start: None,
             })
         })?
         .filter_map(|r| r.ok())
@@ -110,6 +250,7 @@ pub fn save_to(conn: &mut Connection, app: &App) -> Result<()> {
     let tx = conn.transaction()?;
     save_tasks(&tx, &app.tasks)?;
     save_app_state(&tx, app)?;
+    save_sessions(&tx, &app.session_log)?;
     tx.commit()
 }
 
@@ -117,8 +258,10 @@ fn save_tasks(conn: &Connection, tasks: &[Task]) -> Result<()> {
     conn.execute("DELETE FROM tasks", [])?;
     for (i, task) in tasks.iter().enumerate() {
         conn.execute(
-            "INSERT INTO tasks (sort_order, name, notes, project, completed, pomodoros, time_spent_secs, creation_date, completion_date)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            "INSERT INTO tasks (sort_order, name, notes, project, completed, pomodoros, time_spent_secs, creation_date, completion_date,
+                                priority, subtasks, deferred_until, color, recurrence, links, estimated_pomodoros, is_separator,
+                                time_budget_secs, external_id, start_date, labels, id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22)",
             params![
                 i as i64,
                 task.name,
@@ -129,6 +272,19 @@ fn save_tasks(conn: &Connection, tasks: &[Task]) -> Result<()> {
                 task.time_spent.as_secs() as i64,
                 task.creation_date.to_rfc3339(),
                 task.completion_date.map(|d| d.to_rfc3339()),
+                task.priority.map(priority_to_str),
+                serde_json::to_string(&task.subtasks).ok(),
+                task.deferred_until.map(|d| d.to_rfc3339()),
+                task.color.map(color_to_str),
+                task.recurrence.and_then(|r| serde_json::to_string(&r).ok()),
+                serde_json::to_string(&task.links).ok(),
+                task.estimated_pomodoros.map(|n| n as i64),
+                task.is_separator as i64,
+                task.time_budget.map(|d| d.as_secs() as i64),
+                task.external_id,
+                task.start_date.map(|d| d.to_rfc3339()),
+                serde_json::to_string(&task.labels).ok(),
+                task.id as i64,
             ],
         )?;
     }
@@ -136,30 +292,16 @@ fn save_tasks(conn: &Connection, tasks: &[Task]) -> Result<()> {
 }
 
 fn save_app_state(conn: &Connection, app: &App) -> Result<()> {
-    let mode_str = match app.mode {
-        Mode::Pomodoro => "Pomodoro",
-        Mode::ShortBreak => "ShortBreak",
-        Mode::LongBreak => "LongBreak",
-    };
     conn.execute(
         "INSERT OR REPLACE INTO app_state (key, value) VALUES ('mode', ?1)",
-        params![mode_str],
+        params![mode_to_str(app.mode)],
     )?;
     conn.execute(
         "INSERT OR REPLACE INTO app_state (key, value) VALUES ('pomodoros_total', ?1)",
         params![app.pomodoros_completed_total as i64],
     )?;
-    let view_str = match app.current_view {
-        View::Timer => "Timer",
-        View::TaskList => "TaskList",
-        View::Statistics => "Statistics",
-        View::Settings => "Settings",
-        View::TaskDetails => "TaskDetails",
-    };
-    conn.execute(
-        "INSERT OR REPLACE INTO app_state (key, value) VALUES ('current_view', ?1)",
-        params![view_str],
-    )?;
+    // The launch view now comes from `Settings::start_view`; drop the old key
+    conn.execute("DELETE FROM app_state WHERE key = 'current_view'", [])?;
     conn.execute(
         "INSERT OR REPLACE INTO app_state (key, value) VALUES ('time_remaining_secs', ?1)",
         params![app.time_remaining.as_secs() as i64],
@@ -175,5 +317,86 @@ fn save_app_state(conn: &Connection, app: &App) -> Result<()> {
             conn.execute("DELETE FROM app_state WHERE key = 'active_task_index'", [])?;
         }
     }
+    let mut parallel: Vec<usize> = app.active_task_indices.iter().copied().collect();
+    parallel.sort_unstable();
+    conn.execute(
+        "INSERT OR REPLACE INTO app_state (key, value) VALUES ('active_task_indices', ?1)",
+        params![serde_json::to_string(&parallel).unwrap_or_default()],
+    )?;
+    conn.execute(
+        "INSERT OR REPLACE INTO app_state (key, value) VALUES ('projects', ?1)",
+        params![serde_json::to_string(&app.projects).unwrap_or_default()],
+    )?;
+    Ok(())
+}
+
+fn load_sessions(conn: &Connection) -> Result<Vec<SessionEntry>> {
+    let mut stmt = conn.prepare("SELECT mode, started, finished, task_ids FROM sessions ORDER BY id ASC")?;
+    let sessions = stmt
+        .query_map([], |row| {
+            let started: String = row.get(1)?;
+            let finished: String = row.get(2)?;
+            let task_ids: String = row.get(3)?;
+            Ok((row.get::<_, String>(0)?, started, finished, task_ids))
+        })?
+        .filter_map(|r| r.ok())
+        .filter_map(|(mode, started, finished, task_ids)| {
+            Some(SessionEntry {
+                mode: mode_from_str(&mode),
+                started: started.parse().ok()?,
+                finished: finished.parse().ok()?,
+                task_ids: serde_json::from_str(&task_ids).unwrap_or_default(),
+            })
+        })
+        .collect();
+    Ok(sessions)
+}
+
+// The log only grows, and every session in the table was loaded into it, so
+// only the ones past the row count are new.
+fn save_sessions(conn: &Connection, sessions: &[SessionEntry]) -> Result<()> {
+    let saved: i64 = conn.query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))?;
+    for entry in sessions.iter().skip(saved as usize) {
+        insert_session(conn, entry)?;
+    }
+    Ok(())
+}
+
+fn insert_session(conn: &Connection, entry: &SessionEntry) -> Result<()> {
+    conn.execute(
+        "INSERT INTO sessions (mode, started, finished, task_ids) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            mode_to_str(entry.mode),
+            entry.started.to_rfc3339(),
+            entry.finished.to_rfc3339(),
+            serde_json::to_string(&entry.task_ids).unwrap_or_default(),
+        ],
+    )?;
+    Ok(())
+}
+
+/// A session as the `session_log` app_state key used to hold them, by task name.
+#[derive(Deserialize)]
+struct LegacySessionEntry {
+    mode: Mode,
+    started: DateTime<Utc>,
+    finished: DateTime<Utc>,
+    #[serde(default)]
+    tasks: Vec<String>,
+}
+
+// Moves the old JSON session log into the sessions table, matching task
+// names to the loaded tasks; names no longer in the list are dropped.
+fn migrate_session_log(conn: &Connection, tasks: &[Task]) -> Result<()> {
+    let Some(json) = get_state(conn, "session_log") else { return Ok(()) };
+    let legacy: Vec<LegacySessionEntry> = serde_json::from_str(&json).unwrap_or_default();
+    for entry in legacy {
+        let task_ids = entry.tasks.iter()
+            .filter_map(|name| tasks.iter().find(|t| &t.name == name))
+            .map(|t| t.id)
+            .collect();
+        insert_session(conn, &SessionEntry { mode: entry.mode, started: entry.started, finished: entry.finished, task_ids })?;
+    }
+    conn.execute("DELETE FROM app_state WHERE key = 'session_log'", [])?;
     Ok(())
 }
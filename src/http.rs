@@ -0,0 +1,258 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::app::{App, Mode, TimerState};
+
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+/// Connections served at once; further ones are closed unanswered.
+const MAX_CONNECTIONS: usize = 8;
+
+/// Timer state served at `GET /status`.
+#[derive(Serialize, Clone, Default)]
+pub struct Status {
+    mode: Mode,
+    state: TimerState,
+    remaining_secs: u64,
+    task: Option<String>,
+    /// Pomodoros completed across all tasks.
+    pomodoros: u32,
+}
+
+impl Status {
+    fn from_app(app: &App) -> Self {
+        Self {
+            mode: app.mode,
+            state: app.state,
+            remaining_secs: app.time_remaining.as_secs(),
+            task: app.active_task_index
+                .and_then(|i| app.tasks.get(i))
+                .map(|t| t.name.clone()),
+            pomodoros: app.pomodoros_completed_total,
+        }
+    }
+}
+
+/// Actions requested over HTTP; the main loop applies them to the `App`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RemoteCommand {
+    Toggle,
+    Reset,
+    Next,
+}
+
+/// Handle to the background server started by `--http-port`. The server only
+/// reads the last published `Status`, so it never touches the `App` directly.
+pub struct Server {
+    status: Arc<Mutex<Status>>,
+    commands: Receiver<RemoteCommand>,
+}
+
+impl Server {
+    /// Binds to localhost only; the API has no authentication, so requests
+    /// with a non-local `Origin` or `Host` (DNS rebinding) are refused. Each
+    /// connection gets its own thread, so a stalled client can't block the
+    /// others, up to `MAX_CONNECTIONS` at a time.
+    pub fn start(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let status = Arc::new(Mutex::new(Status::default()));
+        let (tx, commands) = mpsc::channel();
+        let shared = Arc::clone(&status);
+        let open = Arc::new(AtomicUsize::new(0));
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if open.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    open.fetch_sub(1, Ordering::SeqCst);
+                    tracing::debug!("http connection dropped, too many open");
+                    continue;
+                }
+                let status = Arc::clone(&shared);
+                let tx = tx.clone();
+                let open = Arc::clone(&open);
+                thread::spawn(move || {
+                    if let Err(e) = handle_connection(stream, &status, &tx) {
+                        tracing::debug!(error = %e, "http request failed");
+                    }
+                    open.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+        tracing::debug!(port, "http server listening");
+        Ok(Self { status, commands })
+    }
+
+    pub fn publish(&self, app: &App) {
+        if let Ok(mut status) = self.status.lock() {
+            *status = Status::from_app(app);
+        }
+    }
+
+    /// Commands received since the last call, oldest first.
+    pub fn pending_commands(&self) -> impl Iterator<Item = RemoteCommand> + '_ {
+        self.commands.try_iter()
+    }
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    status: &Mutex<Status>,
+    tx: &Sender<RemoteCommand>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers, keeping Origin and Host; none of the endpoints take a body
+    let mut origin = None;
+    let mut host = None;
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("origin") {
+                origin = Some(value.trim().to_string());
+            } else if name.trim().eq_ignore_ascii_case("host") {
+                host = Some(value.trim().to_string());
+            }
+        }
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("");
+    let (code, body) = if !host.as_deref().is_some_and(is_local_host) {
+        (403, r#"{"error":"only localhost may be used as Host"}"#.to_string())
+    } else if origin.as_deref().is_some_and(|origin| !is_local_origin(origin)) {
+        (403, r#"{"error":"cross-origin requests are not allowed"}"#.to_string())
+    } else {
+        route(method, path, status, tx)
+    };
+    let reason = match code {
+        200 => "OK",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        code,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Whether a browser `Origin` header names a page served from this machine.
+fn is_local_origin(origin: &str) -> bool {
+    origin.split_once("://").is_some_and(|(_, host)| is_local_host(host))
+}
+
+/// Whether a `Host` header (or the host part of an origin), with or without
+/// a port, names this machine.
+fn is_local_host(host: &str) -> bool {
+    let host = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(""),
+        None => host.split(':').next().unwrap_or(""),
+    };
+    matches!(host, "localhost" | "127.0.0.1" | "::1")
+}
+
+fn route(method: &str, path: &str, status: &Mutex<Status>, tx: &Sender<RemoteCommand>) -> (u16, String) {
+    let command = match path {
+        "/status" => {
+            if method != "GET" {
+                return (405, r#"{"error":"use GET"}"#.to_string());
+            }
+            let status = status.lock().map(|s| s.clone()).unwrap_or_default();
+            return match serde_json::to_string(&status) {
+                Ok(json) => (200, json),
+                Err(_) => (500, r#"{"error":"could not encode status"}"#.to_string()),
+            };
+        }
+        "/toggle" => RemoteCommand::Toggle,
+        "/reset" => RemoteCommand::Reset,
+        "/next" => RemoteCommand::Next,
+        _ => return (404, r#"{"error":"not found"}"#.to_string()),
+    };
+    if method != "POST" {
+        return (405, r#"{"error":"use POST"}"#.to_string());
+    }
+    match tx.send(command) {
+        Ok(()) => (200, r#"{"ok":true}"#.to_string()),
+        Err(_) => (500, r#"{"error":"timer is shutting down"}"#.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::Task;
+
+    #[test]
+    fn status_reports_timer_and_active_task() {
+        let mut app = App {
+            tasks: vec![Task::new("Fix bug".to_string(), None)],
+            active_task_index: Some(0),
+            pomodoros_completed_total: 3,
+            state: TimerState::Running,
+            time_remaining: Duration::from_secs(1200),
+            ..App::default()
+        };
+        app.mode = Mode::Pomodoro;
+        let status = Mutex::new(Status::from_app(&app));
+        let (tx, _rx) = mpsc::channel();
+        let (code, body) = route("GET", "/status", &status, &tx);
+        assert_eq!(code, 200);
+        assert_eq!(
+            body,
+            r#"{"mode":"Pomodoro","state":"Running","remaining_secs":1200,"task":"Fix bug","pomodoros":3}"#
+        );
+    }
+
+    #[test]
+    fn post_endpoints_queue_commands() {
+        let status = Mutex::new(Status::default());
+        let (tx, rx) = mpsc::channel();
+        assert_eq!(route("POST", "/toggle", &status, &tx).0, 200);
+        assert_eq!(route("POST", "/next", &status, &tx).0, 200);
+        assert_eq!(route("POST", "/reset", &status, &tx).0, 200);
+        let received: Vec<_> = rx.try_iter().collect();
+        assert_eq!(received, vec![RemoteCommand::Toggle, RemoteCommand::Next, RemoteCommand::Reset]);
+    }
+
+    #[test]
+    fn wrong_method_and_unknown_path_are_rejected() {
+        let status = Mutex::new(Status::default());
+        let (tx, rx) = mpsc::channel();
+        assert_eq!(route("GET", "/toggle", &status, &tx).0, 405);
+        assert_eq!(route("POST", "/status", &status, &tx).0, 405);
+        assert_eq!(route("GET", "/nope", &status, &tx).0, 404);
+        assert!(rx.try_iter().next().is_none());
+    }
+
+    #[test]
+    fn only_local_origins_are_allowed() {
+        assert!(is_local_origin("http://localhost:3000"));
+        assert!(is_local_origin("http://127.0.0.1"));
+        assert!(is_local_origin("http://[::1]:8080"));
+        assert!(!is_local_origin("https://example.com"));
+        assert!(!is_local_origin("http://localhost.example.com"));
+        assert!(!is_local_origin("null"));
+        assert!(is_local_host("localhost:8080"));
+        assert!(is_local_host("127.0.0.1"));
+        assert!(is_local_host("[::1]:8080"));
+        assert!(!is_local_host("evil.example.com:8080"));
+        assert!(!is_local_host(""));
+    }
+}
@@ -0,0 +1,79 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tracing_subscriber::EnvFilter;
+
+use crate::app::get_data_dir;
+
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+// pomodorust.log.1 … pomodorust.log.3
+const KEPT_ROTATIONS: u32 = 3;
+
+/// Append-only log file that rolls over to `<name>.1` once it passes
+/// `MAX_LOG_BYTES`, shifting older rotations up and dropping the oldest.
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+        Ok(Self { path, file, written })
+    }
+
+    fn rotated(path: &Path, n: u32) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        for n in (1..KEPT_ROTATIONS).rev() {
+            let from = Self::rotated(&self.path, n);
+            if from.exists() {
+                fs::rename(&from, Self::rotated(&self.path, n + 1))?;
+            }
+        }
+        fs::rename(&self.path, Self::rotated(&self.path, 1))?;
+        *self = Self::open(self.path.clone())?;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written + buf.len() as u64 > MAX_LOG_BYTES && self.written > 0 {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Starts file logging to `pomodorust.log` in the data directory when
+/// `RUST_LOG` is set (e.g. `RUST_LOG=pomodorust=debug`). Never writes to the
+/// terminal, which belongs to the TUI.
+pub fn init() {
+    let Ok(filter) = EnvFilter::try_from_default_env() else { return };
+    let Some(dir) = get_data_dir() else { return };
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    let Ok(file) = RotatingFile::open(dir.join("pomodorust.log")) else { return };
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .try_init();
+}
@@ -1,12 +1,18 @@
 use std::{
     io::{self, stdout, Stdout},
     panic,
+    path::PathBuf,
+    sync::mpsc::{self, Sender},
+    thread,
     time::{Duration, Instant},
 };
 
-use clap::Parser;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -15,13 +21,26 @@ use ratatui::prelude::*;
 use rodio::{source::SineWave, stream::DeviceSinkBuilder, Player, Source};
 
 mod app;
+mod cli_output;
 mod db;
+mod http;
+mod logging;
+mod persistence;
+mod screen_lock;
 mod settings;
+mod stats;
 mod ui;
-use app::{App, InputMode, Mode, TimerState, UiState, View};
-use settings::{Settings, Theme};
+mod util;
+mod version_check;
+use app::{command::Command, App, AppMessage, InputMode, Mode, TimerState, UiState, View};
+use cli_output::OutputFormat;
+use persistence::StateFormat;
+use settings::{Settings, SortCriterion, Theme};
 use ratatui_textarea::Input;
-use ui::{draw_notes_modal, draw_settings, draw_statistics, draw_task_details, draw_task_list, draw_timer};
+use ui::{
+    draw_command_palette, draw_context_menu, draw_global_search, draw_help_overlay, draw_mini_timer, draw_notes_modal, draw_label_manager, draw_preset_picker, draw_project_manager, draw_quick_start_modal, draw_settings, draw_sort_menu, draw_statistics,
+    draw_task_details, draw_task_list, draw_timer, draw_toast,
+};
 
 /// An andvanced Pomodoro timer for your terminal.
 #[derive(Parser, Debug)]
@@ -38,6 +57,101 @@ struct Cli {
     /// Long break duration in minutes.
     #[arg(short = 'l', long)]
     long_break_duration: Option<u64>,
+
+    /// View to open on launch, overriding the Start View setting.
+    #[arg(long, value_enum)]
+    start_view: Option<StartView>,
+
+    /// Write a Markdown report of your tasks and sessions to this file and exit.
+    #[arg(long, value_name = "PATH")]
+    export_md: Option<PathBuf>,
+
+    /// Serve timer status and controls as JSON on localhost at this port.
+    #[arg(long, value_name = "PORT")]
+    http_port: Option<u16>,
+
+    /// Add tasks from a JSON array file, skipping names already in the list.
+    #[arg(long, value_name = "PATH")]
+    merge_tasks: Option<PathBuf>,
+
+    /// Format of the state file opened by Ctrl+E: state.json or state.toml.
+    #[arg(long, value_enum, default_value_t = StateFormat::Json)]
+    state_format: StateFormat,
+
+    /// Print active tasks to stdout and exit.
+    #[arg(long, conflicts_with = "list_completed")]
+    list_tasks: bool,
+
+    /// Print completed tasks to stdout and exit.
+    #[arg(long)]
+    list_completed: bool,
+
+    /// Output format for --list-tasks and --list-completed.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Override a setting by its Settings view label, e.g. --set "Countdown Beeps=On". Repeatable.
+    #[arg(long = "set", value_name = "LABEL=VALUE")]
+    set: Vec<String>,
+
+    /// Print today's work log (sessions, tasks, notes) to stdout and exit.
+    #[arg(long)]
+    print: bool,
+
+    /// Check GitHub for a newer release and exit.
+    #[arg(long)]
+    version_check: bool,
+
+    /// Show notifications through osascript (macOS only; otherwise it is only the fallback).
+    #[arg(long)]
+    native_notif: bool,
+
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+#[derive(Subcommand, Debug)]
+enum CliCommand {
+    /// Print a shell completion script to stdout, e.g. `pomodorust completions zsh`.
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum StartView {
+    Timer,
+    Tasks,
+    Stats,
+}
+
+impl From<StartView> for View {
+    fn from(v: StartView) -> Self {
+        match v {
+            StartView::Timer => View::Timer,
+            StartView::Tasks => View::TaskList,
+            StartView::Stats => View::Statistics,
+        }
+    }
+}
+
+/// Applies `--set LABEL=VALUE` entries on top of `settings`; labels match
+/// the Settings view rows case-insensitively. Everything else is kept.
+fn apply_setting_overrides(settings: Settings, entries: &[String]) -> io::Result<Settings> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+    let rows = Vec::<(String, String)>::from(&settings);
+    let mut pairs = Vec::new();
+    for entry in entries {
+        let (label, value) = entry
+            .split_once('=')
+            .ok_or_else(|| invalid(format!("--set expects LABEL=VALUE, got \"{}\"", entry)))?;
+        let label = rows.iter()
+            .find(|(l, _)| l.eq_ignore_ascii_case(label.trim()))
+            .map_or_else(|| label.trim().to_string(), |(l, _)| l.clone());
+        pairs.push((label, value.trim().to_string()));
+    }
+    settings.with_pairs(&pairs).map_err(|e| invalid(format!("--set: {}", e)))
 }
 
 /// Main function to run the application.
@@ -46,18 +160,36 @@ fn main() -> io::Result<()> {
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
         let mut stdout = stdout();
-        execute!(stdout, LeaveAlternateScreen).unwrap();
+        execute!(stdout, LeaveAlternateScreen, DisableMouseCapture).unwrap();
         disable_raw_mode().unwrap();
         original_hook(panic_info);
     }));
     
     // Parse command-line arguments.
     let cli = Cli::parse();
+    logging::init();
 
-    let mut terminal = setup_terminal()?;
-    
-    // Load settings from config file.
-    let mut settings = Settings::load();
+    if let Some(CliCommand::Completions { shell }) = cli.command {
+        clap_complete::generate(shell, &mut Cli::command(), env!("CARGO_PKG_NAME"), &mut stdout());
+        return Ok(());
+    }
+
+    if cli.version_check {
+        version_check::run();
+        return Ok(());
+    }
+
+    // Load settings from config file. Warn before entering the alternate screen
+    // so a typo in config.toml doesn't silently reset everything to defaults.
+    let mut settings = match Settings::load() {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("warning: {}", e);
+            let backup = e.back_up()?;
+            eprintln!("warning: using default settings; the old file was copied to {}", backup.display());
+            Settings::default()
+        }
+    };
 
     // Override settings from CLI arguments if provided.
     if let Some(duration) = cli.pomodoro_duration {
@@ -69,11 +201,52 @@ fn main() -> io::Result<()> {
     if let Some(duration) = cli.long_break_duration {
         settings.long_break_duration = Duration::from_secs(duration * 60);
     }
+    if !cli.set.is_empty() {
+        settings = apply_setting_overrides(settings, &cli.set)?;
+    }
     
     // Load app state with the final settings.
     let mut app = App::load_with_settings(settings);
+    app.process_recurring_tasks();
+    // Applied to the app rather than the settings so it isn't written back to config.toml
+    if let Some(view) = cli.start_view {
+        app.current_view = view.into();
+    }
+
+    if cli.list_tasks || cli.list_completed {
+        let tasks: Vec<&app::Task> = app.tasks.iter().filter(|t| !t.is_separator && t.completed == cli.list_completed).collect();
+        cli_output::print_task_list(&tasks, cli.format)?;
+        return Ok(());
+    }
+
+    if cli.print {
+        stats::print_daily_log(&app, &mut stdout())?;
+        return Ok(());
+    }
+
+    if let Some(path) = cli.export_md {
+        std::fs::write(&path, stats::export_markdown(&app))?;
+        println!("Report written to {}", path.display());
+        return Ok(());
+    }
 
-    run_app(&mut terminal, &mut app)?;
+    let mut startup_message = None;
+    if let Some(path) = cli.merge_tasks {
+        let json = std::fs::read_to_string(&path)?;
+        let summary = app.import_from_json(&json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))?;
+        let mut message = format!("Imported {} task{}", summary.merged, if summary.merged == 1 { "" } else { "s" });
+        if summary.skipped > 0 || summary.failed > 0 {
+            message.push_str(&format!(" ({} skipped, {} failed)", summary.skipped, summary.failed));
+        }
+        startup_message = Some(message);
+    }
+    app.register_task_projects();
+
+    let remote = cli.http_port.map(http::Server::start).transpose()?;
+
+    let mut terminal = setup_terminal()?;
+    run_app(&mut terminal, &mut app, remote.as_ref(), cli.state_format, startup_message, cli.native_notif)?;
     restore_terminal(&mut terminal)?;
     Ok(())
 }
@@ -82,7 +255,7 @@ fn main() -> io::Result<()> {
 fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     Terminal::new(backend)
 }
@@ -90,19 +263,67 @@ fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
 /// Restores the terminal to its original state.
 fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
     terminal.show_cursor()
 }
 
+/// Suspends the TUI while the user edits the state file, then loads the edited
+/// state back in. The timer stays paused; a failed import keeps the old state.
+fn edit_state_in_editor(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    app: &mut App,
+    ui: &mut UiState,
+    format: StateFormat,
+) -> io::Result<()> {
+    let Some(editor) = util::editor_from_env() else {
+        ui.show_toast("Set $EDITOR to edit the state file");
+        return Ok(());
+    };
+    app.pause_timer();
+    let path = match app.export_state(format) {
+        Ok(path) => path,
+        Err(e) => {
+            ui.show_toast(format!("Could not write state file: {}", e));
+            return Ok(());
+        }
+    };
+
+    restore_terminal(terminal)?;
+    let edited = util::run_editor(&editor, &path);
+    *terminal = setup_terminal()?;
+
+    match edited.map_err(|e| e.to_string()).and_then(|_| app.import_state(&path)) {
+        Ok(()) => ui.show_toast("Reloaded state file"),
+        Err(e) => ui.show_toast(format!("State not reloaded: {}", e)),
+    }
+    Ok(())
+}
+
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     app: &mut App,
+    remote: Option<&http::Server>,
+    state_format: StateFormat,
+    startup_message: Option<String>,
+    native_notifications: bool,
 ) -> io::Result<()> {
     let mut last_tick = Instant::now();
     let tick_rate = Duration::from_millis(250);
     let mut ui_state = UiState::default();
-    let mut ticks_since_save: u32 = 0;
-    const AUTOSAVE_TICKS: u32 = 120; // ~30 seconds
+    let (message_tx, messages) = mpsc::channel();
+    // Dropped on every way out of here, which stops the watcher process
+    let _screen_lock = screen_lock::watch(message_tx.clone());
+    // Only a lock that stopped a running timer gets a greeting on unlock
+    let mut paused_by_lock = false;
+    ui_state.app_messages = Some(message_tx);
+    ui_state.native_notifications = native_notifications;
+    if let Some(message) = startup_message {
+        ui_state.show_toast(message);
+    }
+    let mut last_autosave = Instant::now();
+    // What was last saved, so autosave can skip when events changed nothing
+    let mut saved_digest = app.state_digest();
+    let mut last_beep_second: Option<u64> = None;
 
     let audio_system = DeviceSinkBuilder::open_default_sink()
         .ok()
@@ -112,48 +333,119 @@ fn run_app(
         });
 
     loop {
-        terminal.draw(|f| ui(f, app, &ui_state))?;
+        if ui_state.force_redraw {
+            terminal.clear()?;
+            ui_state.force_redraw = false;
+        }
+        terminal.draw(|f| ui(f, app, &mut ui_state))?;
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                handle_key_event(key, app, &mut ui_state, audio_system.as_deref().map(|b| &b.1));
+            match event::read()? {
+                Event::Key(key) => {
+                    handle_key_event(key, app, &mut ui_state, audio_system.as_deref().map(|b| &b.1));
+                }
+                Event::Mouse(mouse) => handle_mouse_event(mouse, app, &mut ui_state),
+                _ => {}
             }
         }
 
         if last_tick.elapsed() >= tick_rate {
-            if let TimerState::Running = app.state {
+            if app.session_summary_visible {
+                if app.session_summary_expired() {
+                    force_complete_session(app, &ui_state, audio_system.as_deref().map(|b| &b.1));
+                }
+            } else if let TimerState::Running = app.state {
                 let elapsed = last_tick.elapsed();
                 if let Some(remaining) = app.time_remaining.checked_sub(elapsed) {
                     app.time_remaining = remaining;
-                    if let Some(index) = app.active_task_index {
-                        if let Some(task) = app.tasks.get_mut(index) {
-                            task.time_spent += elapsed;
+                    if let Some(index) = app.record_focus_time(elapsed) {
+                        if let Some(p) = audio_system.as_deref().map(|b| &b.1) {
+                            budget_warning(p);
+                        }
+                        ui_state.show_toast(format!("Time budget exceeded for: {}", app.tasks[index].name));
+                    }
+                    let seconds = app.time_remaining.as_secs();
+                    if app.settings.countdown_beeps
+                        && app.mode == Mode::Pomodoro
+                        && (1..=COUNTDOWN_BEEP_SECONDS).contains(&seconds)
+                        && last_beep_second != Some(seconds)
+                    {
+                        if let Some(p) = audio_system.as_deref().map(|b| &b.1) {
+                            countdown_beep(p, seconds);
                         }
+                        last_beep_second = Some(seconds);
+                    } else if seconds > COUNTDOWN_BEEP_SECONDS {
+                        last_beep_second = None;
                     }
+                } else if app.current_view == View::Timer {
+                    app.show_session_summary();
                 } else {
-                    app.time_remaining = Duration::from_secs(0);
-                    let finished_mode = app.next_mode();
-                    if let Some(audio) = &audio_system {
-                        play_sound(&audio.1, finished_mode);
+                    force_complete_session(app, &ui_state, audio_system.as_deref().map(|b| &b.1));
+                }
+            }
+            app.clear_expired_mode_transition();
+            if ui_state.screensaver_active(app) {
+                let size = terminal.size()?;
+                ui_state.advance_screensaver(size.width, size.height);
+            }
+            last_tick = Instant::now();
+            if let Some(interval) = app.settings.autosave_interval {
+                if last_autosave.elapsed() >= interval {
+                    let digest = app.state_digest();
+                    if app.dirty || digest != saved_digest {
+                        app.save();
+                        saved_digest = digest;
                     }
-                    if app.settings.desktop_notifications {
-                        show_desktop_notification(finished_mode, app.mode);
+                    last_autosave = Instant::now();
+                }
+            }
+        }
+
+        if let Some(server) = remote {
+            let player = audio_system.as_deref().map(|b| &b.1);
+            for command in server.pending_commands() {
+                match command {
+                    http::RemoteCommand::Toggle => app.toggle_timer(),
+                    http::RemoteCommand::Reset => app.reset_timer(),
+                    http::RemoteCommand::Next => skip_segment(app, &ui_state, player),
+                }
+                app.dirty = true;
+            }
+            server.publish(app);
+        }
+
+        for message in messages.try_iter() {
+            match message {
+                AppMessage::StartTimer => {
+                    if !app.session_summary_visible {
+                        app.start_timer();
+                    }
+                }
+                AppMessage::PauseAndSave => {
+                    paused_by_lock |= app.state == TimerState::Running;
+                    app.pause_timer();
+                    app.save();
+                }
+                AppMessage::NotifyResumed => {
+                    if std::mem::take(&mut paused_by_lock) {
+                        ui_state.show_toast("Welcome back!");
                     }
                 }
             }
+        }
+
+        if ui_state.edit_state_requested {
+            ui_state.edit_state_requested = false;
+            edit_state_in_editor(terminal, app, &mut ui_state, state_format)?;
             last_tick = Instant::now();
-            ticks_since_save += 1;
-            if ticks_since_save >= AUTOSAVE_TICKS {
-                app.save();
-                ticks_since_save = 0;
-            }
         }
 
         if app.should_quit {
+            tracing::debug!(state = %serde_json::to_string(&app.snapshot()).unwrap_or_default(), "quitting");
             app.save();
             return Ok(());
         }
@@ -164,9 +456,52 @@ fn handle_key_event(key: KeyEvent, app: &mut App, ui: &mut UiState, player: Opti
     if key.kind != crossterm::event::KeyEventKind::Press {
         return;
     }
+    tracing::debug!(code = ?key.code, modifiers = ?key.modifiers, view = ?app.current_view, "key");
 
+    // Ctrl+C quits, except where it cancels input or copies the task list
     if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
-        app.should_quit = true;
+        let handled_locally = match ui.input_mode {
+            InputMode::Editing => true,
+            InputMode::Normal => app.current_view == View::TaskList,
+            _ => false,
+        };
+        if !handled_locally {
+            app.should_quit = true;
+            return;
+        }
+    }
+
+    // Repaint everything, e.g. after another program scribbled over the screen
+    if key.code == KeyCode::Char('l') && key.modifiers == KeyModifiers::CONTROL {
+        ui.force_redraw = true;
+        return;
+    }
+
+    // Any key wakes the screensaver without acting on the key itself
+    let was_idle = ui.screensaver_active(app);
+    ui.last_interaction = Instant::now();
+    if was_idle {
+        return;
+    }
+
+    // Any key dismisses the session summary and starts the next segment
+    if app.session_summary_visible {
+        force_complete_session(app, ui, player);
+        return;
+    }
+
+    // The help overlay swallows every key: arrows scroll, anything else closes it
+    if ui.show_help_overlay {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => ui.help_scroll = ui.help_scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => ui.help_scroll = ui.help_scroll.saturating_add(1),
+            _ => ui.show_help_overlay = false,
+        }
+        return;
+    }
+
+    if ui.zen_mode {
+        ui.zen_mode = false;
         return;
     }
 
@@ -174,10 +509,98 @@ fn handle_key_event(key: KeyEvent, app: &mut App, ui: &mut UiState, player: Opti
         InputMode::Editing => handle_editing_input(key, app, ui),
         InputMode::Filtering => handle_filtering_input(key, ui),
         InputMode::EditingNotes => handle_editing_notes_input(key, app, ui),
+        InputMode::EditingSubtask => handle_subtask_input(key, app, ui),
+        InputMode::EditingLink => handle_link_input(key, app, ui),
+        InputMode::EditingProject => handle_project_input(key, app, ui),
+        InputMode::EditingLabel => handle_label_input(key, app, ui),
+        InputMode::EditingPreset => handle_preset_input(key, app, ui),
+        InputMode::EditingStatsRange => handle_stats_range_input(key, app, ui),
+        InputMode::EditingBudget => handle_budget_input(key, app, ui),
+        InputMode::GoTo => handle_go_to_input(key, app, ui),
+        InputMode::QuickStart => handle_quick_start_input(key, app, ui),
         InputMode::Normal => {
-            if key.code == KeyCode::Char('o') && key.modifiers == KeyModifiers::NONE {
-                ui.previous_view = app.current_view;
-                app.current_view = View::Settings;
+            // The palette owns the keyboard while open so typed letters reach the query
+            if let View::CommandPalette { selected } = app.current_view {
+                handle_command_palette_input(key, selected, app, ui, player);
+                return;
+            }
+            if let View::GlobalSearch { selected } = app.current_view {
+                handle_global_search_input(key, selected, app, ui);
+                return;
+            }
+            if key.modifiers == KeyModifiers::CONTROL {
+                match key.code {
+                    KeyCode::Char('p') => {
+                        if !matches!(app.current_view, View::ContextMenu { .. }) {
+                            ui.open_command_palette(app);
+                        }
+                        return;
+                    }
+                    KeyCode::Char('f') => {
+                        if !matches!(app.current_view, View::ContextMenu { .. }) {
+                            ui.open_global_search(app);
+                        }
+                        return;
+                    }
+                    // In the task list Ctrl+R / Ctrl+Z reset and restore task timers instead
+                    KeyCode::Char('r') => {
+                        if app.current_view == View::TaskList {
+                            ui.confirm_reset_task_timers = true;
+                        } else {
+                            ui.reload_settings(app);
+                        }
+                        return;
+                    }
+                    KeyCode::Char('z') => {
+                        if app.current_view == View::TaskList && !ui.task_list_undo.is_empty() {
+                            ui.undo_task_list_action(app);
+                        } else {
+                            ui.undo_settings_reload(app);
+                        }
+                        return;
+                    }
+                    KeyCode::Char('s') => {
+                        app.save();
+                        ui.show_toast("Saved");
+                        return;
+                    }
+                    KeyCode::Char('e') => {
+                        if !matches!(app.current_view, View::ContextMenu { .. }) {
+                            ui.request_state_edit();
+                        }
+                        return;
+                    }
+                    // Ctrl+L and Ctrl+S already redraw and save, so only the timer gets a jump key
+                    KeyCode::Char('t') => {
+                        if app.current_view == View::Settings {
+                            // Restores the saved list positions before leaving
+                            ui.close_settings(app);
+                        }
+                        if matches!(app.current_view, View::TaskList | View::Statistics | View::TaskDetails) {
+                            go_to_view(app, ui, View::Timer);
+                        }
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+            // In details, `o` opens the task's external ID instead when it has one
+            let details_has_external_id = app.current_view == View::TaskDetails
+                && ui.details_task.and_then(|i| app.tasks.get(i)).is_some_and(|t| t.external_id.is_some());
+            if key.code == KeyCode::Char('o') && key.modifiers == KeyModifiers::NONE && !details_has_external_id {
+                if matches!(app.current_view, View::Timer | View::TaskList | View::Statistics | View::TaskDetails) {
+                    ui.open_settings(app);
+                }
+                return;
+            }
+            if key.code == KeyCode::Char('?') {
+                ui.show_help_overlay = true;
+                ui.help_scroll = 0;
+                return;
+            }
+            // Shift+Tab; left alone while the timer's mode-switch prompt is up
+            if key.code == KeyCode::BackTab && ui.pending_mode_switch.is_none() {
+                go_to_view(app, ui, app.current_view.tab_prev());
                 return;
             }
 
@@ -187,61 +610,232 @@ fn handle_key_event(key: KeyEvent, app: &mut App, ui: &mut UiState, player: Opti
                 View::Statistics => handle_stats_input(key, app, ui),
                 View::Settings => handle_settings_input(key, app, ui),
                 View::TaskDetails => handle_task_details_input(key, app, ui),
+                View::ContextMenu { .. } => handle_context_menu_input(key, app, ui),
+                View::ProjectManager { selected } => handle_project_manager_input(key, selected, app, ui),
+                View::SortMenu { selected } => handle_sort_menu_input(key, selected, app, ui),
+                View::LabelManager { selected } => handle_label_manager_input(key, selected, app, ui),
+                View::PresetPicker { selected } => handle_preset_picker_input(key, selected, app, ui),
+                View::CommandPalette { .. } | View::GlobalSearch { .. } => {}
             }
         }
     }
 }
 
-/// Plays a sound notification based on the mode that just finished.
-fn play_sound(sink: &Player, finished_mode: Mode) {
-    let (freq1, freq2, duration) = match finished_mode {
-        Mode::Pomodoro => (440.0, 660.0, 150),
-        _ => (660.0, 440.0, 150),
+fn handle_mouse_event(mouse: MouseEvent, app: &mut App, ui: &mut UiState) {
+    if !matches!(ui.input_mode, InputMode::Normal) {
+        return;
+    }
+    let on_task_list = app.current_view == View::TaskList;
+    match mouse.kind {
+        MouseEventKind::Moved => ui.update_mouse_area(app, mouse.column, mouse.row),
+        MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+            ui.update_mouse_area(app, mouse.column, mouse.row);
+            ui.scroll_hovered_list(app, mouse.kind == MouseEventKind::ScrollDown);
+        }
+        MouseEventKind::Down(MouseButton::Left) if on_task_list => {
+            ui.click_task_list(app, mouse.column, mouse.row)
+        }
+        MouseEventKind::Down(MouseButton::Right) if on_task_list => {
+            ui.open_context_menu(app, mouse.column, mouse.row)
+        }
+        _ => {}
+    }
+}
+
+/// Plays the configured sound for the mode that just finished.
+fn play_sound(sink: &Player, finished_mode: Mode, settings: &Settings) {
+    let sound = match finished_mode {
+        Mode::Pomodoro => settings.pomodoro_end_sound,
+        _ => settings.break_end_sound,
     };
-    let source1 = SineWave::new(freq1)
-        .take_duration(Duration::from_millis(duration))
-        .amplify(0.20);
-    let source2 = SineWave::new(freq2)
-        .take_duration(Duration::from_millis(duration))
-        .amplify(0.20);
-    sink.append(source1);
-    sink.append(source2);
-}
-
-/// Shows a desktop notification.
-fn show_desktop_notification(finished_mode: Mode, next_mode: Mode) {
-    let summary = format!("{} Finished!", finished_mode.title());
-    let body = format!("Time for your {}.", next_mode.title());
-    let _ = Notification::new()
+    for &(freq, millis) in sound.tones() {
+        sink.append(
+            SineWave::new(freq)
+                .take_duration(Duration::from_millis(millis))
+                .amplify(0.20),
+        );
+    }
+}
+
+/// Falling low tones when a task runs out of time budget, unlike any end-of-segment sound.
+fn budget_warning(sink: &Player) {
+    for freq in [392.0, 294.0, 196.0] {
+        sink.append(
+            SineWave::new(freq)
+                .take_duration(Duration::from_millis(220))
+                .amplify(0.25),
+        );
+    }
+}
+
+const COUNTDOWN_BEEP_SECONDS: u64 = 10;
+
+/// Short tick for the last seconds of a pomodoro, rising from 300Hz at 10s
+/// left to 880Hz at 1s.
+fn countdown_beep(sink: &Player, seconds_remaining: u64) {
+    let steps = (COUNTDOWN_BEEP_SECONDS - seconds_remaining.clamp(1, COUNTDOWN_BEEP_SECONDS)) as f32;
+    let freq = 300.0 + steps * (880.0 - 300.0) / (COUNTDOWN_BEEP_SECONDS - 1) as f32;
+    sink.append(
+        SineWave::new(freq)
+            .take_duration(Duration::from_millis(80))
+            .amplify(0.15),
+    );
+}
+
+/// Summary and body of the notification for a segment that just ended.
+fn segment_notification_text(finished_mode: Mode, next_mode: Mode, settings: &Settings) -> (String, String) {
+    (
+        format!("{} Finished!", finished_mode.label(settings)),
+        format!("Time for your {}.", next_mode.label(settings)),
+    )
+}
+
+fn segment_notification(finished_mode: Mode, next_mode: Mode, settings: &Settings) -> Notification {
+    let (summary, body) = segment_notification_text(finished_mode, next_mode, settings);
+    let mut notification = Notification::new();
+    notification
         .summary(&summary)
         .body(&body)
-        .icon("dialog-information")
-        .show();
+        .icon("dialog-information");
+    notification
+}
+
+/// Shows a desktop notification with "Start" / "Dismiss" buttons. Clicking
+/// "Start" sends `AppMessage::StartTimer` back to the main loop. The
+/// notification is shown and awaited on its own thread, since
+/// `wait_for_action` blocks until the user responds or it expires.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn show_desktop_notification(finished_mode: Mode, next_mode: Mode, settings: &Settings, messages: Option<Sender<AppMessage>>, _native: bool) {
+    let mut notification = segment_notification(finished_mode, next_mode, settings);
+    let Some(tx) = messages else {
+        let _ = notification.show();
+        return;
+    };
+    let start_label = match next_mode {
+        Mode::Pomodoro => format!("Start {}", next_mode.label(settings)),
+        Mode::ShortBreak | Mode::LongBreak => "Start Break".to_string(),
+    };
+    notification
+        .action("start", &start_label)
+        .action("dismiss", "Dismiss");
+    thread::spawn(move || {
+        if let Ok(handle) = notification.show() {
+            handle.wait_for_action(|action| {
+                if action == "start" {
+                    let _ = tx.send(AppMessage::StartTimer);
+                }
+            });
+        }
+    });
+}
+
+/// Notification actions need the freedesktop notification server; elsewhere
+/// the notification is informational only.
+#[cfg(not(unix))]
+fn show_desktop_notification(finished_mode: Mode, next_mode: Mode, settings: &Settings, _messages: Option<Sender<AppMessage>>, _native: bool) {
+    let _ = segment_notification(finished_mode, next_mode, settings).show();
+}
+
+/// notify-rust doesn't show anything on some macOS versions, so AppleScript's
+/// `display notification` is the fallback, or the only path with `--native-notif`.
+#[cfg(target_os = "macos")]
+fn show_desktop_notification(finished_mode: Mode, next_mode: Mode, settings: &Settings, _messages: Option<Sender<AppMessage>>, native: bool) {
+    if native || segment_notification(finished_mode, next_mode, settings).show().is_err() {
+        let (summary, body) = segment_notification_text(finished_mode, next_mode, settings);
+        if let Err(e) = osascript_notification(&summary, &body) {
+            tracing::warn!(error = %e, "osascript notification failed");
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn osascript_notification(summary: &str, body: &str) -> io::Result<()> {
+    let quote = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let script = format!("display notification \"{}\" with title \"{}\"", quote(body), quote(summary));
+    let status = std::process::Command::new("osascript").arg("-e").arg(script).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("osascript exited with {}", status)))
+    }
+}
+
+/// Sound and desktop notification for a segment that just ended.
+fn announce_segment_end(finished_mode: Mode, app: &App, ui: &UiState, player: Option<&Player>) {
+    if let Some(p) = player {
+        play_sound(p, finished_mode, &app.settings);
+    }
+    if app.settings.desktop_notifications {
+        show_desktop_notification(finished_mode, app.mode, &app.settings, ui.app_messages.clone(), ui.native_notifications);
+    }
+}
+
+fn skip_segment(app: &mut App, ui: &UiState, player: Option<&Player>) {
+    let finished_mode = app.skip_segment();
+    announce_segment_end(finished_mode, app, ui, player);
+}
+
+fn force_complete_session(app: &mut App, ui: &UiState, player: Option<&Player>) {
+    let finished_mode = app.force_complete_session();
+    announce_segment_end(finished_mode, app, ui, player);
 }
 
 fn handle_timer_input(key: KeyEvent, app: &mut App, ui: &mut UiState, player: Option<&Player>) {
+    if let Some(mode) = ui.pending_mode_switch {
+        match key.code {
+            KeyCode::Char('y') => {
+                app.switch_mode(mode);
+                ui.pending_mode_switch = None;
+            }
+            KeyCode::Char('n') | KeyCode::Esc => ui.pending_mode_switch = None,
+            _ => {}
+        }
+        return;
+    }
+
     match key.code {
         KeyCode::Char('q') => app.should_quit = true,
         KeyCode::Char(' ') => app.toggle_timer(),
         KeyCode::Char('r') => app.reset_timer(),
-        KeyCode::Char('n') => {
-            let finished_mode = app.skip_segment();
-            if let Some(p) = player {
-                play_sound(p, finished_mode);
-            }
-            if app.settings.desktop_notifications {
-                show_desktop_notification(finished_mode, app.mode);
-            }
+        KeyCode::Char('z') => ui.zen_mode = true,
+        KeyCode::Char('p') => ui.request_mode_switch(app, Mode::Pomodoro),
+        KeyCode::Char('s') => ui.request_mode_switch(app, Mode::ShortBreak),
+        KeyCode::Char('l') => ui.request_mode_switch(app, Mode::LongBreak),
+        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            ui.current_input.clear();
+            ui.input_mode = InputMode::QuickStart;
         }
-        KeyCode::Tab => {
-            ui.previous_view = app.current_view;
-            app.current_view = View::TaskList;
+        KeyCode::Char('n') => ui.input_mode = InputMode::Editing,
+        KeyCode::Char('N') => skip_segment(app, ui, player),
+        KeyCode::Char(',') | KeyCode::Char('.') => {
+            app.adjust_pomodoro_duration(if key.code == KeyCode::Char(',') { -1 } else { 1 });
+            ui.show_toast(format!("Pomodoro: {} min", app.settings.pomodoro_duration.as_secs() / 60));
         }
+        KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => app.show_session_summary(),
+        KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => app.take_break_now(),
+        KeyCode::Tab => go_to_view(app, ui, app.current_view.tab_next()),
         _ => {}
     }
 }
 
 fn handle_tasklist_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    if ui.confirm_reset_task_timers {
+        match key.code {
+            KeyCode::Char('y') => ui.resolve_reset_task_timers(app, true),
+            KeyCode::Char('n') | KeyCode::Esc => ui.resolve_reset_task_timers(app, false),
+            _ => {}
+        }
+        return;
+    }
+    if ui.confirm_complete_all {
+        match key.code {
+            KeyCode::Char('y') => ui.resolve_complete_all(app, true),
+            KeyCode::Char('n') | KeyCode::Esc => ui.resolve_complete_all(app, false),
+            _ => {}
+        }
+        return;
+    }
+
     match key {
         KeyEvent {
             code: KeyCode::Up,
@@ -252,7 +846,10 @@ fn handle_tasklist_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
             code: KeyCode::Char('K'),
             modifiers: KeyModifiers::SHIFT,
             ..
-        } => app.move_active_task_up(),
+        } => {
+            app.move_active_task_up();
+            ui.task_list_undo.clear();
+        }
         KeyEvent {
             code: KeyCode::Down,
             modifiers: KeyModifiers::SHIFT,
@@ -262,47 +859,110 @@ fn handle_tasklist_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
             code: KeyCode::Char('J'),
             modifiers: KeyModifiers::SHIFT,
             ..
-        } => app.move_active_task_down(),
+        } => {
+            app.move_active_task_down();
+            ui.task_list_undo.clear();
+        }
 
         KeyEvent { code, .. } => match code {
             KeyCode::Char('q') => app.should_quit = true,
-            KeyCode::Tab => {
-                ui.previous_view = app.current_view;
-                app.current_view = View::Statistics;
-            }
+            KeyCode::Tab => go_to_view(app, ui, app.current_view.tab_next()),
             KeyCode::Char('n') => ui.input_mode = InputMode::Editing,
+            KeyCode::Char('t') => ui.show_mini_timer = !ui.show_mini_timer,
             KeyCode::Char('e') => ui.start_rename(app),
             KeyCode::Char('E') if key.modifiers == KeyModifiers::SHIFT => ui.start_edit_notes_active(app),
             KeyCode::Char('/') => ui.input_mode = InputMode::Filtering,
+            // Most terminals send Ctrl+I as Tab, so `I` does the same
+            KeyCode::Char('i') if key.modifiers.contains(KeyModifiers::CONTROL) => app.toggle_inline_details(),
+            KeyCode::Char('I') => app.toggle_inline_details(),
+            KeyCode::Char('i') => {
+                if let Some(idx) = app.active_task_index {
+                    ui.open_task_details(app, idx);
+                }
+            }
+            // Without keyboard enhancement most terminals send Ctrl+Enter as Ctrl+J
+            KeyCode::Enter | KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                ui.confirm_complete_all = true;
+            }
             KeyCode::Down | KeyCode::Char('j') => ui.next_filtered_task(app),
             KeyCode::Up | KeyCode::Char('k') => ui.previous_filtered_task(app),
+            KeyCode::PageDown => ui.next_filtered_task_page(app),
+            KeyCode::PageUp => ui.previous_filtered_task_page(app),
             KeyCode::Enter => app.complete_active_task(),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some(idx) = app.active_task_index {
+                    app.toggle_task_deferred(idx);
+                }
+            }
+            KeyCode::Char('F') => app.show_deferred = !app.show_deferred,
+            KeyCode::Char('P') => ui.open_project_manager(app),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.copy_task_list(app),
+            KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => app.instant_complete_active_task(),
+            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                ui.current_input.clear();
+                ui.input_mode = InputMode::GoTo;
+            }
+            KeyCode::Char('-') => app.toggle_separator_below_active(),
+            KeyCode::Char('b') => ui.start_budget_input(app),
+            KeyCode::Char('S') => {
+                let selected = SortCriterion::ALL.iter().position(|&c| c == app.settings.default_sort).unwrap_or(0);
+                app.current_view = View::SortMenu { selected };
+            }
+            KeyCode::Char('c') => {
+                if let Some(idx) = app.active_task_index {
+                    app.cycle_task_color(idx);
+                }
+            }
             KeyCode::Char('d') | KeyCode::Delete => app.delete_active_task(),
-            KeyCode::Char(' ') => {
-                if app.active_task_index.is_some() {
-                    ui.previous_view = app.current_view;
-                    app.current_view = View::Timer;
+            KeyCode::Char('a') => {
+                if !app.settings.parallel_mode {
+                    ui.show_toast("Enable Parallel Tasks in settings first");
+                } else if let Some(idx) = app.active_task_index {
+                    app.toggle_task_active(idx);
                 }
             }
+            KeyCode::Char(' ') if app.active_task_index.is_some() => {
+                ui.previous_view = app.current_view;
+                app.current_view = View::Timer;
+            }
             _ => {}
         },
     }
 }
 
 fn handle_stats_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    if ui.confirm_delete_all_completed {
+        match key.code {
+            KeyCode::Char('y') => ui.resolve_delete_all_completed(app, true),
+            KeyCode::Char('n') | KeyCode::Esc => ui.resolve_delete_all_completed(app, false),
+            _ => {}
+        }
+        return;
+    }
+
     match key.code {
-        KeyCode::Char('q') => app.should_quit = true,
-        KeyCode::Tab => {
-            ui.previous_view = app.current_view;
-            app.current_view = View::Timer;
+        KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            ui.select_all_completed = !ui.select_all_completed;
         }
+        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.clear_completed_selection(),
+        // Many terminals send Ctrl+H as Backspace, hence `H` as well
+        KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.toggle_hide_zero_pomodoro_tasks(app),
+        KeyCode::Char('H') => ui.toggle_hide_zero_pomodoro_tasks(app),
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.undo_completed_task_deletion(app),
+        KeyCode::Esc => ui.clear_completed_selection(),
+        KeyCode::Char('q') => app.should_quit = true,
+        KeyCode::Tab => go_to_view(app, ui, app.current_view.tab_next()),
         KeyCode::Char('/') => ui.input_mode = InputMode::Filtering,
+        KeyCode::Char('t') => ui.show_mini_timer = !ui.show_mini_timer,
+        KeyCode::Char('f') => ui.cycle_stats_filter(app),
+        KeyCode::Char('F') => ui.start_stats_range_input(),
         KeyCode::Down | KeyCode::Char('j') => ui.next_completed_task(app),
         KeyCode::Up | KeyCode::Char('k') => ui.previous_completed_task(app),
+        KeyCode::PageDown => ui.next_completed_task_page(app),
+        KeyCode::PageUp => ui.previous_completed_task_page(app),
         KeyCode::Enter => {
-            if ui.completed_task_list_state.is_some() {
-                ui.previous_view = app.current_view;
-                app.current_view = View::TaskDetails;
+            if let Some(idx) = ui.selected_completed_task_index(app) {
+                ui.open_task_details(app, idx);
             }
         }
         KeyCode::Char('d') | KeyCode::Delete => ui.delete_selected_completed_task(app),
@@ -311,13 +971,28 @@ fn handle_stats_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
 }
 
 fn handle_settings_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    if ui.confirm_restore_defaults {
+        match key.code {
+            KeyCode::Char('y') => ui.resolve_restore_defaults(app, true),
+            KeyCode::Char('n') | KeyCode::Esc => ui.resolve_restore_defaults(app, false),
+            _ => {}
+        }
+        return;
+    }
+
     match key.code {
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.confirm_restore_defaults = true,
+        KeyCode::Enter => ui.activate_setting(app),
         KeyCode::Char('q') => app.should_quit = true,
-        KeyCode::Tab => app.current_view = ui.previous_view,
+        KeyCode::Tab => ui.close_settings(app),
         KeyCode::Up | KeyCode::Char('k') => ui.previous_setting(),
         KeyCode::Down | KeyCode::Char('j') => ui.next_setting(),
         KeyCode::Left | KeyCode::Char('h') => ui.modify_setting(app, false),
         KeyCode::Right | KeyCode::Char('l') => ui.modify_setting(app, true),
+        KeyCode::Char('O') => ui.open_data_dir(),
+        // Ctrl+D is already restore defaults, so saving a preset is `S`
+        KeyCode::Char('P') => app.current_view = View::PresetPicker { selected: 0 },
+        KeyCode::Char('S') => ui.start_preset_save(app),
         _ => {}
     }
 }
@@ -326,11 +1001,267 @@ fn handle_task_details_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
     match key.code {
         KeyCode::Char('q') => app.should_quit = true,
         KeyCode::Char('E') if key.modifiers == KeyModifiers::SHIFT => ui.start_edit_notes(app),
+        KeyCode::Char('a') => ui.input_mode = InputMode::EditingSubtask,
+        KeyCode::Char('l') => ui.input_mode = InputMode::EditingLink,
+        KeyCode::Char('o') => ui.open_external_id(app),
+        KeyCode::Char(' ') => ui.toggle_selected_subtask(app),
+        KeyCode::Char('r') => {
+            if let Some(index) = ui.details_task {
+                app.cycle_task_recurrence(index);
+            }
+        }
+        KeyCode::Char('+') | KeyCode::Char('=') => {
+            if let Some(index) = ui.details_task {
+                app.adjust_task_estimate(index, 1);
+            }
+        }
+        KeyCode::Char('-') => {
+            if let Some(index) = ui.details_task {
+                app.adjust_task_estimate(index, -1);
+            }
+        }
+        KeyCode::Down | KeyCode::Char('j') => ui.next_subtask(app),
+        KeyCode::Up | KeyCode::Char('k') => ui.previous_subtask(app),
         KeyCode::Esc | KeyCode::Enter => app.current_view = ui.previous_view,
         _ => {}
     }
 }
 
+fn handle_quick_start_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    match key.code {
+        KeyCode::Enter => ui.submit_quick_start(app),
+        KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.paste_into_input(),
+        KeyCode::Char(c) => {
+            ui.push_input_char(c);
+        }
+        KeyCode::Backspace => { ui.current_input.pop(); }
+        KeyCode::Esc => {
+            ui.input_mode = InputMode::Normal;
+            ui.current_input.clear();
+        }
+        _ => {}
+    }
+}
+
+fn handle_subtask_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    match key.code {
+        KeyCode::Enter => ui.submit_subtask(app),
+        KeyCode::Char(c) => ui.current_input.push(c),
+        KeyCode::Backspace => { ui.current_input.pop(); }
+        KeyCode::Esc => {
+            ui.input_mode = InputMode::Normal;
+            ui.current_input.clear();
+        }
+        _ => {}
+    }
+}
+
+fn handle_link_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    match key.code {
+        KeyCode::Enter => ui.submit_link(app),
+        KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.paste_into_input(),
+        KeyCode::Char(c) => ui.current_input.push(c),
+        KeyCode::Backspace => { ui.current_input.pop(); }
+        KeyCode::Esc => {
+            ui.input_mode = InputMode::Normal;
+            ui.current_input.clear();
+        }
+        _ => {}
+    }
+}
+
+fn handle_project_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    match key.code {
+        KeyCode::Enter => ui.submit_project(app),
+        KeyCode::Char(c) => ui.current_input.push(c),
+        KeyCode::Backspace => { ui.current_input.pop(); }
+        KeyCode::Esc => {
+            ui.input_mode = InputMode::Normal;
+            ui.current_input.clear();
+        }
+        _ => {}
+    }
+}
+
+fn handle_stats_range_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    match key.code {
+        KeyCode::Enter => ui.submit_stats_range(app),
+        KeyCode::Char(c) => ui.current_input.push(c),
+        KeyCode::Backspace => { ui.current_input.pop(); }
+        KeyCode::Esc => ui.cancel_stats_range(),
+        _ => {}
+    }
+}
+
+fn handle_budget_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    match key.code {
+        KeyCode::Enter => ui.submit_budget(app),
+        KeyCode::Char(c) if c.is_ascii_digit() => ui.current_input.push(c),
+        KeyCode::Backspace => { ui.current_input.pop(); }
+        KeyCode::Esc => {
+            ui.input_mode = InputMode::Normal;
+            ui.current_input.clear();
+        }
+        _ => {}
+    }
+}
+
+fn handle_go_to_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    match key.code {
+        KeyCode::Enter => ui.submit_go_to(app),
+        KeyCode::Char(c) if c.is_ascii_digit() => ui.current_input.push(c),
+        KeyCode::Backspace => { ui.current_input.pop(); }
+        KeyCode::Esc => {
+            ui.input_mode = InputMode::Normal;
+            ui.current_input.clear();
+        }
+        _ => {}
+    }
+}
+
+fn handle_project_manager_input(key: KeyEvent, selected: usize, app: &mut App, ui: &mut UiState) {
+    // Row 0 is "(no project)", so there is one more row than projects
+    let rows = app.projects.len() + 1;
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.current_view = View::ProjectManager { selected: (selected + rows - 1) % rows };
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.current_view = View::ProjectManager { selected: (selected + 1) % rows };
+        }
+        KeyCode::Enter => ui.pick_project(app, selected),
+        KeyCode::Char('n') => ui.input_mode = InputMode::EditingProject,
+        KeyCode::Char('c') if selected > 0 => app.cycle_project_color(selected - 1),
+        KeyCode::Char('d') | KeyCode::Delete if selected > 0 => {
+            app.delete_project(selected - 1);
+            app.current_view = View::ProjectManager { selected: selected.min(app.projects.len()) };
+        }
+        KeyCode::Esc | KeyCode::Char('P') => app.current_view = View::TaskList,
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+fn handle_label_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    match key.code {
+        KeyCode::Enter => ui.submit_label(app),
+        KeyCode::Char(c) => ui.current_input.push(c),
+        KeyCode::Backspace => { ui.current_input.pop(); }
+        KeyCode::Esc => {
+            ui.input_mode = InputMode::Normal;
+            ui.current_input.clear();
+        }
+        _ => {}
+    }
+}
+
+fn handle_label_manager_input(key: KeyEvent, selected: usize, app: &mut App, ui: &mut UiState) {
+    let rows = app.settings.available_labels.len();
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if rows > 0 => {
+            app.current_view = View::LabelManager { selected: (selected + rows - 1) % rows };
+        }
+        KeyCode::Down | KeyCode::Char('j') if rows > 0 => {
+            app.current_view = View::LabelManager { selected: (selected + 1) % rows };
+        }
+        KeyCode::Char('n') => ui.input_mode = InputMode::EditingLabel,
+        KeyCode::Char('d') | KeyCode::Delete => {
+            app.delete_label(selected);
+            let last = app.settings.available_labels.len().saturating_sub(1);
+            app.current_view = View::LabelManager { selected: selected.min(last) };
+        }
+        KeyCode::Esc | KeyCode::Enter => app.current_view = View::Settings,
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+fn handle_preset_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    match key.code {
+        KeyCode::Enter => ui.submit_preset(app),
+        KeyCode::Char(c) => ui.current_input.push(c),
+        KeyCode::Backspace => { ui.current_input.pop(); }
+        KeyCode::Esc => {
+            ui.input_mode = InputMode::Normal;
+            ui.current_input.clear();
+        }
+        _ => {}
+    }
+}
+
+fn handle_preset_picker_input(key: KeyEvent, selected: usize, app: &mut App, ui: &mut UiState) {
+    let rows = app.settings.presets.len();
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if rows > 0 => {
+            app.current_view = View::PresetPicker { selected: (selected + rows - 1) % rows };
+        }
+        KeyCode::Down | KeyCode::Char('j') if rows > 0 => {
+            app.current_view = View::PresetPicker { selected: (selected + 1) % rows };
+        }
+        KeyCode::Enter if rows > 0 => {
+            let name = app.settings.presets[selected.min(rows - 1)].name.clone();
+            if app.apply_settings_preset(&name) {
+                ui.show_toast(format!("Preset \"{}\" applied", name));
+            }
+            app.current_view = View::Settings;
+        }
+        KeyCode::Char('n') => ui.input_mode = InputMode::EditingPreset,
+        KeyCode::Char('d') | KeyCode::Delete => {
+            app.delete_settings_preset(selected);
+            let last = app.settings.presets.len().saturating_sub(1);
+            app.current_view = View::PresetPicker { selected: selected.min(last) };
+        }
+        KeyCode::Esc | KeyCode::Enter => app.current_view = View::Settings,
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+fn handle_sort_menu_input(key: KeyEvent, selected: usize, app: &mut App, ui: &mut UiState) {
+    let rows = SortCriterion::ALL.len();
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.current_view = View::SortMenu { selected: (selected + rows - 1) % rows };
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.current_view = View::SortMenu { selected: (selected + 1) % rows };
+        }
+        KeyCode::Enter => {
+            let criterion = SortCriterion::ALL[selected % rows];
+            app.reorder_tasks_by(criterion);
+            // Undo steps were recorded against the old order
+            ui.task_list_undo.clear();
+            app.current_view = View::TaskList;
+            ui.show_toast(format!("Sorted by {}", criterion));
+        }
+        KeyCode::Esc | KeyCode::Char('S') => app.current_view = View::TaskList,
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+fn handle_context_menu_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    let View::ContextMenu { options, selected, target_task, anchor } = app.current_view else {
+        return;
+    };
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            let selected = if selected == 0 { options.len() - 1 } else { selected - 1 };
+            app.current_view = View::ContextMenu { options, selected, target_task, anchor };
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            let selected = (selected + 1) % options.len();
+            app.current_view = View::ContextMenu { options, selected, target_task, anchor };
+        }
+        KeyCode::Enter => {
+            app.current_view = ui.previous_view;
+            ui.run_context_action(app, options[selected], target_task);
+        }
+        KeyCode::Esc => app.current_view = ui.previous_view,
+        _ => {}
+    }
+}
+
 fn handle_editing_notes_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
     match key {
         // Ctrl+S — save
@@ -364,10 +1295,22 @@ fn handle_filtering_input(key: KeyEvent, ui: &mut UiState) {
 }
 
 fn handle_editing_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
+    // Ctrl+C cancels the input like Esc rather than quitting
+    let key = if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)
+    } else {
+        key
+    };
     match key.code {
         KeyCode::Enter => ui.submit_task(app),
-        KeyCode::Char(c) => ui.current_input.push(c),
-        KeyCode::Backspace => { ui.current_input.pop(); }
+        KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.paste_into_input(),
+        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.delete_input_word(),
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.clear_input_line(),
+        KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => ui.undo_input(),
+        KeyCode::Char(c) => {
+            ui.push_input_char(c);
+        }
+        KeyCode::Backspace => ui.input_backspace(),
         KeyCode::Esc => {
             ui.input_mode = InputMode::Normal;
             ui.current_input.clear();
@@ -375,19 +1318,217 @@ fn handle_editing_input(key: KeyEvent, app: &mut App, ui: &mut UiState) {
         }
         _ => {}
     }
+    // Undo is scoped to one input session
+    if !matches!(ui.input_mode, InputMode::Editing) {
+        ui.input_history.clear();
+    }
 }
 
-fn ui(frame: &mut Frame, app: &App, ui_state: &UiState) {
-    let theme = Theme::from_settings(app.settings.theme, app.settings.custom_theme.as_ref());
-    match app.current_view {
-        View::Timer => draw_timer(frame, app, &theme),
+fn handle_command_palette_input(
+    key: KeyEvent,
+    selected: usize,
+    app: &mut App,
+    ui: &mut UiState,
+    player: Option<&Player>,
+) {
+    let matches = ui.palette_matches();
+    match key.code {
+        KeyCode::Esc => ui.close_command_palette(app),
+        KeyCode::Enter => {
+            if let Some(&command) = matches.get(selected) {
+                ui.close_command_palette(app);
+                run_command(command, app, ui, player);
+            }
+        }
+        KeyCode::Down if !matches.is_empty() => {
+            app.current_view = View::CommandPalette { selected: (selected + 1) % matches.len() };
+        }
+        KeyCode::Up if !matches.is_empty() => {
+            let selected = if selected == 0 { matches.len() - 1 } else { selected - 1 };
+            app.current_view = View::CommandPalette { selected };
+        }
+        KeyCode::Char(c) => {
+            ui.palette_query.push(c);
+            app.current_view = View::CommandPalette { selected: 0 };
+        }
+        KeyCode::Backspace => {
+            ui.palette_query.pop();
+            app.current_view = View::CommandPalette { selected: 0 };
+        }
+        _ => {}
+    }
+}
+
+fn handle_global_search_input(key: KeyEvent, selected: usize, app: &mut App, ui: &mut UiState) {
+    let result_count = app.search_tasks(&ui.search_query).len();
+    match key.code {
+        KeyCode::Esc => ui.close_global_search(app),
+        KeyCode::Enter => {
+            if let Some(&(index, _)) = app.search_tasks(&ui.search_query).get(selected) {
+                ui.go_to_task(app, index);
+            }
+        }
+        KeyCode::Down if result_count > 0 => {
+            app.current_view = View::GlobalSearch { selected: (selected + 1) % result_count };
+        }
+        KeyCode::Up if result_count > 0 => {
+            let selected = if selected == 0 { result_count - 1 } else { selected - 1 };
+            app.current_view = View::GlobalSearch { selected };
+        }
+        KeyCode::Char(c) => {
+            ui.search_query.push(c);
+            app.current_view = View::GlobalSearch { selected: 0 };
+        }
+        KeyCode::Backspace => {
+            ui.search_query.pop();
+            app.current_view = View::GlobalSearch { selected: 0 };
+        }
+        _ => {}
+    }
+}
+
+fn go_to_view(app: &mut App, ui: &mut UiState, view: View) {
+    if app.current_view != view {
+        ui.previous_view = app.current_view;
+        app.current_view = view;
+    }
+}
+
+/// `worklog-YYYY-MM-DD.txt` in the data directory, from `stats::print_daily_log`.
+fn write_daily_log_file(app: &App) -> io::Result<PathBuf> {
+    let dir = app::get_data_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory"))?;
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("worklog-{}.txt", chrono::Local::now().format("%Y-%m-%d")));
+    let mut file = std::fs::File::create(&path)?;
+    stats::print_daily_log(app, &mut file)?;
+    Ok(path)
+}
+
+fn run_command(command: Command, app: &mut App, ui: &mut UiState, player: Option<&Player>) {
+    match command {
+        Command::StartPauseTimer => app.toggle_timer(),
+        Command::ResetTimer => app.reset_timer(),
+        Command::SkipSegment => skip_segment(app, ui, player),
+        Command::FinishSession => force_complete_session(app, ui, player),
+        Command::TakeBreakNow => {
+            go_to_view(app, ui, View::Timer);
+            app.take_break_now();
+        }
+        // Mode switches may need the timer view's [y/n] prompt, so go there first
+        Command::SwitchToPomodoro | Command::SwitchToShortBreak | Command::SwitchToLongBreak => {
+            let mode = match command {
+                Command::SwitchToShortBreak => Mode::ShortBreak,
+                Command::SwitchToLongBreak => Mode::LongBreak,
+                _ => Mode::Pomodoro,
+            };
+            go_to_view(app, ui, View::Timer);
+            ui.request_mode_switch(app, mode);
+        }
+        Command::NewTask => {
+            if app.current_view != View::Timer {
+                go_to_view(app, ui, View::TaskList);
+            }
+            ui.input_mode = InputMode::Editing;
+        }
+        Command::GoToTimer => go_to_view(app, ui, View::Timer),
+        Command::GoToTaskList => go_to_view(app, ui, View::TaskList),
+        Command::GoToStatistics => go_to_view(app, ui, View::Statistics),
+        Command::ManageProjects => {
+            go_to_view(app, ui, View::TaskList);
+            ui.open_project_manager(app);
+        }
+        Command::OpenSettings => {
+            if app.current_view != View::Settings {
+                ui.open_settings(app);
+            }
+        }
+        Command::ZenMode => {
+            go_to_view(app, ui, View::Timer);
+            ui.zen_mode = true;
+        }
+        Command::ToggleMiniTimer => ui.show_mini_timer = !ui.show_mini_timer,
+        Command::ShowHelp => {
+            ui.show_help_overlay = true;
+            ui.help_scroll = 0;
+        }
+        Command::Save => {
+            app.save();
+            ui.show_toast("Saved");
+        }
+        Command::ReloadConfig => ui.reload_settings(app),
+        Command::UndoConfigReload => ui.undo_settings_reload(app),
+        Command::EditState => ui.request_state_edit(),
+        Command::PrintDailyLog => match write_daily_log_file(app) {
+            Ok(path) => ui.show_toast(format!("Work log written to {}", path.display())),
+            Err(e) => ui.show_toast(format!("Could not write work log: {}", e)),
+        },
+        Command::Quit => app.should_quit = true,
+    }
+}
+
+fn ui(frame: &mut Frame, app: &App, ui_state: &mut UiState) {
+    let mut theme = Theme::from_settings(app.settings.theme, app.settings.custom_theme.as_ref());
+    if app.settings.accessibility_mode {
+        Theme::accessibility_override(&mut theme);
+    }
+    // The palette floats over whatever view it was opened from
+    let base_view = match app.current_view {
+        View::CommandPalette { .. } => ui_state.palette_return_view,
+        View::GlobalSearch { .. } => ui_state.search_return_view,
+        view => view,
+    };
+    match base_view {
+        View::Timer => draw_timer(frame, app, ui_state, &theme),
         View::TaskList => draw_task_list(frame, app, ui_state, &theme),
         View::Statistics => draw_statistics(frame, app, ui_state, &theme),
         View::Settings => draw_settings(frame, app, ui_state, &theme),
         View::TaskDetails => draw_task_details(frame, app, ui_state, &theme),
+        View::ContextMenu { options, selected, anchor, .. } => {
+            draw_task_list(frame, app, ui_state, &theme);
+            draw_context_menu(frame, options, selected, anchor, &theme);
+        }
+        View::ProjectManager { selected } => {
+            draw_task_list(frame, app, ui_state, &theme);
+            draw_project_manager(frame, app, ui_state, selected, &theme);
+        }
+        View::SortMenu { selected } => {
+            draw_task_list(frame, app, ui_state, &theme);
+            draw_sort_menu(frame, app, selected, &theme);
+        }
+        View::LabelManager { selected } => {
+            draw_settings(frame, app, ui_state, &theme);
+            draw_label_manager(frame, app, ui_state, selected, &theme);
+        }
+        View::PresetPicker { selected } => {
+            draw_settings(frame, app, ui_state, &theme);
+            draw_preset_picker(frame, app, ui_state, selected, &theme);
+        }
+        View::CommandPalette { .. } | View::GlobalSearch { .. } => {}
+    }
+    if ui_state.show_mini_timer && matches!(base_view, View::TaskList | View::Statistics) {
+        draw_mini_timer(frame, app, &theme);
     }
     if matches!(ui_state.input_mode, InputMode::EditingNotes) {
         draw_notes_modal(frame, ui_state, &theme);
     }
+    if matches!(ui_state.input_mode, InputMode::QuickStart) {
+        draw_quick_start_modal(frame, ui_state, &theme);
+    }
+    if let View::CommandPalette { selected } = app.current_view {
+        draw_command_palette(frame, &ui_state.palette_query, &ui_state.palette_matches(), selected, &theme);
+    }
+    if let View::GlobalSearch { selected } = app.current_view {
+        draw_global_search(frame, &ui_state.search_query, &app.search_tasks(&ui_state.search_query), selected, &theme);
+    }
+    if ui_state.show_help_overlay {
+        draw_help_overlay(frame, ui_state.help_scroll, &theme);
+    }
+    if let Some(message) = ui_state.active_toast() {
+        draw_toast(frame, message, &theme);
+    }
+    if app.settings.accessibility_mode {
+        let area = frame.area();
+        frame.buffer_mut().set_style(area, Style::default().add_modifier(Modifier::BOLD));
+    }
 }
 
@@ -0,0 +1,107 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+
+use crate::app::{get_data_dir, App};
+
+/// Format of the hand-editable state file: the legacy `state.json` and the
+/// `Ctrl+E` export. Day-to-day state lives in SQLite either way.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, ValueEnum)]
+pub enum StateFormat {
+    #[default]
+    Json,
+    Toml,
+}
+
+impl StateFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            StateFormat::Json => "json",
+            StateFormat::Toml => "toml",
+        }
+    }
+
+    /// Anything without a `.toml` extension is read as JSON.
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => StateFormat::Toml,
+            _ => StateFormat::Json,
+        }
+    }
+}
+
+/// `state.json` or `state.toml` in the data directory.
+pub fn state_path(format: StateFormat) -> Option<PathBuf> {
+    get_data_dir().map(|d| d.join(format!("state.{}", format.extension())))
+}
+
+pub fn save_state(app: &App, format: StateFormat, path: &Path) -> io::Result<()> {
+    let text = match format {
+        StateFormat::Json => serde_json::to_string_pretty(app).map_err(io::Error::other)?,
+        StateFormat::Toml => toml::to_string_pretty(app).map_err(io::Error::other)?,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, text)
+}
+
+/// Reads a state file, picking the format from its extension.
+pub fn load_state(path: &Path) -> io::Result<App> {
+    let text = fs::read_to_string(path)?;
+    let mut app: App = match StateFormat::from_path(path) {
+        StateFormat::Json => serde_json::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        StateFormat::Toml => toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+    };
+    app.claim_task_ids();
+    Ok(app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{RecurrenceRule, Task};
+    use chrono::Weekday;
+
+    fn sample_app() -> App {
+        let mut task = Task::new("Write docs".to_string(), Some("work".to_string()));
+        task.notes = Some("first draft".to_string());
+        task.recurrence = Some(RecurrenceRule::Weekly(Weekday::Mon));
+        App {
+            tasks: vec![task, Task::new("Inbox".to_string(), None)],
+            active_task_index: Some(0),
+            pomodoros_completed_total: 4,
+            ..App::default()
+        }
+    }
+
+    #[test]
+    fn round_trips_through_both_formats() {
+        let dir = std::env::temp_dir().join(format!("pomodorust-state-{}", std::process::id()));
+        for format in [StateFormat::Json, StateFormat::Toml] {
+            let path = dir.join(format!("state.{}", format.extension()));
+            let app = sample_app();
+            save_state(&app, format, &path).unwrap();
+            let loaded = load_state(&path).unwrap();
+            assert_eq!(loaded.tasks.len(), 2);
+            assert_eq!(loaded.tasks[0].id, app.tasks[0].id);
+            assert_eq!(loaded.tasks[0].name, "Write docs");
+            assert_eq!(loaded.tasks[0].project.as_deref(), Some("work"));
+            assert_eq!(loaded.tasks[0].recurrence, Some(RecurrenceRule::Weekly(Weekday::Mon)));
+            assert_eq!(loaded.tasks[1].project, None);
+            assert_eq!(loaded.active_task_index, Some(0));
+            assert_eq!(loaded.pomodoros_completed_total, 4);
+        }
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn format_follows_extension() {
+        assert_eq!(StateFormat::from_path(Path::new("state.toml")), StateFormat::Toml);
+        assert_eq!(StateFormat::from_path(Path::new("state.TOML")), StateFormat::Toml);
+        assert_eq!(StateFormat::from_path(Path::new("state.json")), StateFormat::Json);
+        assert_eq!(StateFormat::from_path(Path::new("state")), StateFormat::Json);
+    }
+}
@@ -0,0 +1,98 @@
+//! Pauses the timer while the screen is locked, via the session bus's
+//! `ScreenSaver.ActiveChanged` signal (GNOME and freedesktop names).
+
+use std::process::Child;
+use std::sync::mpsc::Sender;
+
+use crate::app::AppMessage;
+
+#[cfg(target_os = "linux")]
+const MATCH_RULES: [&str; 2] = [
+    "type='signal',interface='org.gnome.ScreenSaver',member='ActiveChanged'",
+    "type='signal',interface='org.freedesktop.ScreenSaver',member='ActiveChanged'",
+];
+
+/// Keeps the `dbus-monitor` started by `watch` alive; dropping it kills and
+/// reaps the process, which also ends the reader thread.
+pub struct Watcher {
+    child: Option<Child>,
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        if let Some(child) = &mut self.child {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Follows `dbus-monitor` in a background thread and forwards lock / unlock
+/// as `PauseAndSave` / `NotifyResumed` for as long as the returned `Watcher`
+/// is held. Does nothing if `dbus-monitor` is missing or there is no session bus.
+#[cfg(target_os = "linux")]
+pub fn watch(tx: Sender<AppMessage>) -> Watcher {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+
+    let child = Command::new("dbus-monitor")
+        .arg("--session")
+        .args(MATCH_RULES)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::debug!("screen lock watcher unavailable: {}", e);
+            return Watcher { child: None };
+        }
+    };
+    let Some(stdout) = child.stdout.take() else { return Watcher { child: Some(child) } };
+    std::thread::spawn(move || {
+        let mut in_signal = false;
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if line.contains("member=ActiveChanged") {
+                in_signal = true;
+                continue;
+            }
+            if !in_signal {
+                continue;
+            }
+            in_signal = false;
+            if let Some(message) = parse_active_changed(&line) {
+                if tx.send(message).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    Watcher { child: Some(child) }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn watch(_tx: Sender<AppMessage>) -> Watcher {
+    Watcher { child: None }
+}
+
+/// The `boolean true|false` argument line that follows an `ActiveChanged` header.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_active_changed(line: &str) -> Option<AppMessage> {
+    match line.trim() {
+        "boolean true" => Some(AppMessage::PauseAndSave),
+        "boolean false" => Some(AppMessage::NotifyResumed),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_changed_argument_maps_to_message() {
+        assert_eq!(parse_active_changed("   boolean true"), Some(AppMessage::PauseAndSave));
+        assert_eq!(parse_active_changed("   boolean false"), Some(AppMessage::NotifyResumed));
+        assert_eq!(parse_active_changed("   string \"x\""), None);
+    }
+}
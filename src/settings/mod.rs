@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
-use std::{fs, time::Duration};
+use std::{fmt, fs, path::PathBuf, time::Duration};
 
-use crate::app::get_config_path;
+use crate::app::{get_config_path, Mode, View};
 
 pub mod theme;
 pub use theme::Theme;
@@ -18,6 +18,124 @@ pub enum ColorTheme {
     Custom,
 }
 
+/// Which break follows a finished pomodoro.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum BreakSchedule {
+    /// A long break after every `long_break_interval` pomodoros, short otherwise.
+    #[default]
+    Automatic,
+    AlwaysShort,
+    AlwaysLong,
+    /// Lands on a paused short break; pick the actual break with `[s]`/`[l]`.
+    Manual,
+}
+
+impl BreakSchedule {
+    pub const ALL: [BreakSchedule; 4] = [
+        BreakSchedule::Automatic,
+        BreakSchedule::AlwaysShort,
+        BreakSchedule::AlwaysLong,
+        BreakSchedule::Manual,
+    ];
+}
+
+/// Tone pattern played when a segment ends.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BuiltinSound {
+    Ascending,
+    Descending,
+    DoubleBell,
+    Chime,
+}
+
+impl BuiltinSound {
+    pub const ALL: [BuiltinSound; 4] = [
+        BuiltinSound::Ascending,
+        BuiltinSound::Descending,
+        BuiltinSound::DoubleBell,
+        BuiltinSound::Chime,
+    ];
+
+    /// `(frequency Hz, milliseconds)` per tone; a 0 Hz tone is a rest.
+    pub fn tones(self) -> &'static [(f32, u64)] {
+        match self {
+            BuiltinSound::Ascending => &[(440.0, 150), (660.0, 150)],
+            BuiltinSound::Descending => &[(660.0, 150), (440.0, 150)],
+            BuiltinSound::DoubleBell => &[(880.0, 120), (0.0, 80), (880.0, 120)],
+            BuiltinSound::Chime => &[(523.25, 150), (659.25, 150), (783.99, 250)],
+        }
+    }
+}
+
+/// Order applied by the task list's `[S]` sort menu.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortCriterion {
+    #[default]
+    Name,
+    Priority,
+    CreationDate,
+    TimeSpent,
+    PomodoroCount,
+}
+
+impl SortCriterion {
+    pub const ALL: [SortCriterion; 5] = [
+        SortCriterion::Name,
+        SortCriterion::Priority,
+        SortCriterion::CreationDate,
+        SortCriterion::TimeSpent,
+        SortCriterion::PomodoroCount,
+    ];
+}
+
+impl fmt::Display for SortCriterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SortCriterion::Name => "Name (A-Z)",
+            SortCriterion::Priority => "Priority (highest first)",
+            SortCriterion::CreationDate => "Creation date (oldest first)",
+            SortCriterion::TimeSpent => "Time spent (most first)",
+            SortCriterion::PomodoroCount => "Pomodoros (most first)",
+        })
+    }
+}
+
+impl fmt::Display for BuiltinSound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BuiltinSound::Ascending => "Ascending",
+            BuiltinSound::Descending => "Descending",
+            BuiltinSound::DoubleBell => "Double Bell",
+            BuiltinSound::Chime => "Chime",
+        })
+    }
+}
+
+impl fmt::Display for ColorTheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ColorTheme::Default => "Default",
+            ColorTheme::Dracula => "Dracula",
+            ColorTheme::Solarized => "Solarized",
+            ColorTheme::Nord => "Nord",
+            ColorTheme::GruvboxDark => "Gruvbox Dark",
+            ColorTheme::Cyberpunk => "Cyberpunk",
+            ColorTheme::Custom => "Custom",
+        })
+    }
+}
+
+impl fmt::Display for BreakSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BreakSchedule::Automatic => "Automatic",
+            BreakSchedule::AlwaysShort => "Always Short",
+            BreakSchedule::AlwaysLong => "Always Long",
+            BreakSchedule::Manual => "Manual",
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct CustomThemeColors {
     pub pomodoro_color: Option<String>,
@@ -40,6 +158,38 @@ fn default_short_break_mins() -> u64 { 5 }
 fn default_long_break_mins() -> u64 { 15 }
 fn default_long_break_interval() -> u32 { 4 }
 fn default_notifications() -> bool { true }
+fn default_autosave_interval_secs() -> u64 { 0 }
+fn default_task_age_warning_days() -> u64 { 7 }
+fn default_task_age_danger_days() -> u64 { 30 }
+fn default_show_seconds() -> bool { true }
+fn default_pomodoro_end_sound() -> BuiltinSound { BuiltinSound::Ascending }
+fn default_break_end_sound() -> BuiltinSound { BuiltinSound::Descending }
+fn default_pomodoro_label() -> String { "Pomodoro".to_string() }
+fn default_short_break_label() -> String { "Short Break".to_string() }
+fn default_long_break_label() -> String { "Long Break".to_string() }
+
+/// Named timer setup under `[[presets]]` in config.toml, e.g. "Deep Work" at
+/// 50/10 minutes; saved and applied from the Settings view.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SettingsPreset {
+    pub name: String,
+    pomodoro_duration_mins: u64,
+    short_break_duration_mins: u64,
+    long_break_duration_mins: u64,
+    long_break_interval: u32,
+    #[serde(default)]
+    break_schedule: BreakSchedule,
+}
+
+impl SettingsPreset {
+    /// "50 / 10 / 20 mins", for the preset picker.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} / {} / {} mins",
+            self.pomodoro_duration_mins, self.short_break_duration_mins, self.long_break_duration_mins
+        )
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct SerializableSettings {
@@ -55,8 +205,45 @@ struct SerializableSettings {
     theme: ColorTheme,
     #[serde(default = "default_notifications")]
     desktop_notifications: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    screensaver_timeout_mins: Option<u64>,
+    // 0 disables autosave; TOML has no null to express `None`
+    #[serde(default = "default_autosave_interval_secs")]
+    autosave_interval_secs: u64,
+    #[serde(default)]
+    parallel_mode: bool,
+    #[serde(default = "default_task_age_warning_days")]
+    task_age_warning_days: u64,
+    #[serde(default = "default_task_age_danger_days")]
+    task_age_danger_days: u64,
+    #[serde(default)]
+    break_schedule: BreakSchedule,
+    #[serde(default)]
+    start_view: View,
+    #[serde(default = "default_show_seconds")]
+    show_seconds: bool,
+    #[serde(default)]
+    countdown_beeps: bool,
+    #[serde(default = "default_pomodoro_end_sound")]
+    pomodoro_end_sound: BuiltinSound,
+    #[serde(default = "default_break_end_sound")]
+    break_end_sound: BuiltinSound,
+    #[serde(default)]
+    default_sort: SortCriterion,
+    #[serde(default = "default_pomodoro_label")]
+    pomodoro_label: String,
+    #[serde(default = "default_short_break_label")]
+    short_break_label: String,
+    #[serde(default = "default_long_break_label")]
+    long_break_label: String,
+    #[serde(default)]
+    accessibility_mode: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    available_labels: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     custom_theme: Option<CustomThemeColors>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    presets: Vec<SettingsPreset>,
 }
 
 #[derive(Debug, Clone)]
@@ -67,7 +254,39 @@ pub struct Settings {
     pub long_break_interval: u32,
     pub theme: ColorTheme,
     pub desktop_notifications: bool,
+    /// Idle time on a paused timer before the screensaver kicks in; `None` disables it.
+    pub screensaver_timeout: Option<Duration>,
+    /// How often the running app writes state to disk; `None` saves on quit only.
+    pub autosave_interval: Option<Duration>,
+    /// Lets several tasks be active at once; focus time accrues on all of them.
+    pub parallel_mode: bool,
+    /// Active tasks older than this many days are tinted as a warning.
+    pub task_age_warning_days: u64,
+    /// Active tasks older than this many days are tinted as likely abandoned.
+    pub task_age_danger_days: u64,
+    pub break_schedule: BreakSchedule,
+    /// View shown on launch: Timer, TaskList or Statistics.
+    pub start_view: View,
+    /// Big timer shows `MM:SS`; when off, just the minutes.
+    pub show_seconds: bool,
+    /// Beep once a second through the last 10 seconds of a pomodoro.
+    pub countdown_beeps: bool,
+    /// Played when a pomodoro ends ("take a break").
+    pub pomodoro_end_sound: BuiltinSound,
+    /// Played when a break ends ("back to work").
+    pub break_end_sound: BuiltinSound,
+    /// Last order picked from the task list's sort menu.
+    pub default_sort: SortCriterion,
+    /// Names shown for each mode, e.g. "Deep Work" instead of "Pomodoro".
+    pub pomodoro_label: String,
+    pub short_break_label: String,
+    pub long_break_label: String,
+    /// Thick borders, bold text, brightest colors and more forgiving mouse clicks.
+    pub accessibility_mode: bool,
+    /// Labels tasks can carry, typed as `@name`; managed from Settings → Edit Labels.
+    pub available_labels: Vec<String>,
     pub custom_theme: Option<CustomThemeColors>,
+    pub presets: Vec<SettingsPreset>,
 }
 
 impl From<SerializableSettings> for Settings {
@@ -79,7 +298,31 @@ impl From<SerializableSettings> for Settings {
             long_break_interval: s.long_break_interval,
             theme: s.theme,
             desktop_notifications: s.desktop_notifications,
+            screensaver_timeout: s.screensaver_timeout_mins.map(|m| Duration::from_secs(m * 60)),
+            autosave_interval: Some(s.autosave_interval_secs)
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs),
+            parallel_mode: s.parallel_mode,
+            task_age_warning_days: s.task_age_warning_days,
+            task_age_danger_days: s.task_age_danger_days,
+            break_schedule: s.break_schedule,
+            // Popups make no sense as a landing view
+            start_view: match s.start_view {
+                View::Timer | View::Statistics => s.start_view,
+                _ => View::TaskList,
+            },
+            show_seconds: s.show_seconds,
+            countdown_beeps: s.countdown_beeps,
+            pomodoro_end_sound: s.pomodoro_end_sound,
+            break_end_sound: s.break_end_sound,
+            default_sort: s.default_sort,
+            pomodoro_label: s.pomodoro_label,
+            short_break_label: s.short_break_label,
+            long_break_label: s.long_break_label,
+            accessibility_mode: s.accessibility_mode,
+            available_labels: s.available_labels,
             custom_theme: s.custom_theme,
+            presets: s.presets,
         }
     }
 }
@@ -93,7 +336,25 @@ impl From<&Settings> for SerializableSettings {
             long_break_interval: s.long_break_interval,
             theme: s.theme,
             desktop_notifications: s.desktop_notifications,
+            screensaver_timeout_mins: s.screensaver_timeout.map(|d| d.as_secs() / 60),
+            autosave_interval_secs: s.autosave_interval.map_or(0, |d| d.as_secs()),
+            parallel_mode: s.parallel_mode,
+            task_age_warning_days: s.task_age_warning_days,
+            task_age_danger_days: s.task_age_danger_days,
+            break_schedule: s.break_schedule,
+            start_view: s.start_view,
+            show_seconds: s.show_seconds,
+            countdown_beeps: s.countdown_beeps,
+            pomodoro_end_sound: s.pomodoro_end_sound,
+            break_end_sound: s.break_end_sound,
+            default_sort: s.default_sort,
+            pomodoro_label: s.pomodoro_label.clone(),
+            short_break_label: s.short_break_label.clone(),
+            long_break_label: s.long_break_label.clone(),
+            accessibility_mode: s.accessibility_mode,
+            available_labels: s.available_labels.clone(),
             custom_theme: s.custom_theme.clone(),
+            presets: s.presets.clone(),
         }
     }
 }
@@ -107,23 +368,300 @@ impl Default for Settings {
             long_break_interval: 4,
             theme: ColorTheme::Default,
             desktop_notifications: true,
+            screensaver_timeout: None,
+            autosave_interval: None,
+            parallel_mode: false,
+            task_age_warning_days: 7,
+            task_age_danger_days: 30,
+            break_schedule: BreakSchedule::Automatic,
+            start_view: View::TaskList,
+            show_seconds: true,
+            countdown_beeps: false,
+            pomodoro_end_sound: BuiltinSound::Ascending,
+            break_end_sound: BuiltinSound::Descending,
+            default_sort: SortCriterion::Name,
+            pomodoro_label: default_pomodoro_label(),
+            short_break_label: default_short_break_label(),
+            long_break_label: default_long_break_label(),
+            accessibility_mode: false,
+            available_labels: Vec::new(),
             custom_theme: None,
+            presets: Vec::new(),
+        }
+    }
+}
+
+/// Gentle nudges when the timings stray far from the classic technique
+/// (25 min work, 5 min short break, 15–30 min long break, 4 per set).
+pub fn pomodoro_technique_warnings(s: &Settings) -> Vec<String> {
+    let mins = |d: Duration| d.as_secs() / 60;
+    let pomodoro = mins(s.pomodoro_duration);
+    let short = mins(s.short_break_duration);
+    let long = mins(s.long_break_duration);
+    let mut warnings = Vec::new();
+
+    if pomodoro < 15 {
+        warnings.push(format!("⚠ Pomodoro ({} min) is unusually short. Recommended: 25 min.", pomodoro));
+    } else if pomodoro > 50 {
+        warnings.push(format!("⚠ Pomodoro ({} min) is unusually long. Recommended: 25 min.", pomodoro));
+    }
+    if short < 4 {
+        warnings.push(format!("⚠ Short break ({} min) is unusually short. Recommended: 5 min.", short));
+    } else if short > 10 {
+        warnings.push(format!("⚠ Short break ({} min) is unusually long. Recommended: 5 min.", short));
+    }
+    if long < 15 {
+        warnings.push(format!("⚠ Long break ({} min) is unusually short. Recommended: 15–30 min.", long));
+    } else if long > 30 {
+        warnings.push(format!("⚠ Long break ({} min) is unusually long. Recommended: 15–30 min.", long));
+    }
+    if long <= short {
+        warnings.push("⚠ Long break is not longer than the short break.".to_string());
+    }
+    if !(2..=6).contains(&s.long_break_interval) {
+        warnings.push(format!(
+            "⚠ Long break every {} pomodoros is unusual. Recommended: 4.",
+            s.long_break_interval
+        ));
+    }
+    warnings
+}
+
+/// Returned by `Settings::load` when `config.toml` exists but cannot be parsed.
+#[derive(Debug)]
+pub struct SettingsLoadError {
+    pub path: PathBuf,
+    pub error: toml::de::Error,
+    pub content: String,
+}
+
+impl fmt::Display for SettingsLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to parse {}: {}", self.path.display(), self.error)
+    }
+}
+
+impl std::error::Error for SettingsLoadError {}
+
+impl SettingsLoadError {
+    /// Copies the unparsable file to `config.toml.bak` next to it, so running on
+    /// defaults (and saving them on exit) can't lose the user's settings.
+    pub fn back_up(&self) -> std::io::Result<PathBuf> {
+        let backup = self.path.with_extension("toml.bak");
+        fs::write(&backup, &self.content)?;
+        Ok(backup)
+    }
+}
+
+/// Returned by `Settings::from_pairs` for a label or value it doesn't recognize.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    UnknownKey(String),
+    InvalidValue { key: String, value: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnknownKey(key) => write!(f, "unknown setting \"{}\"", key),
+            ParseError::InvalidValue { key, value } => write!(f, "invalid value \"{}\" for {}", value, key),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn on_off(on: bool) -> String {
+    if on { "On" } else { "Off" }.to_string()
+}
+
+fn start_view_label(view: View) -> &'static str {
+    match view {
+        View::Timer => "Timer",
+        View::Statistics => "Statistics",
+        _ => "Task List",
+    }
+}
+
+/// The settings table as `(label, value)` pairs, in the row order of the
+/// Settings view, e.g. `("Pomodoro Duration", "25 mins")`.
+impl From<&Settings> for Vec<(String, String)> {
+    fn from(s: &Settings) -> Self {
+        let mins = |d: Duration| format!("{} mins", d.as_secs() / 60);
+        [
+            ("Pomodoro Duration", mins(s.pomodoro_duration)),
+            ("Short Break", mins(s.short_break_duration)),
+            ("Long Break", mins(s.long_break_duration)),
+            ("Color Theme", s.theme.to_string()),
+            ("Desktop Notifications", on_off(s.desktop_notifications)),
+            ("Long Break Interval", format!("{} pomodoros", s.long_break_interval)),
+            ("Screensaver", s.screensaver_timeout.map_or("Off".to_string(), |d| format!("after {}", mins(d)))),
+            ("Parallel Tasks", on_off(s.parallel_mode)),
+            ("Stale Task Warning", format!("after {} days", s.task_age_warning_days)),
+            ("Abandoned Task Alert", format!("after {} days", s.task_age_danger_days)),
+            ("Break Schedule", s.break_schedule.to_string()),
+            ("Start View", start_view_label(s.start_view).to_string()),
+            ("Show Seconds", on_off(s.show_seconds)),
+            ("Countdown Beeps", on_off(s.countdown_beeps)),
+            ("Pomodoro End Sound", s.pomodoro_end_sound.to_string()),
+            ("Break End Sound", s.break_end_sound.to_string()),
+            ("Autosave", s.autosave_interval.map_or("Off".to_string(), |d| format!("every {} secs", d.as_secs()))),
+            ("Accessibility Mode", on_off(s.accessibility_mode)),
+        ]
+        .into_iter()
+        .map(|(label, value)| (label.to_string(), value))
+        .collect()
+    }
+}
+
+/// The variant of `all` whose `Display` text is `value`.
+fn parse_label<T: fmt::Display + Copy>(all: &[T], value: &str) -> Option<T> {
+    all.iter().copied().find(|v| v.to_string() == value)
+}
+
+impl Settings {
+    /// Inverse of the `Vec<(String, String)>` conversion. Labels that are
+    /// missing keep their default; unknown labels and bad values are errors.
+    pub fn from_pairs(pairs: &[(String, String)]) -> Result<Settings, ParseError> {
+        Settings::default().with_pairs(pairs)
+    }
+
+    /// These settings with `pairs` applied. Everything without a pair, including
+    /// fields that have no Settings row (presets, labels, custom theme), is kept.
+    pub fn with_pairs(&self, pairs: &[(String, String)]) -> Result<Settings, ParseError> {
+        let mut s = self.clone();
+        for (key, value) in pairs {
+            let invalid = || ParseError::InvalidValue { key: key.clone(), value: value.clone() };
+            // `"<prefix><n><suffix>"`, e.g. `"after 7 days"`
+            let number = |prefix: &str, suffix: &str| -> Result<u64, ParseError> {
+                value.strip_prefix(prefix)
+                    .and_then(|v| v.strip_suffix(suffix))
+                    .and_then(|n| n.parse().ok())
+                    .ok_or_else(invalid)
+            };
+            let mins = |prefix: &str| match number(prefix, " mins")? {
+                0 => Err(invalid()),
+                m => Ok(Duration::from_secs(m * 60)),
+            };
+            let flag = || match value.as_str() {
+                "On" => Ok(true),
+                "Off" => Ok(false),
+                _ => Err(invalid()),
+            };
+            match key.as_str() {
+                "Pomodoro Duration" => s.pomodoro_duration = mins("")?,
+                "Short Break" => s.short_break_duration = mins("")?,
+                "Long Break" => s.long_break_duration = mins("")?,
+                "Color Theme" => {
+                    let themes = [
+                        ColorTheme::Default,
+                        ColorTheme::Dracula,
+                        ColorTheme::Solarized,
+                        ColorTheme::Nord,
+                        ColorTheme::GruvboxDark,
+                        ColorTheme::Cyberpunk,
+                        ColorTheme::Custom,
+                    ];
+                    s.theme = parse_label(&themes, value).ok_or_else(invalid)?;
+                }
+                "Desktop Notifications" => s.desktop_notifications = flag()?,
+                "Long Break Interval" => {
+                    s.long_break_interval = u32::try_from(number("", " pomodoros")?).map_err(|_| invalid())?;
+                }
+                "Screensaver" => {
+                    s.screensaver_timeout = match value.as_str() {
+                        "Off" => None,
+                        _ => Some(mins("after ")?),
+                    };
+                }
+                "Parallel Tasks" => s.parallel_mode = flag()?,
+                "Stale Task Warning" => s.task_age_warning_days = number("after ", " days")?,
+                "Abandoned Task Alert" => s.task_age_danger_days = number("after ", " days")?,
+                "Break Schedule" => s.break_schedule = parse_label(&BreakSchedule::ALL, value).ok_or_else(invalid)?,
+                "Start View" => {
+                    s.start_view = [View::Timer, View::TaskList, View::Statistics]
+                        .into_iter()
+                        .find(|&v| start_view_label(v) == value)
+                        .ok_or_else(invalid)?;
+                }
+                "Show Seconds" => s.show_seconds = flag()?,
+                "Countdown Beeps" => s.countdown_beeps = flag()?,
+                "Pomodoro End Sound" => s.pomodoro_end_sound = parse_label(&BuiltinSound::ALL, value).ok_or_else(invalid)?,
+                "Break End Sound" => s.break_end_sound = parse_label(&BuiltinSound::ALL, value).ok_or_else(invalid)?,
+                "Autosave" => {
+                    s.autosave_interval = match value.as_str() {
+                        "Off" => None,
+                        _ => match number("every ", " secs")? {
+                            0 => return Err(invalid()),
+                            secs => Some(Duration::from_secs(secs)),
+                        },
+                    };
+                }
+                "Accessibility Mode" => s.accessibility_mode = flag()?,
+                _ => return Err(ParseError::UnknownKey(key.clone())),
+            }
         }
+        Ok(s)
     }
 }
 
 impl Settings {
-    pub fn load() -> Self {
+    /// Break to take after a pomodoro; `pomodoros_in_set` counts the one just
+    /// finished, so it runs from 1 up to `long_break_interval`.
+    pub fn next_break_mode(&self, pomodoros_in_set: u32) -> Mode {
+        match self.break_schedule {
+            BreakSchedule::Automatic if pomodoros_in_set >= self.long_break_interval.max(1) => Mode::LongBreak,
+            BreakSchedule::AlwaysLong => Mode::LongBreak,
+            BreakSchedule::Automatic | BreakSchedule::AlwaysShort | BreakSchedule::Manual => Mode::ShortBreak,
+        }
+    }
+
+    /// Stores the durations, long-break interval and break schedule as `name`,
+    /// replacing any preset already called that.
+    pub fn save_preset(&mut self, name: &str) {
+        let mins = |d: Duration| d.as_secs() / 60;
+        let preset = SettingsPreset {
+            name: name.to_string(),
+            pomodoro_duration_mins: mins(self.pomodoro_duration),
+            short_break_duration_mins: mins(self.short_break_duration),
+            long_break_duration_mins: mins(self.long_break_duration),
+            long_break_interval: self.long_break_interval,
+            break_schedule: self.break_schedule,
+        };
+        match self.presets.iter_mut().find(|p| p.name == name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    /// These settings with preset `name`'s timer values applied.
+    pub fn load_preset(&self, name: &str) -> Option<Settings> {
+        let preset = self.presets.iter().find(|p| p.name == name)?;
+        let mins = |m: u64| Duration::from_secs(m.max(1) * 60);
+        Some(Settings {
+            pomodoro_duration: mins(preset.pomodoro_duration_mins),
+            short_break_duration: mins(preset.short_break_duration_mins),
+            long_break_duration: mins(preset.long_break_duration_mins),
+            long_break_interval: preset.long_break_interval.max(1),
+            break_schedule: preset.break_schedule,
+            ..self.clone()
+        })
+    }
+
+    /// A missing config file is not an error — defaults are written out and returned.
+    /// A config file that exists but fails to parse is left untouched and reported.
+    pub fn load() -> Result<Self, Box<SettingsLoadError>> {
         if let Some(path) = get_config_path() {
             if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(serializable) = toml::from_str::<SerializableSettings>(&content) {
-                    return serializable.into();
-                }
+                return match toml::from_str::<SerializableSettings>(&content) {
+                    Ok(serializable) => Ok(serializable.into()),
+                    Err(error) => Err(Box::new(SettingsLoadError { path, error, content })),
+                };
             }
         }
         let default_settings = Settings::default();
         default_settings.save();
-        default_settings
+        Ok(default_settings)
     }
 
     pub fn save(&self) {
@@ -143,6 +681,8 @@ impl Settings {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ratatui::style::Color;
+    use ratatui::widgets::BorderType;
 
     #[test]
     fn deserialize_with_custom_theme() {
@@ -181,4 +721,149 @@ base_bg = "#282828"
         assert_eq!(s.long_break_interval, 4);
         assert!(s.custom_theme.is_some());
     }
+
+    #[test]
+    fn load_error_reports_path_and_keeps_content() {
+        let content = "pomodoro_duration_mins = \"twenty\"\n".to_string();
+        let error = toml::from_str::<SerializableSettings>(&content).unwrap_err();
+        let err = SettingsLoadError { path: PathBuf::from("/tmp/config.toml"), error, content: content.clone() };
+        assert!(err.to_string().starts_with("failed to parse /tmp/config.toml"));
+        assert_eq!(err.content, content);
+    }
+
+    #[test]
+    fn next_break_mode_follows_schedule() {
+        let mut settings = Settings::default();
+        assert_eq!(settings.next_break_mode(3), Mode::ShortBreak);
+        assert_eq!(settings.next_break_mode(4), Mode::LongBreak);
+        settings.break_schedule = BreakSchedule::AlwaysShort;
+        assert_eq!(settings.next_break_mode(4), Mode::ShortBreak);
+        settings.break_schedule = BreakSchedule::AlwaysLong;
+        assert_eq!(settings.next_break_mode(1), Mode::LongBreak);
+        settings.break_schedule = BreakSchedule::Manual;
+        assert_eq!(settings.next_break_mode(4), Mode::ShortBreak);
+    }
+
+    #[test]
+    fn start_view_only_accepts_main_views() {
+        let parse = |toml: &str| Settings::from(toml::from_str::<SerializableSettings>(toml).unwrap()).start_view;
+        assert_eq!(parse(""), View::TaskList);
+        assert_eq!(parse("start_view = \"Timer\""), View::Timer);
+        assert_eq!(parse("start_view = \"Settings\""), View::TaskList);
+    }
+
+    #[test]
+    fn technique_warnings() {
+        assert!(pomodoro_technique_warnings(&Settings::default()).is_empty());
+
+        let settings = Settings {
+            short_break_duration: Duration::from_secs(3 * 60),
+            long_break_interval: 10,
+            ..Settings::default()
+        };
+        assert_eq!(
+            pomodoro_technique_warnings(&settings),
+            vec![
+                "⚠ Short break (3 min) is unusually short. Recommended: 5 min.".to_string(),
+                "⚠ Long break every 10 pomodoros is unusual. Recommended: 4.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn pairs_round_trip() {
+        let settings = Settings {
+            pomodoro_duration: Duration::from_secs(50 * 60),
+            theme: ColorTheme::GruvboxDark,
+            screensaver_timeout: Some(Duration::from_secs(10 * 60)),
+            task_age_warning_days: 3,
+            break_schedule: BreakSchedule::AlwaysLong,
+            start_view: View::Statistics,
+            countdown_beeps: true,
+            break_end_sound: BuiltinSound::DoubleBell,
+            autosave_interval: Some(Duration::from_secs(120)),
+            accessibility_mode: true,
+            ..Settings::default()
+        };
+        let pairs = Vec::<(String, String)>::from(&settings);
+        assert_eq!(pairs[0], ("Pomodoro Duration".to_string(), "50 mins".to_string()));
+        assert_eq!(pairs[6], ("Screensaver".to_string(), "after 10 mins".to_string()));
+        let parsed = Settings::from_pairs(&pairs).unwrap();
+        assert_eq!(Vec::<(String, String)>::from(&parsed), pairs);
+
+        let bad = |key: &str, value: &str| Settings::from_pairs(&[(key.to_string(), value.to_string())]);
+        assert_eq!(bad("Volume", "11").unwrap_err(), ParseError::UnknownKey("Volume".to_string()));
+        assert!(bad("Short Break", "0 mins").is_err());
+        assert!(bad("Show Seconds", "yes").is_err());
+
+        let mut customized = settings.clone();
+        customized.save_preset("Deep Work");
+        customized.available_labels = vec!["urgent".to_string()];
+        let updated = customized.with_pairs(&[("Countdown Beeps".to_string(), "Off".to_string())]).unwrap();
+        assert!(!updated.countdown_beeps);
+        assert_eq!(updated.theme, ColorTheme::GruvboxDark);
+        assert_eq!(updated.presets, customized.presets);
+        assert_eq!(updated.available_labels, customized.available_labels);
+    }
+
+    #[test]
+    fn mode_labels_fall_back_when_blank() {
+        let settings = Settings {
+            pomodoro_label: "Deep Work".to_string(),
+            short_break_label: " ".to_string(),
+            ..Settings::default()
+        };
+        assert_eq!(Mode::Pomodoro.label(&settings), "Deep Work");
+        assert_eq!(Mode::ShortBreak.label(&settings), "Short Break");
+        assert_eq!(Mode::LongBreak.label(&settings), "Long Break");
+    }
+
+    #[test]
+    fn accessibility_override_brightens_colors() {
+        let mut theme = Theme::default();
+        Theme::accessibility_override(&mut theme);
+        assert_eq!(theme.help_text_fg, Color::Rgb(215, 225, 255));
+        assert_eq!(theme.base_bg, Color::Black);
+        assert_eq!(theme.border_type, BorderType::Thick);
+    }
+
+    #[test]
+    fn presets_save_and_load_by_name() {
+        let mut settings = Settings {
+            pomodoro_duration: Duration::from_secs(50 * 60),
+            short_break_duration: Duration::from_secs(10 * 60),
+            ..Settings::default()
+        };
+        settings.save_preset("Deep Work");
+        settings.pomodoro_duration = Duration::from_secs(25 * 60);
+        settings.save_preset("Quick");
+        settings.save_preset("Quick");
+        assert_eq!(settings.presets.len(), 2);
+
+        let deep = settings.load_preset("Deep Work").unwrap();
+        assert_eq!(deep.pomodoro_duration, Duration::from_secs(50 * 60));
+        assert_eq!(deep.short_break_duration, Duration::from_secs(10 * 60));
+        assert_eq!(deep.presets, settings.presets);
+        assert!(settings.load_preset("Missing").is_none());
+
+        let toml = toml::to_string_pretty(&SerializableSettings::from(&settings)).unwrap();
+        assert!(toml.contains("[[presets]]"));
+        let reloaded: Settings = toml::from_str::<SerializableSettings>(&toml).unwrap().into();
+        assert_eq!(reloaded.presets, settings.presets);
+    }
+
+    #[test]
+    fn load_error_backs_up_the_unparsable_file() {
+        let dir = std::env::temp_dir().join(format!("pomodorust-settings-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        let content = "pomodoro_duration_mins = \"oops\"".to_string();
+        let error = toml::from_str::<SerializableSettings>(&content).unwrap_err();
+        let load_error = SettingsLoadError { path, error, content: content.clone() };
+
+        let backup = load_error.back_up().unwrap();
+        assert_eq!(backup, dir.join("config.toml.bak"));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), content);
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
@@ -1,4 +1,5 @@
 use ratatui::style::Color;
+use ratatui::widgets::BorderType;
 use super::{ColorTheme, CustomThemeColors};
 
 fn hex_to_color(hex: &str) -> Option<Color> {
@@ -24,9 +25,51 @@ pub struct Theme {
     pub paused_fg: Color,
     pub highlight_bg: Color,
     pub help_text_fg: Color,
+    pub border_type: BorderType,
+}
+
+/// Brightest variant of a foreground color: the light ANSI colors, or an RGB
+/// value scaled until its strongest channel is 255. Grays become white.
+fn brightest(color: Color) -> Color {
+    match color {
+        Color::Red | Color::LightRed => Color::LightRed,
+        Color::Green | Color::LightGreen => Color::LightGreen,
+        Color::Yellow | Color::LightYellow => Color::LightYellow,
+        Color::Blue | Color::LightBlue => Color::LightBlue,
+        Color::Magenta | Color::LightMagenta => Color::LightMagenta,
+        Color::Cyan | Color::LightCyan => Color::LightCyan,
+        Color::Rgb(r, g, b) => {
+            let max = r.max(g).max(b) as u16;
+            if max == 0 {
+                return Color::White;
+            }
+            let scale = |c: u8| (c as u16 * 255 / max) as u8;
+            Color::Rgb(scale(r), scale(g), scale(b))
+        }
+        _ => Color::White,
+    }
 }
 
 impl Theme {
+    /// Accessibility mode, whatever the theme: thick borders, a black
+    /// background and every text color at its brightest.
+    pub fn accessibility_override(theme: &mut Theme) {
+        for color in [
+            &mut theme.pomodoro_color,
+            &mut theme.short_break_color,
+            &mut theme.long_break_color,
+            &mut theme.accent_color,
+            &mut theme.base_fg,
+            &mut theme.running_fg,
+            &mut theme.paused_fg,
+            &mut theme.help_text_fg,
+        ] {
+            *color = brightest(*color);
+        }
+        theme.base_bg = Color::Black;
+        theme.border_type = BorderType::Thick;
+    }
+
     pub fn from_settings(theme_enum: ColorTheme, custom: Option<&CustomThemeColors>) -> Self {
         match theme_enum {
             ColorTheme::Default => Self::default(),
@@ -54,6 +97,7 @@ impl Theme {
             paused_fg:         Color::Rgb(254, 128,  25), // bright orange #fe8019
             highlight_bg:      Color::Rgb( 60,  56,  54), // bg1 #3c3836
             help_text_fg:      Color::Rgb(146, 131, 116), // gray #928374
+            border_type:       BorderType::Rounded,
         }
     }
 
@@ -72,6 +116,7 @@ impl Theme {
             paused_fg:         Color::Rgb(255, 109,   0), // neon orange     #ff6d00
             highlight_bg:      Color::Rgb( 30,  10,  60), // deep purple     #1e0a3c
             help_text_fg:      Color::Rgb(123, 104, 238), // medium slate    #7b68ee
+            border_type:       BorderType::Rounded,
         }
     }
 
@@ -99,6 +144,7 @@ impl Theme {
             paused_fg:         field!(paused_fg),
             highlight_bg:      field!(highlight_bg),
             help_text_fg:      field!(help_text_fg),
+            border_type:       BorderType::Rounded,
         }
     }
 
@@ -119,6 +165,7 @@ impl Theme {
             paused_fg:         Color::Rgb(255, 184, 108), // orange
             highlight_bg:      Color::Rgb( 68,  71,  90),
             help_text_fg:      Color::Rgb( 98, 114, 164),
+            border_type:       BorderType::Rounded,
         }
     }
 
@@ -139,6 +186,7 @@ impl Theme {
             paused_fg:         Color::Rgb(181, 137,   0), // yellow
             highlight_bg:      Color::Rgb(  7,  54,  66), // base02
             help_text_fg:      Color::Rgb( 88, 110, 117), // base01
+            border_type:       BorderType::Rounded,
         }
     }
 
@@ -159,6 +207,7 @@ impl Theme {
             paused_fg:         Color::Rgb(235, 203, 139), // nord13 yellow
             highlight_bg:      Color::Rgb( 59,  66,  82), // nord1
             help_text_fg:      Color::Rgb( 76,  86, 106), // nord3
+            border_type:       BorderType::Rounded,
         }
     }
 }
@@ -184,6 +233,7 @@ impl Default for Theme {
             highlight_bg:      Color::Rgb( 45,  52,  68),
             // Subdued but legible against near-black bg
             help_text_fg:      Color::Rgb(110, 115, 130),
+            border_type:       BorderType::Rounded,
         }
     }
 }
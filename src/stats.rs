@@ -0,0 +1,321 @@
+use std::cmp::Reverse;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+
+use crate::app::{App, Mode, Task};
+use crate::settings::Settings;
+use crate::util::format_hm;
+
+const DAYS_IN_CHART: i64 = 7;
+const MAX_BAR_WIDTH: u64 = 30;
+const LOG_WIDTH: usize = 80;
+
+// Cut to `width` characters, ending in `…` when shortened
+fn fit_width(s: &str, width: usize) -> String {
+    if s.chars().count() <= width {
+        return s.to_string();
+    }
+    let mut cut: String = s.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+// Pipes would split a Markdown table cell
+fn escape_cell(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+fn completion_day<Tz: TimeZone>(task: &Task, tz: &Tz) -> Option<NaiveDate> {
+    task.completion_date.map(|d| d.with_timezone(tz).date_naive())
+}
+
+/// Consecutive days with at least one completed task, ending today — or
+/// yesterday, so the streak doesn't read 0 before the first task of the day.
+fn current_streak<Tz: TimeZone>(app: &App, now: &DateTime<Tz>) -> u32 {
+    let tz = now.timezone();
+    let days: std::collections::HashSet<NaiveDate> = app.tasks.iter()
+        .filter_map(|t| completion_day(t, &tz))
+        .collect();
+    let today = now.date_naive();
+    let mut day = if days.contains(&today) { today } else { today - chrono::Duration::days(1) };
+    let mut streak = 0;
+    while days.contains(&day) {
+        streak += 1;
+        day -= chrono::Duration::days(1);
+    }
+    streak
+}
+
+/// Share of tracked time that went into completed pomodoros: 1.0 when every
+/// tracked minute was pomodoro time, lower with more pausing and overrun.
+/// 0.0 until any time is tracked.
+pub fn focus_efficiency(tasks: &[Task], settings: &Settings) -> f64 {
+    let total_time: Duration = tasks.iter().map(|t| t.time_spent).sum();
+    if total_time.is_zero() {
+        return 0.0;
+    }
+    let total_pomodoros: u32 = tasks.iter().map(|t| t.pomodoros).sum();
+    total_pomodoros as f64 * settings.pomodoro_duration.as_secs_f64() / total_time.as_secs_f64()
+}
+
+/// Plain-text log of today's segments for a work journal, for `--print` and
+/// the command palette. Lines fit in 80 columns; task notes follow the
+/// first session of each task.
+pub fn print_daily_log(app: &App, out: &mut dyn Write) -> io::Result<()> {
+    write_daily_log(app, &Local::now(), out)
+}
+
+fn write_daily_log<Tz: TimeZone>(app: &App, now: &DateTime<Tz>, out: &mut dyn Write) -> io::Result<()>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let tz = now.timezone();
+    let today = now.date_naive();
+    writeln!(out, "Work log for {}", today.format("%Y-%m-%d"))?;
+    writeln!(out)?;
+
+    let mut seen: Vec<u64> = Vec::new();
+    let mut focused = Duration::ZERO;
+    let mut pomodoros = 0;
+    let entries = app.session_log.iter().filter(|e| e.finished.with_timezone(&tz).date_naive() == today);
+    for entry in entries {
+        let span = format!(
+            "[{}–{}]",
+            entry.started.with_timezone(&tz).format("%H:%M"),
+            entry.finished.with_timezone(&tz).format("%H:%M"),
+        );
+        if entry.mode != Mode::Pomodoro {
+            writeln!(out, "{} {}", span, entry.mode.label(&app.settings))?;
+            continue;
+        }
+        pomodoros += 1;
+        // The span includes pauses; no pomodoro focuses for longer than its length
+        let span_length = (entry.finished - entry.started).to_std().unwrap_or_default();
+        focused += span_length.min(app.settings.pomodoro_duration);
+        // Tasks deleted since drop out of the line
+        let tasks: Vec<&Task> = entry.task_ids.iter()
+            .filter_map(|id| app.tasks.iter().find(|t| t.id == *id))
+            .collect();
+        let name = if tasks.is_empty() {
+            entry.mode.label(&app.settings).to_string()
+        } else {
+            tasks.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join(", ")
+        };
+        // Running count of today's sessions on the same task(s)
+        let count = app.session_log.iter()
+            .filter(|e| e.mode == Mode::Pomodoro && e.task_ids == entry.task_ids)
+            .filter(|e| e.finished.with_timezone(&tz).date_naive() == today && e.finished <= entry.finished)
+            .count();
+        let suffix = format!(" ({} ●)", count);
+        let room = LOG_WIDTH - span.chars().count() - 1 - suffix.chars().count();
+        writeln!(out, "{} {}{}", span, fit_width(&name, room), suffix)?;
+
+        for task in tasks {
+            if seen.contains(&task.id) {
+                continue;
+            }
+            seen.push(task.id);
+            for line in task.notes.iter().flat_map(|n| n.lines()).filter(|l| !l.trim().is_empty()) {
+                writeln!(out, "    {}", fit_width(line.trim_end(), LOG_WIDTH - 4))?;
+            }
+        }
+    }
+
+    if pomodoros == 0 {
+        writeln!(out, "No sessions yet today.")?;
+    } else {
+        writeln!(out)?;
+        writeln!(out, "{} pomodoro{}, {} focused", pomodoros, if pomodoros == 1 { "" } else { "s" }, format_hm(focused))?;
+    }
+    Ok(())
+}
+
+/// Markdown report of the whole task history, for `--export-md`.
+pub fn export_markdown(app: &App) -> String {
+    render_report(app, &Local::now())
+}
+
+fn render_report<Tz: TimeZone>(app: &App, now: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let tz = now.timezone();
+    let mut out = String::new();
+
+    out.push_str("# Pomodorust Report\n\n");
+    out.push_str(&format!("Generated on {}\n\n", now.format("%Y-%m-%d %H:%M")));
+
+    let total_focus: Duration = app.tasks.iter().map(|t| t.time_spent).sum();
+    let streak = current_streak(app, now);
+    out.push_str("## Summary\n\n");
+    out.push_str("| Metric | Value |\n| ------ | ----- |\n");
+    out.push_str(&format!("| Total sessions | {} |\n", app.pomodoros_completed_total));
+    out.push_str(&format!("| Total focus time | {} |\n", format_hm(total_focus)));
+    out.push_str(&format!("| Current streak | {} day{} |\n\n", streak, if streak == 1 { "" } else { "s" }));
+
+    out.push_str("## Tasks\n\n");
+    if app.tasks.is_empty() {
+        out.push_str("No tasks yet.\n\n");
+    } else {
+        out.push_str("| Task | Project | Status | Pomodoros | Focus time |\n");
+        out.push_str("| ---- | ------- | ------ | --------: | ---------: |\n");
+        let mut tasks: Vec<_> = app.tasks.iter().filter(|t| !t.is_separator).collect();
+        // Stable sort keeps list order among tasks with equal pomodoros
+        tasks.sort_by_key(|t| Reverse(t.pomodoros));
+        for task in tasks {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                escape_cell(&task.name),
+                task.project.as_deref().map_or(String::new(), |p| format!("@{}", escape_cell(p))),
+                if task.completed { "Completed" } else { "Active" },
+                task.pomodoros,
+                format_hm(task.time_spent),
+            ));
+        }
+        out.push('\n');
+    }
+
+    // Pomodoros credited to tasks completed on each of the last seven days
+    let today = now.date_naive();
+    let daily: Vec<(NaiveDate, u64)> = (0..DAYS_IN_CHART)
+        .rev()
+        .map(|ago| {
+            let day = today - chrono::Duration::days(ago);
+            let count = app.tasks.iter()
+                .filter(|t| completion_day(t, &tz) == Some(day))
+                .map(|t| t.pomodoros as u64)
+                .sum();
+            (day, count)
+        })
+        .collect();
+    let peak = daily.iter().map(|(_, c)| *c).max().unwrap_or(0);
+    out.push_str("## Last 7 days\n\n```text\n");
+    for (day, count) in daily {
+        let width = if peak > MAX_BAR_WIDTH { count * MAX_BAR_WIDTH / peak } else { count };
+        let bar = "█".repeat(width as usize);
+        if bar.is_empty() {
+            out.push_str(&format!("{} | {}\n", day.format("%Y-%m-%d"), count));
+        } else {
+            out.push_str(&format!("{} | {} {}\n", day.format("%Y-%m-%d"), bar, count));
+        }
+    }
+    out.push_str("```\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::SessionEntry;
+    use chrono::Utc;
+
+    fn task(name: &str, project: Option<&str>, pomodoros: u32, mins: u64, completed_on: Option<u32>) -> Task {
+        let mut t = Task::new(name.to_string(), project.map(str::to_string));
+        t.creation_date = Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap();
+        t.pomodoros = pomodoros;
+        t.time_spent = Duration::from_secs(mins * 60);
+        if let Some(day) = completed_on {
+            t.completed = true;
+            t.completion_date = Some(Utc.with_ymd_and_hms(2024, 3, day, 12, 0, 0).unwrap());
+        }
+        t
+    }
+
+    #[test]
+    fn report_is_deterministic_for_fixed_state() {
+        let app = App {
+            pomodoros_completed_total: 9,
+            tasks: vec![
+                task("Inbox zero", None, 1, 20, Some(9)),
+                task("Write | report", Some("work"), 5, 125, Some(10)),
+                task("Plan sprint", Some("work"), 3, 75, None),
+            ],
+            ..App::default()
+        };
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 18, 30, 0).unwrap();
+        let expected = "\
+# Pomodorust Report
+
+Generated on 2024-03-10 18:30
+
+## Summary
+
+| Metric | Value |
+| ------ | ----- |
+| Total sessions | 9 |
+| Total focus time | 3h 40m |
+| Current streak | 2 days |
+
+## Tasks
+
+| Task | Project | Status | Pomodoros | Focus time |
+| ---- | ------- | ------ | --------: | ---------: |
+| Write \\| report | @work | Completed | 5 | 2h 5m |
+| Plan sprint | @work | Active | 3 | 1h 15m |
+| Inbox zero |  | Completed | 1 | 0h 20m |
+
+## Last 7 days
+
+```text
+2024-03-04 | 0
+2024-03-05 | 0
+2024-03-06 | 0
+2024-03-07 | 0
+2024-03-08 | 0
+2024-03-09 | █ 1
+2024-03-10 | █████ 5
+```
+";
+        assert_eq!(render_report(&app, &now), expected);
+    }
+
+    #[test]
+    fn focus_efficiency_compares_pomodoro_time_with_time_spent() {
+        let settings = Settings::default();
+        // 3 × 25 min of pomodoros over 100 min tracked
+        let tasks = vec![task("Write", None, 2, 60, None), task("Review", None, 1, 40, Some(2))];
+        assert!((focus_efficiency(&tasks, &settings) - 0.75).abs() < 1e-9);
+        assert_eq!(focus_efficiency(&[task("Idle", None, 0, 0, None)], &settings), 0.0);
+        assert_eq!(focus_efficiency(&[], &settings), 0.0);
+    }
+
+    #[test]
+    fn daily_log_lists_todays_sessions_with_notes() {
+        let mut fix = task("Fix login bug", None, 2, 50, None);
+        fix.notes = Some("Token expiry off by one\n\nCheck the refresh path".to_string());
+        let at = |h, m| Utc.with_ymd_and_hms(2024, 3, 10, h, m, 0).unwrap();
+        let session = |mode, start: DateTime<Utc>, end: DateTime<Utc>, task_ids: &[u64]| SessionEntry {
+            mode,
+            started: start,
+            finished: end,
+            task_ids: task_ids.to_vec(),
+        };
+        let id = fix.id;
+        let app = App {
+            tasks: vec![fix],
+            session_log: vec![
+                session(Mode::Pomodoro, at(8, 0) - chrono::Duration::days(1), at(8, 25) - chrono::Duration::days(1), &[id]),
+                session(Mode::Pomodoro, at(9, 0), at(9, 25), &[id]),
+                session(Mode::ShortBreak, at(9, 30), at(9, 35), &[]),
+                session(Mode::Pomodoro, at(9, 35), at(10, 0), &[id]),
+            ],
+            ..App::default()
+        };
+        let mut out = Vec::new();
+        write_daily_log(&app, &at(18, 0), &mut out).unwrap();
+        let expected = "\
+Work log for 2024-03-10
+
+[09:00–09:25] Fix login bug (1 ●)
+    Token expiry off by one
+    Check the refresh path
+[09:30–09:35] Short Break
+[09:35–10:00] Fix login bug (2 ●)
+
+2 pomodoros, 0h 50m focused
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+}
@@ -0,0 +1,78 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::app::command::Command;
+use crate::settings::Theme;
+
+const PALETTE_WIDTH: u16 = 48;
+const MAX_VISIBLE_COMMANDS: u16 = 10;
+
+pub fn draw_command_palette(
+    frame: &mut Frame,
+    query: &str,
+    matches: &[Command],
+    selected: usize,
+    theme: &Theme,
+) {
+    let screen = frame.area();
+    let list_height = (matches.len() as u16).clamp(1, MAX_VISIBLE_COMMANDS) + 2;
+    let width = PALETTE_WIDTH.min(screen.width);
+    let height = (3 + list_height).min(screen.height);
+    // Anchored near the top like most editors' palettes, horizontally centered
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height).min(2),
+        width,
+        height,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    frame.render_widget(Clear, area);
+
+    let input_block = Block::default()
+        .title(" Command Palette ")
+        .borders(Borders::ALL)
+        .border_type(theme.border_type)
+        .style(Style::default().fg(theme.accent_color).bg(theme.base_bg));
+    let inner = input_block.inner(chunks[0]);
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.help_text_fg)),
+            Span::styled(query, Style::default().fg(theme.base_fg)),
+        ]))
+        .block(input_block),
+        chunks[0],
+    );
+    frame.set_cursor_position((inner.x + 2 + query.chars().count() as u16, inner.y));
+
+    let list_block = Block::default()
+        .title_bottom(Line::from(" [↑/↓] Select | [Enter] Run | [Esc] Close ").centered())
+        .borders(Borders::ALL)
+        .border_type(theme.border_type)
+        .style(Style::default().fg(theme.accent_color).bg(theme.base_bg));
+    if matches.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No matching commands")
+                .style(Style::default().fg(theme.help_text_fg))
+                .alignment(Alignment::Center)
+                .block(list_block),
+            chunks[1],
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = matches.iter().map(|c| ListItem::new(c.label())).collect();
+    let mut list_state = ListState::default();
+    list_state.select(Some(selected.min(matches.len() - 1)));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(list_block)
+            .style(Style::default().fg(theme.base_fg))
+            .highlight_style(Style::default().bg(theme.highlight_bg).add_modifier(Modifier::BOLD))
+            .highlight_symbol("> "),
+        chunks[1],
+        &mut list_state,
+    );
+}
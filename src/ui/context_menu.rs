@@ -0,0 +1,41 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::app::ContextOption;
+use crate::settings::Theme;
+
+pub fn draw_context_menu(
+    frame: &mut Frame,
+    options: &[ContextOption],
+    selected: usize,
+    anchor: (u16, u16),
+    theme: &Theme,
+) {
+    let screen = frame.area();
+    let label_width = options.iter().map(|o| o.label().len()).max().unwrap_or(0) as u16;
+    // Labels + highlight symbol + borders, clamped so the popup never leaves the terminal
+    let width = (label_width + 6).min(screen.width);
+    let height = (options.len() as u16 + 2).min(screen.height);
+    let x = anchor.0.min(screen.x + screen.width - width);
+    let y = anchor.1.min(screen.y + screen.height - height);
+    let area = Rect::new(x, y, width, height);
+
+    let items: Vec<ListItem> = options.iter().map(|o| ListItem::new(o.label())).collect();
+    let mut list_state = ListState::default();
+    list_state.select(Some(selected));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(theme.border_type)
+                    .style(Style::default().fg(theme.accent_color).bg(theme.base_bg)),
+            )
+            .style(Style::default().fg(theme.base_fg))
+            .highlight_style(Style::default().bg(theme.highlight_bg).add_modifier(Modifier::BOLD))
+            .highlight_symbol("> "),
+        area,
+        &mut list_state,
+    );
+}
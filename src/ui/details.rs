@@ -1,10 +1,12 @@
 use chrono::prelude::*;
 use ratatui::{prelude::*, widgets::*};
 
-use crate::app::{App, UiState};
+use crate::app::{App, InputMode, UiState};
 use crate::settings::Theme;
 
 const WIDE_THRESHOLD: u16 = 90;
+/// Characters of the first note line shown under the Notes row.
+const NOTES_PREVIEW_CHARS: usize = 50;
 
 pub fn draw_task_details(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme) {
     let wide = frame.area().width >= WIDE_THRESHOLD;
@@ -27,45 +29,59 @@ pub fn draw_task_details(frame: &mut Frame, app: &App, ui: &UiState, theme: &The
         chunks[0],
     );
 
-    // Help bar
-    frame.render_widget(
-        Paragraph::new(" [Esc / Enter] Back | [Shift+E] Edit notes | [q]uit ")
-            .block(
-                Block::default()
-                    .title("Controls")
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .style(Style::default().fg(theme.help_text_fg)),
-            )
-            .alignment(Alignment::Center),
-        chunks[2],
-    );
+    // Help bar; replaced by the subtask or link input while adding one
+    if matches!(ui.input_mode, InputMode::EditingSubtask | InputMode::EditingLink) {
+        let title = if matches!(ui.input_mode, InputMode::EditingLink) {
+            "New link ([Enter] Add | [Esc] Cancel)"
+        } else {
+            "New subtask ([Enter] Add | [Esc] Cancel)"
+        };
+        let input_block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .style(Style::default().fg(theme.paused_fg));
+        let inner = input_block.inner(chunks[2]);
+        frame.render_widget(Paragraph::new(ui.current_input.as_str()).block(input_block), chunks[2]);
+        frame.set_cursor_position((inner.x + ui.current_input.chars().count() as u16, inner.y));
+    } else {
+        let help_text = if wide {
+            " [Esc / Enter] Back | [a]dd subtask | [Space] Toggle subtask | [↑/↓] Nav | [r]epeat | [l]ink | [+/-] Estimate | [Shift+E] Edit notes | [q]uit "
+        } else {
+            " [Esc] [a] [Spc] [↑/↓] [r] [l] [+/-] [E] [q] "
+        };
+        frame.render_widget(
+            Paragraph::new(help_text)
+                .block(
+                    Block::default()
+                        .title("Controls")
+                        .borders(Borders::ALL)
+                        .border_type(theme.border_type)
+                        .style(Style::default().fg(theme.help_text_fg)),
+                )
+                .alignment(Alignment::Center),
+            chunks[2],
+        );
+    }
 
     let body = chunks[1];
 
-    let Some(selected) = ui.completed_task_list_state else {
+    if ui.details_task.is_none() {
         frame.render_widget(
             Paragraph::new("No task selected.")
                 .alignment(Alignment::Center)
-                .block(Block::default().borders(Borders::ALL).border_type(BorderType::Rounded)
+                .block(Block::default().borders(Borders::ALL).border_type(theme.border_type)
                     .style(Style::default().fg(theme.base_fg).bg(theme.base_bg))),
             body,
         );
         return;
-    };
-
-    let filter = ui.filter_input.to_lowercase();
-    let completed: Vec<_> = app.tasks.iter()
-        .filter(|t| t.completed && (filter.is_empty()
-            || t.name.to_lowercase().contains(&filter)
-            || t.notes.as_deref().map_or(false, |n| n.to_lowercase().contains(&filter))))
-        .collect();
+    }
 
-    let Some(task) = completed.get(selected) else {
+    let Some(task) = ui.details_task.and_then(|i| app.tasks.get(i)) else {
         frame.render_widget(
             Paragraph::new("Error: task not found.")
                 .alignment(Alignment::Center)
-                .block(Block::default().borders(Borders::ALL).border_type(BorderType::Rounded)
+                .block(Block::default().borders(Borders::ALL).border_type(theme.border_type)
                     .style(Style::default().fg(theme.base_fg).bg(theme.base_bg))),
             body,
         );
@@ -78,6 +94,10 @@ pub fn draw_task_details(frame: &mut Frame, app: &App, ui: &UiState, theme: &The
         || "N/A".to_string(),
         |dt| DateTime::<Local>::from(dt).format("%Y-%m-%d %H:%M:%S").to_string(),
     );
+    let started_str = task.start_date.map_or_else(
+        || "Not yet".to_string(),
+        |dt| DateTime::<Local>::from(dt).format("%Y-%m-%d %H:%M").to_string(),
+    );
     let time_spent_fmt = format!(
         "{}h {}m {}s",
         task.time_spent.as_secs() / 3600,
@@ -91,20 +111,81 @@ pub fn draw_task_details(frame: &mut Frame, app: &App, ui: &UiState, theme: &The
 
     let mut rows = vec![
         Row::new(vec![Cell::from("Task"), Cell::from(task.name.clone())]),
-        Row::new(vec![Cell::from("Status"), Cell::from("✓ Completed")])
-            .style(Style::default().fg(theme.running_fg)),
+        if task.completed {
+            Row::new(vec![Cell::from("Status"), Cell::from("✓ Completed")])
+                .style(Style::default().fg(theme.running_fg))
+        } else {
+            Row::new(vec![Cell::from("Status"), Cell::from("○ Active")])
+                .style(Style::default().fg(theme.paused_fg))
+        },
         Row::new(vec![Cell::from("Created"), Cell::from(created.format("%Y-%m-%d %H:%M").to_string())]),
+        Row::new(vec![Cell::from("First worked on"), Cell::from(started_str)]),
         Row::new(vec![Cell::from("Completed"), Cell::from(completed_str)]),
         Row::new(vec![Cell::from("Time to Complete"), Cell::from(time_to_complete)]),
-        Row::new(vec![Cell::from("Time Focused"), Cell::from(time_spent_fmt)]),
-        Row::new(vec![Cell::from("Pomodoros"), Cell::from(format!("{} ●", task.pomodoros))]),
     ];
+    if let Some(span) = task.time_from_start_to_completion() {
+        let mins = span.as_secs() / 60;
+        rows.push(Row::new(vec![
+            Cell::from("Start to Finish"),
+            Cell::from(format!("{}d {}h {}m", mins / 1440, mins / 60 % 24, mins % 60)),
+        ]));
+    }
+    rows.push(Row::new(vec![Cell::from("Time Focused"), Cell::from(time_spent_fmt)]));
+    rows.push(Row::new(vec![Cell::from("Pomodoros"), Cell::from(match task.estimated_pomodoros {
+        Some(estimate) => format!("{} / {} ●", task.pomodoros, estimate),
+        None => format!("{} ●", task.pomodoros),
+    })]));
     if let Some(proj) = &task.project {
         rows.push(Row::new(vec![
             Cell::from("Project"),
             Cell::from(format!("@{}", proj)).style(Style::default().fg(theme.accent_color)),
         ]));
     }
+    if let Some(id) = &task.external_id {
+        rows.push(Row::new(vec![
+            Cell::from("External ID"),
+            Cell::from(Line::from(vec![
+                Span::styled(id.clone(), Style::default().fg(theme.accent_color)),
+                Span::styled("  [o] open", Style::default().fg(theme.help_text_fg)),
+            ])),
+        ]));
+    }
+    if let Some(rule) = task.recurrence {
+        rows.push(Row::new(vec![Cell::from("Repeats"), Cell::from(format!("↻ {}", rule))]));
+    }
+    if let Some(first_line) = task.notes.as_deref().and_then(|n| n.lines().find(|l| !l.trim().is_empty())) {
+        rows.push(Row::new(vec![
+            Cell::from("Notes"),
+            Cell::from(format!("{} words / {} chars", task.notes_word_count(), task.notes_char_count())),
+        ]));
+        let mut preview: String = first_line.trim().chars().take(NOTES_PREVIEW_CHARS).collect();
+        if first_line.trim().chars().count() > NOTES_PREVIEW_CHARS {
+            preview.push('…');
+        }
+        rows.push(Row::new(vec![Cell::from(""), Cell::from(preview)]).style(Style::default().fg(theme.help_text_fg)));
+    }
+    if !task.subtasks.is_empty() {
+        let done = task.subtasks.iter().filter(|s| s.completed).count();
+        rows.push(Row::new(vec![
+            Cell::from("Subtasks"),
+            Cell::from(format!("{}/{}", done, task.subtasks.len())),
+        ]));
+        for (i, sub) in task.subtasks.iter().enumerate() {
+            let checkbox = if sub.completed { "[x]" } else { "[ ]" };
+            let mut style = if sub.completed {
+                Style::default().fg(theme.help_text_fg)
+            } else {
+                Style::default().fg(theme.base_fg)
+            };
+            if ui.subtask_selection == Some(i) {
+                style = style.bg(theme.highlight_bg).add_modifier(Modifier::BOLD);
+            }
+            rows.push(Row::new(vec![
+                Cell::from(""),
+                Cell::from(format!("  {} {}", checkbox, sub.name)),
+            ]).style(style));
+        }
+    }
 
     let row_count = rows.len();
     let stats_table = Table::new(rows, [Constraint::Length(18), Constraint::Min(16)])
@@ -113,7 +194,7 @@ pub fn draw_task_details(frame: &mut Frame, app: &App, ui: &UiState, theme: &The
             Block::default()
                 .title("Statistics")
                 .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
+                .border_type(theme.border_type)
                 .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
         )
         .column_spacing(2)
@@ -133,7 +214,7 @@ pub fn draw_task_details(frame: &mut Frame, app: &App, ui: &UiState, theme: &The
                 Block::default()
                     .title("Notes")
                     .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
+                    .border_type(theme.border_type)
                     .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
             )
     } else {
@@ -143,7 +224,7 @@ pub fn draw_task_details(frame: &mut Frame, app: &App, ui: &UiState, theme: &The
                 Block::default()
                     .title("Notes")
                     .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
+                    .border_type(theme.border_type)
                     .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
             )
     };
@@ -154,7 +235,9 @@ pub fn draw_task_details(frame: &mut Frame, app: &App, ui: &UiState, theme: &The
             .constraints([Constraint::Percentage(42), Constraint::Percentage(58)])
             .split(body);
         frame.render_widget(stats_table, cols[0]);
-        frame.render_widget(notes_widget, cols[1]);
+        let (notes_area, links_area) = split_off_links(cols[1], task.links.len());
+        frame.render_widget(notes_widget, notes_area);
+        draw_links(frame, &task.links, links_area, ui.hyperlinks, theme);
     } else {
         // Narrow: stats fixed height, notes takes the rest
         let rows_needed = row_count as u16 + 4; // data rows + header + borders + padding
@@ -163,6 +246,57 @@ pub fn draw_task_details(frame: &mut Frame, app: &App, ui: &UiState, theme: &The
             .constraints([Constraint::Length(rows_needed), Constraint::Min(0)])
             .split(body);
         frame.render_widget(stats_table, vert[0]);
-        frame.render_widget(notes_widget, vert[1]);
+        let (notes_area, links_area) = split_off_links(vert[1], task.links.len());
+        frame.render_widget(notes_widget, notes_area);
+        draw_links(frame, &task.links, links_area, ui.hyperlinks, theme);
+    }
+}
+
+/// Carves a "Links" box off the bottom of the notes area, sized to fit every
+/// link when there is room. The box is empty when the task has no links.
+fn split_off_links(area: Rect, link_count: usize) -> (Rect, Rect) {
+    if link_count == 0 {
+        return (area, Rect::new(area.x, area.bottom(), area.width, 0));
+    }
+    let height = (link_count as u16 + 2).min(area.height / 2);
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(height)])
+        .split(area);
+    (parts[0], parts[1])
+}
+
+fn draw_links(frame: &mut Frame, links: &[String], area: Rect, hyperlinks: bool, theme: &Theme) {
+    if links.is_empty() || area.height == 0 {
+        return;
+    }
+    let block = Block::default()
+        .title("Links")
+        .borders(Borders::ALL)
+        .border_type(theme.border_type)
+        .style(Style::default().fg(theme.base_fg).bg(theme.base_bg));
+    let inner = block.inner(area);
+    let link_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::UNDERLINED);
+    let lines: Vec<Line> = links.iter().map(|url| Line::from(Span::styled(url.as_str(), link_style))).collect();
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    if hyperlinks {
+        for (i, url) in links.iter().enumerate().take(inner.height as usize) {
+            mark_hyperlink(frame.buffer_mut(), url, Rect::new(inner.x, inner.y + i as u16, inner.width, 1));
+        }
+    }
+}
+
+/// Wraps the rendered text in OSC 8 escapes two cells at a time. ratatui
+/// measures an escaped symbol as two columns wide, so longer chunks would
+/// throw off its diffing (ratatui#902).
+fn mark_hyperlink(buf: &mut Buffer, url: &str, row: Rect) {
+    let chars: Vec<char> = url.chars().take(row.width as usize).collect();
+    for (i, pair) in chars.chunks(2).enumerate() {
+        let text: String = pair.iter().collect();
+        let symbol = format!("\x1b]8;;{}\x07{}\x1b]8;;\x07", url, text);
+        if let Some(cell) = buf.cell_mut((row.x + i as u16 * 2, row.y)) {
+            cell.set_symbol(&symbol);
+        }
     }
 }
@@ -0,0 +1,95 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::app::Task;
+use crate::settings::Theme;
+
+const SEARCH_WIDTH: u16 = 60;
+const MAX_VISIBLE_RESULTS: u16 = 12;
+
+pub fn draw_global_search(
+    frame: &mut Frame,
+    query: &str,
+    results: &[(usize, &Task)],
+    selected: usize,
+    theme: &Theme,
+) {
+    let screen = frame.area();
+    let list_height = (results.len() as u16).clamp(1, MAX_VISIBLE_RESULTS) + 2;
+    let width = SEARCH_WIDTH.min(screen.width);
+    let height = (3 + list_height).min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height).min(2),
+        width,
+        height,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    frame.render_widget(Clear, area);
+
+    let input_block = Block::default()
+        .title(" Search Tasks ")
+        .borders(Borders::ALL)
+        .border_type(theme.border_type)
+        .style(Style::default().fg(theme.accent_color).bg(theme.base_bg));
+    let inner = input_block.inner(chunks[0]);
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("/ ", Style::default().fg(theme.help_text_fg)),
+            Span::styled(query, Style::default().fg(theme.base_fg)),
+        ]))
+        .block(input_block),
+        chunks[0],
+    );
+    frame.set_cursor_position((inner.x + 2 + query.chars().count() as u16, inner.y));
+
+    let list_block = Block::default()
+        .title_bottom(Line::from(" [↑/↓] Select | [Enter] Go to task | [Esc] Close ").centered())
+        .borders(Borders::ALL)
+        .border_type(theme.border_type)
+        .style(Style::default().fg(theme.accent_color).bg(theme.base_bg));
+    if results.is_empty() {
+        let hint = if query.trim().is_empty() {
+            "Type to search names, notes and @projects"
+        } else {
+            "No matching tasks"
+        };
+        frame.render_widget(
+            Paragraph::new(hint)
+                .style(Style::default().fg(theme.help_text_fg))
+                .alignment(Alignment::Center)
+                .block(list_block),
+            chunks[1],
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = results
+        .iter()
+        .map(|(_, task)| {
+            let (checkbox, style) = if task.completed {
+                ("[✓] ", Style::default().fg(theme.help_text_fg))
+            } else {
+                ("[ ] ", Style::default().fg(theme.base_fg))
+            };
+            let mut spans = vec![Span::styled(format!("{}{}", checkbox, task.name), style)];
+            if let Some(proj) = &task.project {
+                spans.push(Span::styled(format!(" @{}", proj), Style::default().fg(theme.accent_color)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    list_state.select(Some(selected.min(results.len() - 1)));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(list_block)
+            .highlight_style(Style::default().bg(theme.highlight_bg).add_modifier(Modifier::BOLD))
+            .highlight_symbol("> "),
+        chunks[1],
+        &mut list_state,
+    );
+}
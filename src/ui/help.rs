@@ -0,0 +1,142 @@
+use ratatui::{prelude::*, widgets::*};
+
+use super::centered_rect;
+use crate::settings::Theme;
+
+/// Every keybinding, grouped by where it applies. Keep in sync with the handlers in `main.rs`.
+pub const KEYBINDINGS: &[(&str, &[(&str, &str)])] = &[
+    ("Global", &[
+        ("o", "Open settings"),
+        ("?", "Show this help"),
+        ("Shift+Tab", "Previous view"),
+        ("Ctrl+T", "Jump to timer"),
+        ("Ctrl+P", "Command palette"),
+        ("Ctrl+F", "Search all tasks"),
+        ("Ctrl+L", "Redraw the screen"),
+        ("Ctrl+R", "Reload config.toml"),
+        ("Ctrl+Z", "Undo config reload"),
+        ("Ctrl+S", "Save now"),
+        ("Ctrl+E", "Edit state file in $EDITOR"),
+        ("Ctrl+V", "Paste into a task name or link input"),
+        ("Ctrl+W / Ctrl+U", "Task name input: delete word / clear line"),
+        ("Ctrl+Z", "Task name input: undo last delete"),
+        ("q / Ctrl+C", "Quit (Ctrl+C copies in the task list)"),
+    ]),
+    ("Timer", &[
+        ("Space", "Start / pause"),
+        ("r", "Reset timer"),
+        ("n", "New task (becomes active)"),
+        ("Ctrl+N", "Quick start: name a task and start"),
+        ("N", "Skip to next segment"),
+        ("Ctrl+K", "Finish session now (counts it)"),
+        ("Ctrl+B", "Break now, resume pomodoro after"),
+        (", / .", "Pomodoro length -/+ 1 min"),
+        ("p / s / l", "Switch to Pomodoro / Short / Long break"),
+        ("z", "Zen mode (any key exits)"),
+        ("Tab", "Task list"),
+    ]),
+    ("Task List", &[
+        ("↑/k ↓/j", "Navigate"),
+        ("PgUp / PgDn", "Page up / down"),
+        ("Shift+↑/K Shift+↓/J", "Reorder task"),
+        ("n", "New task (@project supported)"),
+        ("e", "Rename task"),
+        ("Shift+E", "Edit notes"),
+        ("i", "Task details"),
+        ("/", "Filter"),
+        ("Enter", "Toggle complete"),
+        ("d / Del", "Delete task"),
+        ("a", "Toggle parallel task (parallel mode)"),
+        ("Ctrl+D", "Defer task for a day / undefer"),
+        ("F", "Show / hide deferred tasks"),
+        ("P", "Projects (assign / n new / c color / d delete)"),
+        ("-", "Insert / remove separator below task"),
+        ("b", "Set time budget (minutes)"),
+        ("Ctrl+X", "Mark done without a pomodoro"),
+        ("Ctrl+Enter", "Complete all active tasks (asks first)"),
+        ("Ctrl+G", "Go to task by number"),
+        ("Ctrl+R", "Reset all task timers (asks first)"),
+        ("Ctrl+Z", "Undo timer reset / complete all"),
+        ("Ctrl+I / I", "Show / hide details under the selected task"),
+        ("S", "Sort tasks (name, priority, date, time, pomodoros)"),
+        ("Ctrl+C", "Copy active tasks to the clipboard"),
+        ("c", "Cycle task color"),
+        ("Space", "Go to timer"),
+        ("t", "Toggle mini timer"),
+        ("Click / Right click", "Select / context menu"),
+        ("Tab", "Statistics"),
+    ]),
+    ("Statistics", &[
+        ("↑/k ↓/j", "Navigate"),
+        ("PgUp / PgDn", "Page up / down"),
+        ("/", "Filter"),
+        ("Enter", "Task details"),
+        ("Ctrl+A", "Select all completed (d deletes all)"),
+        ("Esc / Ctrl+W", "Clear selection"),
+        ("t", "Toggle mini timer"),
+        ("f", "Period: all time / today / week / month"),
+        ("F", "Custom date range"),
+        ("Ctrl+H / H", "Hide / show tasks with 0 pomodoros"),
+        ("Ctrl+U", "Undo last delete"),
+        ("d / Del", "Delete task"),
+        ("Tab", "Timer"),
+    ]),
+    ("Task Details", &[
+        ("a", "Add subtask"),
+        ("↑/k ↓/j", "Select subtask"),
+        ("Space", "Toggle subtask"),
+        ("r", "Cycle repeat rule"),
+        ("l", "Add link"),
+        ("o", "Open external ID"),
+        ("+ / -", "Raise / lower pomodoro estimate"),
+        ("Shift+E", "Edit notes"),
+        ("Esc / Enter", "Back"),
+    ]),
+    ("Settings", &[
+        ("↑/k ↓/j", "Select setting"),
+        ("←/h →/l", "Change value"),
+        ("O", "Open data folder"),
+        ("Enter on Edit Labels", "Manage labels: [n]ew, [d]elete"),
+        ("Ctrl+D / Enter on last row", "Restore defaults (asks first)"),
+        ("S", "Save timer settings as a preset"),
+        ("P", "Presets: [Enter] apply, [n]ew, [d]elete"),
+        ("Tab", "Close"),
+    ]),
+];
+
+pub fn draw_help_overlay(frame: &mut Frame, scroll: u16, theme: &Theme) {
+    let area = centered_rect(70, 80, frame.area());
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (section, bindings) in KEYBINDINGS {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            *section,
+            Style::default().fg(theme.accent_color).add_modifier(Modifier::BOLD),
+        )));
+        for (keys, action) in bindings.iter() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<22}", keys), Style::default().fg(theme.paused_fg)),
+                Span::styled(*action, Style::default().fg(theme.base_fg)),
+            ]));
+        }
+    }
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .scroll((scroll, 0))
+            .block(
+                Block::default()
+                    .title(" ? KEYBINDINGS ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::from(" [↑/↓] Scroll | any other key closes ").centered())
+                    .borders(Borders::ALL)
+                    .border_type(theme.border_type)
+                    .style(Style::default().fg(theme.accent_color).bg(theme.base_bg)),
+            ),
+        area,
+    );
+}
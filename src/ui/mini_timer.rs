@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use ratatui::{prelude::*, widgets::*};
+
+use crate::app::{App, Mode, TimerState};
+use crate::settings::Theme;
+
+/// Three-row countdown bar drawn over the top of the current view.
+pub fn draw_mini_timer(frame: &mut Frame, app: &App, theme: &Theme) {
+    let mode_color = match app.mode {
+        Mode::Pomodoro => theme.pomodoro_color,
+        Mode::ShortBreak => theme.short_break_color,
+        Mode::LongBreak => theme.long_break_color,
+    };
+    let icon = match app.state {
+        TimerState::Running => "▶",
+        TimerState::Paused => "⏸",
+    };
+    let time_text = if app.mode.duration(&app.settings) >= Duration::from_secs(3600) {
+        app.time_remaining_formatted_hms()
+    } else {
+        app.time_remaining_formatted()
+    };
+    let area = Rect { height: 3.min(frame.area().height), ..frame.area() };
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(format!("{} {} {}", icon, time_text, app.mode.label(&app.settings)))
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(mode_color).add_modifier(Modifier::BOLD))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(theme.border_type)
+                    .border_style(Style::default().fg(mode_color))
+                    .style(Style::default().bg(theme.base_bg)),
+            ),
+        area,
+    );
+}
@@ -1,19 +1,70 @@
+pub mod command_palette;
+pub mod context_menu;
 pub mod details;
+pub mod global_search;
+pub mod help;
+pub mod labels;
+pub mod mini_timer;
 pub mod notes_modal;
+pub mod presets;
+pub mod progress_arc;
+pub mod projects;
+pub mod quick_start;
 pub mod settings;
+pub mod sort_menu;
 pub mod statistics;
 pub mod task_list;
 pub mod timer;
+pub mod toast;
 
+pub use command_palette::draw_command_palette;
+pub use context_menu::draw_context_menu;
 pub use details::draw_task_details;
+pub use global_search::draw_global_search;
+pub use help::draw_help_overlay;
+pub use labels::draw_label_manager;
+pub use mini_timer::draw_mini_timer;
 pub use notes_modal::draw_notes_modal;
+pub use presets::draw_preset_picker;
+pub use projects::draw_project_manager;
+pub use quick_start::draw_quick_start_modal;
 pub use settings::draw_settings;
+pub use sort_menu::draw_sort_menu;
 pub use statistics::draw_statistics;
 pub use task_list::draw_task_list;
 pub use timer::draw_timer;
+pub use toast::draw_toast;
 
 use ratatui::prelude::*;
 
+use crate::app::TaskColor;
+use crate::settings::{Settings, Theme};
+
+pub(super) fn task_color(color: TaskColor) -> Color {
+    match color {
+        TaskColor::Red => Color::LightRed,
+        TaskColor::Green => Color::LightGreen,
+        TaskColor::Blue => Color::LightBlue,
+        TaskColor::Yellow => Color::LightYellow,
+        TaskColor::Magenta => Color::LightMagenta,
+        TaskColor::Cyan => Color::LightCyan,
+    }
+}
+
+/// Badge color for a label: theme colors taken in turn by its place in the list.
+pub(super) fn label_color(label: &str, settings: &Settings, theme: &Theme) -> Color {
+    let palette = [
+        theme.pomodoro_color,
+        theme.short_break_color,
+        theme.long_break_color,
+        theme.accent_color,
+        theme.running_fg,
+        theme.paused_fg,
+    ];
+    let position = settings.available_labels.iter().position(|l| l == label).unwrap_or(0);
+    palette[position % palette.len()]
+}
+
 pub(super) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -12,7 +12,7 @@ pub fn draw_notes_modal(frame: &mut Frame, ui: &UiState, theme: &Theme) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
+        .border_type(theme.border_type)
         .title(" Notes — [Ctrl+S] Save  [Esc] Cancel ")
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(theme.accent_color).bg(theme.base_bg));
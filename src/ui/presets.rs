@@ -0,0 +1,76 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::app::{App, InputMode, UiState};
+use crate::settings::Theme;
+
+const POPUP_WIDTH: u16 = 48;
+const MAX_VISIBLE_ROWS: u16 = 12;
+
+/// `P` popup over Settings: saved timer presets, applied with `Enter`.
+pub fn draw_preset_picker(frame: &mut Frame, app: &App, ui: &UiState, selected: usize, theme: &Theme) {
+    let naming = matches!(ui.input_mode, InputMode::EditingPreset);
+    let presets = &app.settings.presets;
+    let screen = frame.area();
+    let list_height = (presets.len().max(1) as u16).min(MAX_VISIBLE_ROWS) + 2;
+    let width = POPUP_WIDTH.min(screen.width);
+    let height = (list_height + if naming { 3 } else { 0 }).min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(if naming { 3 } else { 0 })])
+        .split(area);
+    frame.render_widget(Clear, area);
+
+    let dim = Style::default().fg(theme.help_text_fg);
+    let items: Vec<ListItem> = if presets.is_empty() {
+        vec![ListItem::new(Span::styled("No presets yet. Press [n] to save one.", dim))]
+    } else {
+        presets
+            .iter()
+            .map(|preset| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(preset.name.clone(), Style::default().fg(theme.base_fg)),
+                    Span::styled(format!("  {}", preset.summary()), dim),
+                ]))
+            })
+            .collect()
+    };
+
+    let mut list_state = ListState::default();
+    list_state.select((!presets.is_empty()).then_some(selected.min(presets.len().saturating_sub(1))));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(
+                Block::default()
+                    .title(" Presets ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::from(" [Enter] Apply | [n]ew | [d]elete | [Esc] Back ").centered())
+                    .borders(Borders::ALL)
+                    .border_type(theme.border_type)
+                    .style(Style::default().fg(theme.accent_color).bg(theme.base_bg)),
+            )
+            .highlight_style(Style::default().bg(theme.highlight_bg).add_modifier(Modifier::BOLD))
+            .highlight_symbol("> "),
+        chunks[0],
+        &mut list_state,
+    );
+
+    if naming {
+        let block = Block::default()
+            .title(" Save current timer settings as ")
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .style(Style::default().fg(theme.paused_fg).bg(theme.base_bg));
+        let inner = block.inner(chunks[1]);
+        frame.render_widget(
+            Paragraph::new(Span::styled(ui.current_input.as_str(), Style::default().fg(theme.base_fg))).block(block),
+            chunks[1],
+        );
+        frame.set_cursor_position((inner.x + ui.current_input.chars().count() as u16, inner.y));
+    }
+}
@@ -0,0 +1,76 @@
+use ratatui::prelude::*;
+
+/// Horizontal radius of the ring in cells; terminal cells are roughly twice as
+/// tall as wide, so the vertical radius is about a third of this.
+pub const ARC_RADIUS_X: u16 = 16;
+pub const ARC_RADIUS_Y: u16 = 5;
+
+// Partial fills for the segment the progress boundary falls in
+const PARTIAL_BLOCKS: [&str; 8] = ["▏", "▎", "▍", "▌", "▋", "▊", "▉", "█"];
+const EMPTY_SEGMENT: &str = "·";
+
+/// Ring cells as (dx, dy) offsets from the center, clockwise from 12 o'clock.
+const ARC_CELLS: &[(i16, i16)] = &[
+    (0, -5), (1, -5), (2, -5), (3, -5), (4, -5), (5, -5), (6, -5), (7, -5),
+    (7, -4), (8, -4), (9, -4), (10, -4), (11, -4), (11, -3), (12, -3), (13, -3),
+    (14, -3), (14, -2), (15, -2), (15, -1), (16, -1), (16, 0), (16, 1), (15, 1),
+    (15, 2), (14, 2), (14, 3), (13, 3), (12, 3), (11, 3), (11, 4), (10, 4),
+    (9, 4), (8, 4), (7, 4), (7, 5), (6, 5), (5, 5), (4, 5), (3, 5),
+    (2, 5), (1, 5), (0, 5), (-1, 5), (-2, 5), (-3, 5), (-4, 5), (-5, 5),
+    (-6, 5), (-7, 5), (-7, 4), (-8, 4), (-9, 4), (-10, 4), (-11, 4), (-11, 3),
+    (-12, 3), (-13, 3), (-14, 3), (-14, 2), (-15, 2), (-15, 1), (-16, 1), (-16, 0),
+    (-16, -1), (-15, -1), (-15, -2), (-14, -2), (-14, -3), (-13, -3), (-12, -3), (-11, -3),
+    (-11, -4), (-10, -4), (-9, -4), (-8, -4), (-7, -4), (-7, -5), (-6, -5), (-5, -5),
+    (-4, -5), (-3, -5), (-2, -5), (-1, -5),
+];
+
+/// Smallest area that fits the whole ring.
+pub fn arc_size() -> (u16, u16) {
+    (ARC_RADIUS_X * 2 + 1, ARC_RADIUS_Y * 2 + 1)
+}
+
+/// Draws the ring centered in `area`, filling `ratio` (0.0..=1.0) of it
+/// clockwise with `style`. Does nothing if `area` is too small for the ring.
+pub fn draw_progress_arc(frame: &mut Frame, area: Rect, ratio: f64, style: Style) {
+    let (width, height) = arc_size();
+    if area.width < width || area.height < height {
+        return;
+    }
+    let cx = (area.x + area.width / 2) as i16;
+    let cy = (area.y + area.height / 2) as i16;
+    let filled = ratio.clamp(0.0, 1.0) * ARC_CELLS.len() as f64;
+    let full_segments = filled.floor() as usize;
+    let partial = ((filled - filled.floor()) * PARTIAL_BLOCKS.len() as f64) as usize;
+    let empty_style = Style::default().add_modifier(Modifier::DIM);
+
+    let buf = frame.buffer_mut();
+    for (i, &(dx, dy)) in ARC_CELLS.iter().enumerate() {
+        let (symbol, cell_style) = if i < full_segments {
+            ("█", style)
+        } else if i == full_segments && partial > 0 {
+            (PARTIAL_BLOCKS[partial - 1], style)
+        } else {
+            (EMPTY_SEGMENT, empty_style)
+        };
+        let position = Position::new((cx + dx) as u16, (cy + dy) as u16);
+        if let Some(cell) = buf.cell_mut(position) {
+            cell.set_symbol(symbol).set_style(cell_style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_is_closed_and_fits_its_bounds() {
+        for (a, b) in ARC_CELLS.iter().zip(ARC_CELLS.iter().cycle().skip(1)) {
+            assert!((a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1, "gap between {:?} and {:?}", a, b);
+        }
+        assert!(ARC_CELLS.iter().all(|&(dx, dy)| {
+            dx.unsigned_abs() <= ARC_RADIUS_X && dy.unsigned_abs() <= ARC_RADIUS_Y
+        }));
+        assert_eq!(ARC_CELLS[0], (0, -(ARC_RADIUS_Y as i16)));
+    }
+}
@@ -0,0 +1,81 @@
+use ratatui::{prelude::*, widgets::*};
+
+use super::task_color;
+use crate::app::{App, InputMode, UiState};
+use crate::settings::Theme;
+
+const POPUP_WIDTH: u16 = 50;
+const MAX_VISIBLE_ROWS: u16 = 12;
+
+/// Project manager / picker over the task list. Row 0 clears the task's project.
+pub fn draw_project_manager(frame: &mut Frame, app: &App, ui: &UiState, selected: usize, theme: &Theme) {
+    let naming = matches!(ui.input_mode, InputMode::EditingProject);
+    let screen = frame.area();
+    let list_height = (app.projects.len() as u16 + 1).min(MAX_VISIBLE_ROWS) + 2;
+    let width = POPUP_WIDTH.min(screen.width);
+    let height = (list_height + if naming { 3 } else { 0 }).min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(if naming { 3 } else { 0 })])
+        .split(area);
+    frame.render_widget(Clear, area);
+
+    let current = app.active_task_index
+        .and_then(|i| app.tasks.get(i))
+        .and_then(|t| t.project.as_deref());
+    let dim = Style::default().fg(theme.help_text_fg);
+    let mut items = vec![ListItem::new(Line::from(vec![
+        Span::styled(if current.is_none() { "✓ " } else { "  " }, dim),
+        Span::styled("(no project)", dim.add_modifier(Modifier::ITALIC)),
+    ]))];
+    for project in &app.projects {
+        let tasks = app.tasks.iter()
+            .filter(|t| !t.completed && t.project.as_deref() == Some(project.name.as_str()))
+            .count();
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(if current == Some(project.name.as_str()) { "✓ " } else { "  " }, dim),
+            Span::styled("● ", Style::default().fg(task_color(project.color))),
+            Span::styled(format!("@{}", project.name), Style::default().fg(theme.base_fg)),
+            Span::styled(format!("  {} open", tasks), dim),
+        ])));
+    }
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(selected.min(app.projects.len())));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(
+                Block::default()
+                    .title(" Projects ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::from(" [Enter] Assign | [n]ew | [c]olor | [d]elete | [Esc] Close ").centered())
+                    .borders(Borders::ALL)
+                    .border_type(theme.border_type)
+                    .style(Style::default().fg(theme.accent_color).bg(theme.base_bg)),
+            )
+            .highlight_style(Style::default().bg(theme.highlight_bg).add_modifier(Modifier::BOLD))
+            .highlight_symbol("> "),
+        chunks[0],
+        &mut list_state,
+    );
+
+    if naming {
+        let block = Block::default()
+            .title(" New project ")
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .style(Style::default().fg(theme.paused_fg).bg(theme.base_bg));
+        let inner = block.inner(chunks[1]);
+        frame.render_widget(
+            Paragraph::new(Span::styled(ui.current_input.as_str(), Style::default().fg(theme.base_fg))).block(block),
+            chunks[1],
+        );
+        frame.set_cursor_position((inner.x + ui.current_input.chars().count() as u16, inner.y));
+    }
+}
@@ -0,0 +1,35 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::app::UiState;
+use crate::settings::Theme;
+
+const MODAL_WIDTH: u16 = 50;
+
+pub fn draw_quick_start_modal(frame: &mut Frame, ui: &UiState, theme: &Theme) {
+    let screen = frame.area();
+    let width = MODAL_WIDTH.min(screen.width);
+    let height = 3.min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(theme.border_type)
+        .title(" Quick start ")
+        .title_bottom(Line::from(" [Enter] Start | [Esc] Cancel ").centered())
+        .style(Style::default().fg(theme.accent_color).bg(theme.base_bg));
+    let inner = block.inner(area);
+
+    let input = if ui.current_input.is_empty() {
+        Span::styled("Unnamed task", Style::default().fg(theme.help_text_fg).add_modifier(Modifier::DIM))
+    } else {
+        Span::styled(ui.current_input.as_str(), Style::default().fg(theme.base_fg))
+    };
+    frame.render_widget(Paragraph::new(Line::from(input)).block(block), area);
+    frame.set_cursor_position((inner.x + ui.current_input.chars().count() as u16, inner.y));
+}
@@ -2,54 +2,38 @@ use ratatui::{prelude::*, widgets::*};
 
 use super::centered_rect;
 use crate::app::{App, UiState};
-use crate::settings::Theme;
+use crate::settings::{pomodoro_technique_warnings, Theme};
 
 pub fn draw_settings(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme) {
-    let area = centered_rect(60, 50, frame.area());
+    let area = centered_rect(60, 60, frame.area());
 
     let settings_block = Block::default()
         .title(" ⚙ SETTINGS ")
         .borders(Borders::ALL)
-        .border_type(BorderType::Double)
+        .border_type(theme.border_type)
         .style(Style::default().fg(theme.accent_color).bg(theme.base_bg))
         .title_alignment(Alignment::Center);
 
     let inner_area = settings_block.inner(area);
     let inner_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .constraints([Constraint::Min(0), Constraint::Length(1), Constraint::Length(1)])
         .margin(1)
         .split(inner_area);
 
-    let rows: Vec<Row> = vec![
-        Row::new(vec![
-            Cell::from("Pomodoro Duration"),
-            Cell::from(format!("< {} mins >", app.settings.pomodoro_duration.as_secs() / 60)),
-        ]),
-        Row::new(vec![
-            Cell::from("Short Break"),
-            Cell::from(format!("< {} mins >", app.settings.short_break_duration.as_secs() / 60)),
-        ]),
-        Row::new(vec![
-            Cell::from("Long Break"),
-            Cell::from(format!("< {} mins >", app.settings.long_break_duration.as_secs() / 60)),
-        ]),
-        Row::new(vec![
-            Cell::from("Color Theme"),
-            Cell::from(format!("< {:?} >", app.settings.theme)),
-        ]),
-        Row::new(vec![
-            Cell::from("Desktop Notifications"),
-            Cell::from(format!("< {} >", if app.settings.desktop_notifications { "On" } else { "Off" })),
-        ]),
-        Row::new(vec![
-            Cell::from("Long Break Interval"),
-            Cell::from(format!("< {} pomodoros >", app.settings.long_break_interval)),
-        ]),
-    ]
-    .into_iter()
-    .map(|r| r.height(1).style(Style::default().fg(theme.base_fg)))
-    .collect();
+    // Row order matches `settings_selection`; "Edit Labels" and "Reset to Defaults" come last
+    let rows: Vec<Row> = Vec::<(String, String)>::from(&app.settings)
+        .into_iter()
+        .map(|(label, value)| Row::new(vec![Cell::from(label), Cell::from(format!("< {} >", value))]))
+        .chain([
+            Row::new(vec![
+                Cell::from("Edit Labels"),
+                Cell::from(format!("[Enter] ({})", app.settings.available_labels.len())),
+            ]),
+            Row::new(vec![Cell::from("Reset to Defaults"), Cell::from("[Enter]")]),
+        ])
+        .map(|r| r.height(1).style(Style::default().fg(theme.base_fg)))
+        .collect();
 
     let mut table_state = TableState::default();
     table_state.select(Some(ui.settings_selection));
@@ -61,10 +45,41 @@ pub fn draw_settings(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme)
     frame.render_widget(Clear, area);
     frame.render_widget(settings_block, area);
     frame.render_stateful_widget(table, inner_layout[0], &mut table_state);
+    if let Some(warning) = pomodoro_technique_warnings(&app.settings).into_iter().next() {
+        frame.render_widget(
+            Paragraph::new(warning)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(theme.paused_fg)),
+            inner_layout[1],
+        );
+    }
     frame.render_widget(
-        Paragraph::new(" [↑/↓] Navigate | [←/→] Change | [Tab] Back ")
+        Paragraph::new(" [↑/↓] Navigate | [←/→] Change | [Ctrl+D] Defaults | [S]ave / [P]resets | [O]pen data folder | [Tab] Back ")
             .alignment(Alignment::Center)
             .style(Style::default().fg(theme.help_text_fg)),
-        inner_layout[1],
+        inner_layout[2],
     );
+
+    if ui.confirm_restore_defaults {
+        let popup = centered_rect(40, 20, frame.area());
+        frame.render_widget(Clear, popup);
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from("Restore every setting to its default?"),
+                Line::from(""),
+                Line::from(Span::styled("[y] Restore | [n] Cancel", Style::default().fg(theme.help_text_fg))),
+            ])
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .title(" Reset to Defaults ")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(theme.border_type)
+                    .style(Style::default().fg(theme.paused_fg).bg(theme.base_bg)),
+            ),
+            popup,
+        );
+    }
 }
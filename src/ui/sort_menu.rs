@@ -0,0 +1,50 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::app::App;
+use crate::settings::{SortCriterion, Theme};
+
+const POPUP_WIDTH: u16 = 40;
+
+/// `S` popup over the task list; the last criterion used is ticked.
+pub fn draw_sort_menu(frame: &mut Frame, app: &App, selected: usize, theme: &Theme) {
+    let screen = frame.area();
+    let width = POPUP_WIDTH.min(screen.width);
+    let height = (SortCriterion::ALL.len() as u16 + 2).min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, area);
+
+    let dim = Style::default().fg(theme.help_text_fg);
+    let items: Vec<ListItem> = SortCriterion::ALL
+        .iter()
+        .map(|&criterion| {
+            ListItem::new(Line::from(vec![
+                Span::styled(if criterion == app.settings.default_sort { "✓ " } else { "  " }, dim),
+                Span::styled(criterion.to_string(), Style::default().fg(theme.base_fg)),
+            ]))
+        })
+        .collect();
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(selected.min(SortCriterion::ALL.len() - 1)));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(
+                Block::default()
+                    .title(" Sort tasks by ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::from(" [Enter] Sort | [Esc] Close ").centered())
+                    .borders(Borders::ALL)
+                    .border_type(theme.border_type)
+                    .style(Style::default().fg(theme.accent_color).bg(theme.base_bg)),
+            )
+            .highlight_style(Style::default().bg(theme.highlight_bg).add_modifier(Modifier::BOLD))
+            .highlight_symbol("> "),
+        area,
+        &mut list_state,
+    );
+}
@@ -1,13 +1,16 @@
 use std::time::Duration;
 
-use chrono::{Datelike, Local, Weekday};
+use chrono::{Datelike, Local, NaiveDate, Weekday};
 use ratatui::{prelude::*, widgets::*};
 
-use crate::app::{App, InputMode, UiState};
-use crate::app::ui_state::task_matches_filter;
+use crate::app::{App, InputMode, StatisticsFilter, Task, UiState};
+use crate::app::ui_state::{completed_task_indices, hidden_zero_pomodoro_count};
+use super::task_color;
 use crate::settings::Theme;
+use crate::stats::focus_efficiency;
+use crate::util::format_hm;
 
-// Below this total terminal width, collapse chart and show sparkline underneath
+// Below this total terminal width, drop the bar chart and show the summary full-width
 const BARCHART_MIN_WIDTH: u16 = 50;
 
 fn weekday_label(wd: Weekday) -> &'static str {
@@ -30,7 +33,7 @@ fn weekly_bar_data(app: &App) -> Vec<(String, u64)> {
     for task in &app.tasks {
         if let Some(completed) = task.completion_date {
             let d = (completed.with_timezone(&Local).date_naive() - monday).num_days();
-            if d >= 0 && d < 7 {
+            if (0..7).contains(&d) {
                 counts[d as usize] += task.pomodoros as u64;
             }
         }
@@ -59,47 +62,64 @@ fn four_week_max(app: &App) -> u64 {
     daily.values().copied().max().unwrap_or(1).max(1)
 }
 
-// Last 7 rolling days for the sparkline fallback
-fn last7_sparkline(app: &App) -> Vec<u64> {
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// Heading, one block per day scaled to the busiest day, then the day names
+fn draw_activity(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let counts = app.daily_counts(7);
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
     let today = Local::now().date_naive();
-    let mut counts = [0u64; 7];
-    for task in &app.tasks {
-        if let Some(completed) = task.completion_date {
-            let days_ago = (today - completed.with_timezone(&Local).date_naive()).num_days();
-            if days_ago >= 0 && (days_ago as usize) < 7 {
-                counts[6 - days_ago as usize] += task.pomodoros as u64;
-            }
+    let cell = (area.width / 7).max(4) as usize;
+    let mut bars = String::new();
+    let mut days = String::new();
+    for (i, &count) in counts.iter().enumerate() {
+        let level = if count == 0 { ' ' } else { SPARK_LEVELS[((count * 8 - 1) / max) as usize] };
+        bars.push_str(&format!("{:^cell$}", level.to_string().repeat(cell.saturating_sub(2).min(3))));
+        let date = today - chrono::Duration::days(6 - i as i64);
+        days.push_str(&format!("{:^cell$}", weekday_label(date.weekday())));
+    }
+    let total: u64 = counts.iter().sum();
+    let lines = vec![
+        Line::from(format!(" Last 7 days · {} pomodoro{}", total, if total == 1 { "" } else { "s" }))
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+        Line::from(bars).style(Style::default().fg(theme.pomodoro_color)),
+        Line::from(days).style(Style::default().fg(theme.base_fg).add_modifier(Modifier::DIM)),
+    ];
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
+        area,
+    );
+}
+
+// Consecutive runs of tasks sharing a (local) completion date; input must already be sorted
+fn group_by_completion_date<'a>(tasks: impl Iterator<Item = &'a Task>) -> Vec<(NaiveDate, Vec<&'a Task>)> {
+    let mut groups: Vec<(NaiveDate, Vec<&'a Task>)> = Vec::new();
+    for task in tasks {
+        let date = task.completion_date
+            .unwrap_or(task.creation_date)
+            .with_timezone(&Local)
+            .date_naive();
+        match groups.last_mut() {
+            Some((d, group)) if *d == date => group.push(task),
+            _ => groups.push((date, vec![task])),
         }
     }
-    counts.to_vec()
+    groups
 }
 
-pub fn draw_statistics(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme) {
+pub fn draw_statistics(frame: &mut Frame, app: &App, ui: &mut UiState, theme: &Theme) {
     let wide = frame.area().width >= BARCHART_MIN_WIDTH;
 
-    // Vertical layout differs between wide and narrow modes
-    let chunks = if wide {
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),  // title
-                Constraint::Length(8),  // summary (left) + barchart (right)
-                Constraint::Min(0),     // task list
-                Constraint::Length(4),  // help
-            ])
-            .split(frame.area())
-    } else {
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),  // title
-                Constraint::Length(8),  // summary full-width
-                Constraint::Length(3),  // sparkline
-                Constraint::Min(0),     // task list
-                Constraint::Length(4),  // help
-            ])
-            .split(frame.area())
-    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // title
+            Constraint::Length(3),  // 7-day activity
+            Constraint::Length(12), // summary, with the barchart to its right when wide
+            Constraint::Min(0),     // task list
+            Constraint::Length(4),  // help
+        ])
+        .split(frame.area());
 
     // Title
     let stats_title = if !ui.filter_input.is_empty() {
@@ -114,6 +134,7 @@ pub fn draw_statistics(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme
             .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
         chunks[0],
     );
+    draw_activity(frame, app, chunks[1], theme);
 
     // --- Stats data ---
     let today = Local::now().date_naive();
@@ -122,32 +143,68 @@ pub fn draw_statistics(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme
         .filter(|dt| dt.with_timezone(&Local).date_naive() == today)
         .count() as u64;
     let today_time: Duration = app.tasks.iter()
-        .filter(|t| t.completion_date.map_or(false, |dt| dt.with_timezone(&Local).date_naive() == today))
+        .filter(|t| t.completion_date.is_some_and(|dt| dt.with_timezone(&Local).date_naive() == today))
         .map(|t| t.time_spent)
         .sum();
     let total_time: Duration = app.tasks.iter().map(|t| t.time_spent).sum();
-    let fmt_time = |d: Duration| format!("{}h {}m", d.as_secs() / 3600, (d.as_secs() % 3600) / 60);
+    // With a period filter the second block totals just the listed tasks
+    let filter = ui.filter_input.to_lowercase();
+    let indices = completed_task_indices(app, &filter);
+    let totals_heading = app.stats_filter.to_string();
+    let (totals_pomodoros, totals_time) = if app.stats_filter == StatisticsFilter::AllTime {
+        (app.pomodoros_completed_total as u64, total_time)
+    } else {
+        (
+            indices.iter().map(|&i| app.tasks[i].pomodoros as u64).sum(),
+            indices.iter().map(|&i| app.tasks[i].time_spent).sum(),
+        )
+    };
     let bold = Style::default().add_modifier(Modifier::BOLD);
+    let summary = app.compute_summary_stats();
 
     let summary_lines = vec![
         Line::from(Span::styled("Today", bold)),
         Line::from(format!("Pomodoros:    {}", today_pomodoros)),
-        Line::from(format!("Time Focused: {}", fmt_time(today_time))),
-        Line::from(Span::styled("All Time", bold)),
-        Line::from(format!("Pomodoros:    {}", app.pomodoros_completed_total)),
-        Line::from(format!("Time Focused: {}", fmt_time(total_time))),
+        Line::from(format!("Time Focused: {}", format_hm(today_time))),
+        Line::from(Span::styled(totals_heading, bold)),
+        Line::from(format!("Pomodoros:    {}", totals_pomodoros)),
+        Line::from(format!("Time Focused: {}", format_hm(totals_time))),
+        Line::from(format!(
+            "Completion:   {}",
+            summary.completion_rate.map_or("–".to_string(), |rate| {
+                format!("{:.0}% ({}/{} tasks)", rate, summary.completed_tasks, summary.total_tasks)
+            })
+        )),
+        Line::from(format!(
+            "Avg/Task:     {}",
+            summary.avg_pomodoros_per_task.map_or("–".to_string(), |avg| format!("{:.1} ●", avg))
+        )),
+        Line::from(format!(
+            "Focus Efficiency: {:.0}%",
+            focus_efficiency(&app.tasks, &app.settings) * 100.0
+        )),
+        Line::from(format!(
+            "Busiest Hour: {}",
+            summary.busiest_hour.map_or("–".to_string(), |h| format!("{:02}:00–{:02}:00", h, (h + 1) % 24))
+        )),
     ];
 
     if wide {
-        // Summary left (38%) + BarChart right (62%)
+        // Summary left (38%) + BarChart right (62%); a project breakdown takes a third column once projects exist
+        let project_data = app.project_pomodoros();
+        let show_projects = !project_data.is_empty();
         let top_cols = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(38), Constraint::Percentage(62)])
-            .split(chunks[1]);
+            .constraints(if show_projects {
+                vec![Constraint::Percentage(32), Constraint::Percentage(40), Constraint::Percentage(28)]
+            } else {
+                vec![Constraint::Percentage(38), Constraint::Percentage(62)]
+            })
+            .split(chunks[2]);
 
         let stats_block = Block::default()
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
+            .border_type(theme.border_type)
             .title("Summary")
             .style(Style::default().fg(theme.base_fg).bg(theme.base_bg));
         let stats_inner = stats_block.inner(top_cols[0]);
@@ -178,7 +235,7 @@ pub fn draw_statistics(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .border_type(BorderType::Rounded)
+                        .border_type(theme.border_type)
                         .title("This week")
                         .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
                 )
@@ -195,57 +252,78 @@ pub fn draw_statistics(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme
                 .data(BarGroup::default().bars(&bars)),
             top_cols[1],
         );
+
+        if show_projects {
+            let project_bars: Vec<Bar> = project_data
+                .iter()
+                .map(|(project, count)| {
+                    Bar::default()
+                        .label(Line::from(format!("@{}", project.name)))
+                        .value(*count as u64)
+                        .style(Style::default().fg(task_color(project.color)))
+                })
+                .collect();
+            frame.render_widget(
+                BarChart::default()
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_type(theme.border_type)
+                            .title("By project")
+                            .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
+                    )
+                    .direction(Direction::Horizontal)
+                    .bar_width(1)
+                    .bar_gap(0)
+                    .value_style(Style::default().fg(theme.base_fg).add_modifier(Modifier::BOLD))
+                    .label_style(Style::default().fg(theme.base_fg))
+                    .data(BarGroup::default().bars(&project_bars)),
+                top_cols[2],
+            );
+        }
     } else {
         // Summary full-width
         let stats_block = Block::default()
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
+            .border_type(theme.border_type)
             .title("Summary")
             .style(Style::default().fg(theme.base_fg).bg(theme.base_bg));
-        let stats_inner = stats_block.inner(chunks[1]);
-        frame.render_widget(stats_block, chunks[1]);
+        let stats_inner = stats_block.inner(chunks[2]);
+        frame.render_widget(stats_block, chunks[2]);
         frame.render_widget(
             Paragraph::new(summary_lines).alignment(Alignment::Left),
             stats_inner,
         );
-
-        // Sparkline below summary
-        let spark_data = last7_sparkline(app);
-        frame.render_widget(
-            Sparkline::default()
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .border_type(BorderType::Rounded)
-                        .title("Last 7 days")
-                        .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
-                )
-                .data(spark_data.iter().copied())
-                .style(Style::default().fg(theme.pomodoro_color)),
-            chunks[2],
-        );
     }
 
-    // Indices shift by 1 in narrow mode due to extra sparkline chunk
-    let (tasks_idx, help_idx) = if wide { (2, 3) } else { (3, 4) };
+    let (tasks_idx, help_idx) = (3, 4);
 
     // --- Completed task list ---
-    let filter = ui.filter_input.to_lowercase();
-    let completed_tasks: Vec<_> = app
-        .tasks
-        .iter()
-        .filter(|t| t.completed && (filter.is_empty() || task_matches_filter(t, &filter)))
-        .collect();
-    let mut list_state = ListState::default();
-    list_state.select(ui.completed_task_list_state);
+    // Grouped under date headers; the selection indexes tasks only, so map it to a row
+    let groups = group_by_completion_date(indices.iter().map(|&i| &app.tasks[i]));
+    let header_style = Style::default().fg(theme.accent_color).add_modifier(Modifier::ITALIC);
 
-    let list_items: Vec<ListItem> = completed_tasks
-        .iter()
-        .map(|task| {
+    let mut list_items: Vec<ListItem> = Vec::new();
+    let mut selected_row = None;
+    let mut task_pos = 0;
+    for (date, tasks) in &groups {
+        list_items.push(ListItem::new(Line::from(Span::styled(
+            format!("── {} ──", date.format("%Y-%m-%d")),
+            header_style,
+        ))));
+        for task in tasks {
+            if ui.completed_task_list_state == Some(task_pos) {
+                selected_row = Some(list_items.len());
+            }
+            let (marker, row_style) = if ui.select_all_completed {
+                ("[*] ", Style::default().fg(theme.base_fg).bg(theme.highlight_bg))
+            } else {
+                ("", Style::default().fg(theme.base_fg))
+            };
             let mut spans = vec![
                 Span::styled(
-                    format!("{:<40} | {} ●", task.name, task.pomodoros),
-                    Style::default().fg(theme.base_fg),
+                    format!("{}{:<40} | {} ●", marker, task.name, task.pomodoros),
+                    row_style,
                 ),
             ];
             if let Some(proj) = &task.project {
@@ -254,21 +332,27 @@ pub fn draw_statistics(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme
                     Style::default().fg(theme.accent_color),
                 ));
             }
-            ListItem::new(Line::from(spans))
-        })
-        .collect();
+            list_items.push(ListItem::new(Line::from(spans)));
+            task_pos += 1;
+        }
+    }
+    let mut list_state = ListState::default();
+    list_state.select(selected_row);
 
-    let task_list_title = if !filter.is_empty() {
-        format!("Completed & Archived Tasks [/{}]", ui.filter_input)
+    let mut task_list_title = if !filter.is_empty() {
+        format!("Completed Tasks · {} [/{}]", app.stats_filter, ui.filter_input)
     } else {
-        "Completed & Archived Tasks".to_string()
+        format!("Completed Tasks · {}", app.stats_filter)
     };
+    if app.hide_zero_pomodoro_tasks {
+        task_list_title.push_str(&format!(" · Hiding {} zero-pomodoro tasks", hidden_zero_pomodoro_count(app, &filter)));
+    }
     frame.render_stateful_widget(
         List::new(list_items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
+                    .border_type(theme.border_type)
                     .title(task_list_title)
                     .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
             )
@@ -277,6 +361,7 @@ pub fn draw_statistics(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme
         chunks[tasks_idx],
         &mut list_state,
     );
+    ui.completed_list_area = chunks[tasks_idx];
 
     // --- Help bar / filter bar ---
     match ui.input_mode {
@@ -288,7 +373,7 @@ pub fn draw_statistics(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme
                     .block(
                         Block::default()
                             .borders(Borders::ALL)
-                            .border_type(BorderType::Rounded)
+                            .border_type(theme.border_type)
                             .title("Filter")
                             .style(Style::default().fg(theme.accent_color)),
                     ),
@@ -300,21 +385,58 @@ pub fn draw_statistics(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme
             ));
             return;
         }
+        InputMode::EditingStatsRange => {
+            let title = if ui.range_start.is_some() { "To (YYYY-MM-DD)" } else { "From (YYYY-MM-DD)" };
+            frame.render_widget(
+                Paragraph::new(ui.current_input.as_str())
+                    .style(Style::default().fg(theme.paused_fg))
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_type(theme.border_type)
+                            .title(title)
+                            .style(Style::default().fg(theme.accent_color)),
+                    ),
+                chunks[help_idx],
+            );
+            frame.set_cursor_position((
+                chunks[help_idx].x + 1 + ui.current_input.len() as u16,
+                chunks[help_idx].y + 1,
+            ));
+            return;
+        }
         _ => {}
     }
 
-    let help_text = if chunks[help_idx].width > 80 {
-        " [Tab] Timer | [↑/↓] Navigate | [/] Filter | [Enter] Details | [d]elete | [q]uit "
+    // A pending delete-all takes over the help bar with a y/n prompt
+    // Only the listed tasks are selected and deleted
+    let completed_count = indices.len();
+    let (help_text, help_style) = if ui.confirm_delete_all_completed {
+        (
+            format!(" Delete {} listed completed tasks? [y/n] ", completed_count),
+            Style::default().fg(theme.paused_fg).add_modifier(Modifier::BOLD),
+        )
+    } else if ui.select_all_completed {
+        (
+            format!(" {} selected | [d]elete all | [Ctrl+A / Esc] Clear selection ", completed_count),
+            Style::default(),
+        )
+    } else if chunks[help_idx].width > 80 {
+        (
+            " [Tab] Timer | [↑/↓] Navigate | [/] Filter | [Enter] Details | [d]elete | [Ctrl+U] Undo | [Ctrl+A] Select all | [Esc] Deselect | [f] Period | [F] Range | [H]ide 0● | [t]imer | [q]uit ".to_string(),
+            Style::default(),
+        )
     } else {
-        " [Tab] [↑/↓] [/] [Ent] [d] [q] "
+        (" [Tab] [↑/↓] [/] [Ent] [d] [^U] [^A] [Esc] [f] [F] [H] [t] [q] ".to_string(), Style::default())
     };
     frame.render_widget(
         Paragraph::new(help_text)
+            .style(help_style)
             .block(
                 Block::default()
                     .title("Controls")
                     .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
+                    .border_type(theme.border_type)
                     .style(Style::default().fg(theme.help_text_fg)),
             )
             .alignment(Alignment::Center),
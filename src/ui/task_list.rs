@@ -1,86 +1,263 @@
+use std::time::Duration;
+
+use chrono::Utc;
 use ratatui::{prelude::*, widgets::*};
 
-use crate::app::{App, InputMode, TimerState, UiState};
+use super::{label_color, task_color};
+use crate::app::{App, InputMode, Task, TimerState, UiState};
+use crate::app::ui_state::{task_matches_filter, MAX_TASK_NAME_LEN};
 use crate::settings::Theme;
+use crate::util::{format_hm, format_relative};
+
+const RELATIVE_DATE_MIN_WIDTH: u16 = 60;
+const PROGRESS_BAR_WIDTH: usize = 10;
+
+/// `██████░░░░` for `spent` out of `estimate`; full once the estimate is used up.
+fn progress_bar(spent: Duration, estimate: Duration) -> String {
+    let ratio = if estimate.is_zero() {
+        1.0
+    } else {
+        (spent.as_secs_f64() / estimate.as_secs_f64()).min(1.0)
+    };
+    let filled = (ratio * PROGRESS_BAR_WIDTH as f64).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(PROGRESS_BAR_WIDTH - filled))
+}
+
+/// Extra lines under a task expanded with `Ctrl+I`: time, pomodoros against
+/// the estimate, the first line of the notes and the project tag.
+fn inline_details(app: &App, task: &Task, theme: &Theme) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(theme.help_text_fg);
+    let pomodoros = match task.estimated_pomodoros {
+        Some(estimate) => format!("{} / {} est.", task.pomodoros, estimate),
+        None => task.pomodoros.to_string(),
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        format!("      Time: {} | Pomodoros: {}", format_hm(task.time_spent), pomodoros),
+        dim,
+    ))];
+    if let Some(note) = task.notes.as_deref().and_then(|n| n.lines().find(|l| !l.trim().is_empty())) {
+        lines.push(Line::from(Span::styled(
+            format!("      ✎ {}", note.trim()),
+            dim.add_modifier(Modifier::ITALIC),
+        )));
+    }
+    if let Some(project) = &task.project {
+        lines.push(Line::from(vec![
+            Span::styled("      Tags: ", dim),
+            Span::styled(
+                format!("@{}", project),
+                Style::default().fg(app.project(project).map_or(theme.accent_color, |p| task_color(p.color))),
+            ),
+        ]));
+    }
+    lines
+}
+
+pub fn draw_task_list(frame: &mut Frame, app: &App, ui: &mut UiState, theme: &Theme) {
+    let filter = ui.filter_input.to_lowercase();
+    let active_tasks: Vec<_> = app
+        .active_tasks()
+        .into_iter()
+        // Separators only make sense in the full list
+        .filter(|(_, t)| filter.is_empty() || task_matches_filter(t, &filter))
+        .collect();
+
+    // First line of the selected task's notes, shown under the list
+    let notes_preview = active_tasks
+        .iter()
+        .find(|(i, _)| Some(*i) == app.active_task_index)
+        .and_then(|(_, t)| t.notes.as_deref())
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+    let preview_height = if notes_preview.is_some() { 2 } else { 0 };
 
-pub fn draw_task_list(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
             Constraint::Min(0),
+            Constraint::Length(preview_height),
             Constraint::Length(3),
             Constraint::Length(4),
         ])
         .split(frame.area());
 
+    let overdue = app.tasks_with_overdue_estimates();
+    let header = if overdue.is_empty() {
+        " ✓ TASKS ".to_string()
+    } else {
+        format!(" ✓ TASKS ({} overdue) ", overdue.len())
+    };
     frame.render_widget(
         Block::default()
-            .title(" ✓ TASKS ")
+            .title(header)
             .title_alignment(Alignment::Center)
             .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
         chunks[0],
     );
 
-    let filter = ui.filter_input.to_lowercase();
-    let active_tasks: Vec<_> = app
-        .tasks
-        .iter()
-        .enumerate()
-        .filter(|(_, t)| {
-            if t.completed { return false; }
-            if filter.is_empty() { return true; }
-            let proj_match = t.project.as_deref().map_or(false, |p| {
-                let tag = format!("@{}", p.to_lowercase());
-                tag.contains(&filter) || p.to_lowercase().contains(&filter)
-            });
-            let notes_match = t.notes.as_deref()
-                .map_or(false, |n| n.to_lowercase().contains(&filter));
-            t.name.to_lowercase().contains(&filter) || proj_match || notes_match
-        })
-        .collect();
-
-    let mut list_state = ListState::default();
-    if let Some(active_index) = app.active_task_index {
-        if let Some(pos) = active_tasks.iter().position(|(i, _)| *i == active_index) {
-            list_state.select(Some(pos));
-        }
-    }
-
-    let list_title = if !ui.filter_input.is_empty() {
+    let mut list_title = if !ui.filter_input.is_empty() {
         format!("Active Tasks [/{}]", ui.filter_input)
     } else {
         "Active Tasks".to_string()
     };
+    let deferred = app.deferred_task_count();
+    if deferred > 0 {
+        let visibility = if app.show_deferred { "shown" } else { "hidden" };
+        list_title.push_str(&format!(" ({} deferred, {})", deferred, visibility));
+    }
 
+    let show_age = chunks[1].width > RELATIVE_DATE_MIN_WIDTH;
+    // Inner width minus borders and the ">> " highlight symbol
+    let row_width = chunks[1].width.saturating_sub(5) as usize;
     let active_list_items: Vec<ListItem> = active_tasks
         .iter()
         .map(|(i, task)| {
-            let running = Some(*i) == app.active_task_index && app.state == TimerState::Running;
-            let marker = if running { "▶ " } else { "  " };
-            let base_style = if running {
-                Style::default().fg(theme.pomodoro_color)
+            if task.is_separator {
+                return ListItem::new(Line::from(Span::styled(
+                    "─".repeat(row_width),
+                    Style::default().fg(theme.help_text_fg),
+                )));
+            }
+            let focused = if app.settings.parallel_mode && !app.active_task_indices.is_empty() {
+                app.active_task_indices.contains(i)
+            } else {
+                Some(*i) == app.active_task_index
+            };
+            let running = focused && app.state == TimerState::Running;
+            // In parallel mode every task sharing the timer is marked, even while paused
+            let marker = if running {
+                "▶ "
+            } else if app.settings.parallel_mode && app.active_task_indices.contains(i) {
+                "◆ "
+            } else {
+                "  "
+            };
+            let age_days = Utc::now().signed_duration_since(task.creation_date).num_days().max(0) as u64;
+            let age_fg = if age_days >= app.settings.task_age_danger_days {
+                theme.pomodoro_color
+            } else if age_days >= app.settings.task_age_warning_days {
+                theme.paused_fg
+            } else {
+                theme.base_fg
+            };
+            // Running beats a manual color, which beats the stale-task tint
+            let fg = if running {
+                theme.pomodoro_color
             } else {
-                Style::default().fg(theme.base_fg)
+                task.color.map_or(age_fg, task_color)
             };
-            let mut spans = vec![
-                Span::styled(format!("[ ] {}{}", marker, task.name), base_style),
-            ];
+            let base_style = Style::default().fg(fg);
+            let mut spans = Vec::new();
+            if overdue.contains(i) {
+                spans.push(Span::styled("⚠ ", Style::default().fg(theme.paused_fg)));
+            }
+            spans.push(Span::styled(format!("[ ] {}{}", marker, task.name), base_style));
+            if task.is_deferred() {
+                spans.push(Span::styled(" (deferred)", Style::default().fg(theme.help_text_fg).add_modifier(Modifier::ITALIC)));
+            }
+            if task.recurrence.is_some() {
+                spans.push(Span::styled(" ↻", Style::default().fg(theme.help_text_fg)));
+            }
+            if task.all_subtasks_complete() {
+                spans.push(Span::styled(" ✓", Style::default().fg(theme.running_fg)));
+            }
+            if let Some(priority) = task.priority {
+                spans.push(Span::styled(
+                    format!(" {}", priority.marker()),
+                    Style::default().fg(theme.paused_fg).add_modifier(Modifier::BOLD),
+                ));
+            }
+            for label in &task.labels {
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(
+                    format!(" {} ", label),
+                    Style::default().fg(theme.base_bg).bg(label_color(label, &app.settings, theme)),
+                ));
+            }
             if let Some(proj) = &task.project {
                 spans.push(Span::styled(
                     format!(" @{}", proj),
-                    Style::default().fg(theme.accent_color),
+                    Style::default().fg(app.project(proj).map_or(theme.accent_color, |p| task_color(p.color))),
                 ));
             }
-            ListItem::new(Line::from(spans))
+            // The running task shows how far it is into its estimate, or just the time so far
+            if running {
+                let spent_mins = task.time_spent.as_secs() / 60;
+                match app.estimated_duration(task) {
+                    Some(estimate) => {
+                        let over = task.time_spent > estimate;
+                        spans.push(Span::styled(
+                            format!(" {}", progress_bar(task.time_spent, estimate)),
+                            Style::default().fg(if over { theme.paused_fg } else { theme.running_fg }),
+                        ));
+                        spans.push(Span::styled(
+                            format!(" {}m/{}m", spent_mins, estimate.as_secs() / 60),
+                            Style::default().fg(theme.help_text_fg),
+                        ));
+                    }
+                    None => spans.push(Span::styled(
+                        format!(" {}m", spent_mins),
+                        Style::default().fg(theme.help_text_fg),
+                    )),
+                }
+            }
+            if show_age {
+                let age = format_relative(task.creation_date);
+                let used: usize = spans.iter().map(Span::width).sum();
+                let padding = row_width.saturating_sub(used + age.chars().count()).max(1);
+                spans.push(Span::raw(" ".repeat(padding)));
+                spans.push(Span::styled(age, Style::default().fg(theme.help_text_fg)));
+            }
+            let mut lines = vec![Line::from(spans)];
+            if app.inline_expanded_task == Some(*i) {
+                lines.extend(inline_details(app, task, theme));
+            }
+            ListItem::new(lines)
         })
         .collect();
 
-    let active_list = List::new(active_list_items)
+    // Once projects exist the list is grouped, each group under a colored header.
+    // `row_tasks` maps list rows back to task indices; headers map to `None`.
+    let mut row_tasks: Vec<Option<usize>> = Vec::new();
+    // Lines per row; an inline-expanded task is taller than one line
+    let mut row_heights: Vec<u16> = Vec::new();
+    let mut list_items: Vec<ListItem> = Vec::new();
+    let mut group: Option<Option<&str>> = None;
+    for ((i, task), item) in active_tasks.iter().zip(active_list_items) {
+        if !app.projects.is_empty() {
+            let project = task.project.as_deref().and_then(|p| app.project(p));
+            let key = project.map(|p| p.name.as_str());
+            if group != Some(key) {
+                group = Some(key);
+                let header = match project {
+                    Some(p) => Span::styled(
+                        format!("── @{} ", p.name),
+                        Style::default().fg(task_color(p.color)).add_modifier(Modifier::BOLD),
+                    ),
+                    None => Span::styled("── No project ", Style::default().fg(theme.help_text_fg).add_modifier(Modifier::BOLD)),
+                };
+                list_items.push(ListItem::new(Line::from(header)));
+                row_tasks.push(None);
+                row_heights.push(1);
+            }
+        }
+        row_heights.push(item.height() as u16);
+        list_items.push(item);
+        // Separators can't be clicked into selection
+        row_tasks.push((!task.is_separator).then_some(*i));
+    }
+
+    let mut list_state = ListState::default();
+    if let Some(active_index) = app.active_task_index {
+        list_state.select(row_tasks.iter().position(|row| *row == Some(active_index)));
+    }
+
+    let active_list = List::new(list_items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
+                .border_type(theme.border_type)
                 .title(list_title)
                 .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
         )
@@ -92,24 +269,76 @@ pub fn draw_task_list(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme)
         .highlight_symbol(">> ");
     frame.render_stateful_widget(active_list, chunks[1], &mut list_state);
 
-    let input_title = if ui.editing_task_index.is_some() { "Rename Task" } else { "New Task" };
+    if let Some(notes) = notes_preview {
+        let mut lines = notes.lines();
+        let first = lines.next().unwrap_or("");
+        let more = lines.count();
+        let mut preview = vec![Line::from(Span::styled(
+            format!("✎ {}", first),
+            Style::default().fg(theme.help_text_fg).add_modifier(Modifier::ITALIC),
+        ))];
+        if more > 0 {
+            preview.push(Line::from(Span::styled(
+                format!("  (+{} more line{})", more, if more == 1 { "" } else { "s" }),
+                Style::default().fg(theme.help_text_fg).add_modifier(Modifier::DIM),
+            )));
+        }
+        frame.render_widget(
+            Paragraph::new(preview).style(Style::default().bg(theme.base_bg)),
+            chunks[2].inner(Margin { horizontal: 2, vertical: 0 }),
+        );
+    }
+
+    // Record where each visible task landed so mouse clicks can be mapped back
+    let list_top = chunks[1].y + 1;
+    let visible_rows = chunks[1].height.saturating_sub(2) as usize;
+    let offset = list_state.offset();
+    let mut line = 0u16;
+    ui.task_row_map = row_tasks
+        .iter()
+        .zip(&row_heights)
+        .skip(offset)
+        .map_while(|(task, &height)| {
+            let top = line;
+            line += height;
+            ((top as usize) < visible_rows).then_some((*task, list_top + top))
+        })
+        .filter_map(|(task, y)| task.map(|i| (i, y)))
+        .collect();
+    ui.task_list_area = chunks[1];
+    ui.task_input_area = chunks[3];
+
+    let input_title = match ui.input_mode {
+        InputMode::EditingBudget => "Time Budget (minutes, empty to clear)".to_string(),
+        InputMode::GoTo => format!("Go to Task # (1–{})", app.active_tasks().iter().filter(|(_, t)| !t.is_separator).count()),
+        _ if ui.editing_task_index.is_some() => "Rename Task".to_string(),
+        _ => "New Task".to_string(),
+    };
+    // Character count once the name is over 80% of the limit
+    let input_len = ui.current_input.chars().count();
+    let length_counter = (matches!(ui.input_mode, InputMode::Editing) && input_len * 5 > MAX_TASK_NAME_LEN * 4)
+        .then(|| Line::from(format!(" {}/{} ", input_len, MAX_TASK_NAME_LEN)).right_aligned());
     let input = Paragraph::new(ui.current_input.as_str())
         .style(match ui.input_mode {
-            InputMode::Normal | InputMode::Filtering | InputMode::EditingNotes => Style::default().fg(theme.base_fg),
-            InputMode::Editing => Style::default().fg(theme.paused_fg),
+            InputMode::Editing | InputMode::EditingBudget | InputMode::GoTo => Style::default().fg(theme.paused_fg),
+            _ => Style::default().fg(theme.base_fg),
         })
-        .block(
-            Block::default()
+        .block({
+            let block = Block::default()
                 .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
+                .border_type(theme.border_type)
                 .title(input_title)
-                .style(Style::default().fg(theme.base_fg).bg(theme.base_bg)),
-        );
-    frame.render_widget(input, chunks[2]);
-    if let InputMode::Editing = ui.input_mode {
+                .style(Style::default().fg(theme.base_fg).bg(theme.base_bg));
+            match length_counter {
+                Some(counter) => block.title_bottom(counter),
+                None => block,
+            }
+        });
+    frame.render_widget(input, chunks[3]);
+    if let InputMode::Editing | InputMode::EditingBudget | InputMode::GoTo = ui.input_mode {
         frame.set_cursor_position((
-            chunks[2].x + ui.current_input.len() as u16 + 1,
-            chunks[2].y + 1,
+            chunks[3].x + ui.current_input.len() as u16 + 1,
+            chunks[3].y + 1,
         ));
     }
 
@@ -122,25 +351,55 @@ pub fn draw_task_list(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme)
                     .block(
                         Block::default()
                             .borders(Borders::ALL)
-                            .border_type(BorderType::Rounded)
+                            .border_type(theme.border_type)
                             .title("Filter")
                             .style(Style::default().fg(theme.accent_color)),
                     ),
-                chunks[3],
+                chunks[4],
             );
             frame.set_cursor_position((
-                chunks[3].x + 1 + 1 + ui.filter_input.len() as u16,
-                chunks[3].y + 1,
+                chunks[4].x + 1 + 1 + ui.filter_input.len() as u16,
+                chunks[4].y + 1,
             ));
         }
+        _ if ui.confirm_complete_all => {
+            frame.render_widget(
+                Paragraph::new(" Mark every active task as complete? [y/n] ")
+                    .style(Style::default().fg(theme.paused_fg).add_modifier(Modifier::BOLD))
+                    .block(
+                        Block::default()
+                            .title("Confirm")
+                            .borders(Borders::ALL)
+                            .border_type(theme.border_type)
+                            .style(Style::default().fg(theme.help_text_fg)),
+                    )
+                    .alignment(Alignment::Center),
+                chunks[4],
+            );
+        }
+        _ if ui.confirm_reset_task_timers => {
+            frame.render_widget(
+                Paragraph::new(" Reset all task timers? This clears pomodoro counts and time spent. [y/n] ")
+                    .style(Style::default().fg(theme.paused_fg).add_modifier(Modifier::BOLD))
+                    .block(
+                        Block::default()
+                            .title("Confirm")
+                            .borders(Borders::ALL)
+                            .border_type(theme.border_type)
+                            .style(Style::default().fg(theme.help_text_fg)),
+                    )
+                    .alignment(Alignment::Center),
+                chunks[4],
+            );
+        }
         _ => {
             let help_text = match ui.input_mode {
-                InputMode::Editing => " [Enter] Submit | [Esc] Cancel ",
+                InputMode::Editing | InputMode::EditingBudget | InputMode::GoTo => " [Enter] Submit | [Esc] Cancel ",
                 _ => {
-                    if chunks[3].width > 80 {
-                        " [Tab] Stats | [↑/↓] Nav | [S+↑/↓] Move | [n]ew | [e]dit name | [Shift+E] notes | [/] Filter | [Enter] Complete | [d]elete | [q]uit "
+                    if chunks[4].width > 80 {
+                        " [Tab] Stats | [↑/↓] Nav | [S+↑/↓] Move | [n]ew | [e]dit name | [Shift+E] notes | [i]nfo | [/] Filter | [P]roject | [-] Separator | [S]ort | [b]udget | [Enter] Complete | [d]elete | [t]imer | [q]uit "
                     } else {
-                        " [Tab] [↑/↓] [S+↑/↓] [n] [e] [E] [/] [Ent] [d] [q] "
+                        " [Tab] [↑/↓] [S+↑/↓] [n] [e] [E] [i] [/] [P] [-] [S] [b] [Ent] [d] [t] [q] "
                     }
                 }
             };
@@ -150,12 +409,26 @@ pub fn draw_task_list(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme)
                         Block::default()
                             .title("Controls")
                             .borders(Borders::ALL)
-                            .border_type(BorderType::Rounded)
+                            .border_type(theme.border_type)
                             .style(Style::default().fg(theme.help_text_fg)),
                     )
                     .alignment(Alignment::Center),
-                chunks[3],
+                chunks[4],
             );
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_bar_fills_with_time_spent() {
+        let estimate = Duration::from_secs(100 * 60);
+        assert_eq!(progress_bar(Duration::ZERO, estimate), "░░░░░░░░░░");
+        assert_eq!(progress_bar(Duration::from_secs(30 * 60), estimate), "███░░░░░░░");
+        assert_eq!(progress_bar(Duration::from_secs(250 * 60), estimate), "██████████");
+        assert_eq!(progress_bar(Duration::from_secs(60), Duration::ZERO), "██████████");
+    }
+}
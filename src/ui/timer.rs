@@ -1,8 +1,14 @@
-use chrono::Duration as ChronoDuration;
+use std::time::Duration;
+
+use chrono::Local;
+
 use ratatui::{prelude::*, widgets::*};
 
-use crate::app::{App, Mode, TimerState};
+use crate::app::{App, InputMode, Mode, TimerState, UiState};
+use crate::app::ui_state::SCREENSAVER_TEXT;
+use super::progress_arc::{arc_size, draw_progress_arc};
 use crate::settings::Theme;
+use crate::util::format_hm;
 
 fn get_char_art(c: char) -> Vec<&'static str> {
     match c {
@@ -33,18 +39,126 @@ fn create_big_text_paragraph<'a>(text: &str, style: Style) -> Paragraph<'a> {
     Paragraph::new(lines).alignment(Alignment::Center)
 }
 
-pub fn draw_timer(frame: &mut Frame, app: &App, theme: &Theme) {
-    let (accent_color, mode_bg_color) = match app.mode {
+const ARC_MIN_TERMINAL_WIDTH: u16 = 60;
+const SUMMARY_WIDTH: u16 = 40;
+
+/// "Session Complete" popup shown while `app.session_summary_visible`. Counts
+/// include the segment that just ended, which `next_mode` has not credited yet.
+fn draw_session_summary(frame: &mut Frame, app: &App, theme: &Theme) {
+    let credited = u32::from(app.mode == Mode::Pomodoro);
+    let task = app.active_task_index.and_then(|i| app.tasks.get(i));
+    let label = Style::default().fg(theme.help_text_fg);
+    let value = Style::default().fg(theme.base_fg).add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![
+        Line::from(Span::styled(format!("{} finished", app.mode.label(&app.settings)), value)).centered(),
+        Line::from(""),
+    ];
+    if let Some(task) = task {
+        lines.push(Line::from(vec![Span::styled("Task:  ", label), Span::styled(task.name.clone(), value)]));
+        lines.push(Line::from(vec![
+            Span::styled("On this task today:  ", label),
+            Span::styled(format!("{} ●", app.pomodoros_today_on(task) + credited), value),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("Total pomodoros:  ", label),
+        Span::styled(format!("{} ●", app.pomodoros_completed_total + credited), value),
+    ]));
+
+    let screen = frame.area();
+    let width = SUMMARY_WIDTH.min(screen.width);
+    let height = (lines.len() as u16 + 2).min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .title(" Session Complete ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::from(" [any key] Continue ").centered())
+                    .borders(Borders::ALL)
+                    .border_type(theme.border_type)
+                    .style(Style::default().fg(theme.accent_color).bg(theme.base_bg)),
+            ),
+        area,
+    );
+}
+
+pub fn draw_timer(frame: &mut Frame, app: &App, ui: &UiState, theme: &Theme) {
+    let mode_colors = |mode: Mode| match mode {
         Mode::Pomodoro => (theme.pomodoro_color, theme.pomodoro_bg),
         Mode::ShortBreak => (theme.short_break_color, theme.short_break_bg),
         Mode::LongBreak => (theme.long_break_color, theme.long_break_bg),
     };
+    let (accent_color, _) = mode_colors(app.mode);
+    // Right after a segment ends, keep the old background for a moment so the change registers
+    let (_, mode_bg_color) = mode_colors(app.transition_from().unwrap_or(app.mode));
 
     let base_style = Style::default().bg(theme.base_bg).fg(theme.base_fg);
     let accent_style = Style::default().fg(accent_color);
     let running_style = Style::default().fg(theme.running_fg);
     let paused_style = Style::default().fg(theme.paused_fg);
 
+    // Segments of an hour or more get an hours field instead of `90:00`
+    let time_text = if app.settings.show_seconds && app.mode.duration(&app.settings) >= Duration::from_secs(3600) {
+        app.time_remaining_formatted_hms()
+    } else if app.settings.show_seconds {
+        app.time_remaining_formatted()
+    } else {
+        app.time_remaining_formatted_minutes()
+    };
+
+    if ui.screensaver_active(app) {
+        let area = frame.area();
+        let (x, y, _, _) = ui.screensaver_pos;
+        let text_area = Rect::new(
+            area.x + x as u16,
+            area.y + y as u16,
+            (SCREENSAVER_TEXT.len() as u16).min(area.width),
+            1,
+        )
+        .intersection(area);
+        frame.render_widget(Block::default().style(Style::default().bg(theme.base_bg)), area);
+        frame.render_widget(
+            Paragraph::new(SCREENSAVER_TEXT).style(accent_style.add_modifier(Modifier::BOLD)),
+            text_area,
+        );
+        return;
+    }
+
+    // Zen mode: nothing but the countdown and a faint mode label
+    if ui.zen_mode {
+        let zen_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(5),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(frame.area());
+        frame.render_widget(Block::default().style(Style::default().bg(mode_bg_color)), frame.area());
+        frame.render_widget(create_big_text_paragraph(&time_text, accent_style), zen_layout[1]);
+        frame.render_widget(
+            Paragraph::new(app.mode.label(&app.settings))
+                .style(Style::default().fg(theme.help_text_fg).dim())
+                .alignment(Alignment::Center),
+            zen_layout[3],
+        );
+        if app.session_summary_visible {
+            draw_session_summary(frame, app, theme);
+        }
+        return;
+    }
+
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -69,31 +183,50 @@ pub fn draw_timer(frame: &mut Frame, app: &App, theme: &Theme) {
     };
 
     let timer_block = Block::default()
-        .title(app.mode.title())
+        .title(app.mode.label(&app.settings))
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
+        .border_type(theme.border_type)
         .border_style(timer_block_border_style)
         .style(Style::default().bg(mode_bg_color));
 
     let timer_area = timer_block.inner(main_layout[1]);
     frame.render_widget(timer_block, main_layout[1]);
 
+    let total_duration = app.mode.duration(&app.settings).as_secs_f64();
+    let remaining_duration = app.time_remaining.as_secs_f64();
+    let progress_ratio = if total_duration > 0.0 {
+        ((total_duration - remaining_duration) / total_duration).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+
+    // The progress ring needs a wide terminal and room for itself plus the info lines below
+    let (arc_width, arc_height) = arc_size();
+    let show_arc = frame.area().width >= ARC_MIN_TERMINAL_WIDTH
+        && timer_area.width >= arc_width
+        && timer_area.height >= arc_height + 7;
+    let center_height = if show_arc { arc_height } else { 5 };
+
     let vertical_center_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(0),
-            Constraint::Length(5),
+            Constraint::Length(center_height),
             Constraint::Min(1),
         ])
         .split(timer_area);
 
-    let time =
-        ChronoDuration::from_std(app.time_remaining).unwrap_or_else(|_| ChronoDuration::zero());
-    let time_text = format!("{:02}:{:02}", time.num_minutes(), time.num_seconds() % 60);
+    let big_text_area = if show_arc {
+        draw_progress_arc(frame, vertical_center_layout[1], progress_ratio, accent_style);
+        let center = vertical_center_layout[1];
+        Rect::new(center.x, center.y + (center.height - 5) / 2, center.width, 5)
+    } else {
+        vertical_center_layout[1]
+    };
     frame.render_widget(
         create_big_text_paragraph(&time_text, accent_style),
-        vertical_center_layout[1],
+        big_text_area,
     );
 
     let bottom_info_layout = Layout::default()
@@ -104,68 +237,163 @@ pub fn draw_timer(frame: &mut Frame, app: &App, theme: &Theme) {
             Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
         ])
         .horizontal_margin(4)
         .split(vertical_center_layout[2]);
 
-    let task_name = app
-        .active_task_index
-        .and_then(|i| app.tasks.get(i))
-        .map_or("No active task", |t| &t.name);
+    let active_task = app.active_task_index.and_then(|i| app.tasks.get(i));
     frame.render_widget(
-        Paragraph::new(task_name)
+        Paragraph::new(active_task.map_or("No active task", |t| t.name.as_str()))
             .style(accent_style.add_modifier(Modifier::ITALIC))
             .alignment(Alignment::Center),
         bottom_info_layout[1],
     );
+    if let Some(task) = active_task {
+        let mut worked = format!("Worked: {}", format_hm(task.time_spent));
+        if let (TimerState::Running, Mode::Pomodoro, Some(elapsed)) = (app.state, app.mode, task.session_elapsed()) {
+            worked.push_str(&format!("  ·  Session: {}m", elapsed.as_secs() / 60));
+        }
+        frame.render_widget(
+            Paragraph::new(worked)
+                .style(Style::default().fg(theme.help_text_fg))
+                .alignment(Alignment::Center),
+            bottom_info_layout[2],
+        );
+    }
 
-    let (status_text, status_style) = match app.state {
-        TimerState::Running => ("▶ Running", running_style),
-        TimerState::Paused => ("⏸ Paused", paused_style),
-    };
-    frame.render_widget(
-        Paragraph::new(status_text)
-            .style(status_style)
-            .alignment(Alignment::Center),
-        bottom_info_layout[2],
-    );
-
-    let total_duration = app.mode.duration(&app.settings).as_secs_f64();
-    let remaining_duration = app.time_remaining.as_secs_f64();
-    let progress_ratio = if total_duration > 0.0 {
-        ((total_duration - remaining_duration) / total_duration).clamp(0.0, 1.0)
+    let editing = matches!(ui.input_mode, InputMode::Editing);
+    if editing {
+        // Quick-add: the input replaces the status and progress lines
+        let prompt = "New task: ";
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(prompt, Style::default().fg(theme.help_text_fg)),
+                Span::styled(ui.current_input.as_str(), paused_style),
+            ])),
+            bottom_info_layout[3],
+        );
+        frame.render_widget(
+            Paragraph::new("[Enter] Add & select | [Esc] Cancel")
+                .style(Style::default().fg(theme.help_text_fg))
+                .alignment(Alignment::Center),
+            bottom_info_layout[4],
+        );
+        frame.set_cursor_position((
+            bottom_info_layout[3].x + (prompt.len() + ui.current_input.chars().count()) as u16,
+            bottom_info_layout[3].y,
+        ));
     } else {
-        1.0
-    };
+        let (status_text, status_style) = match app.state {
+            TimerState::Running => ("▶ Running", running_style),
+            TimerState::Paused => ("⏸ Paused", paused_style),
+        };
+        frame.render_widget(
+            Paragraph::new(status_text)
+                .style(status_style)
+                .alignment(Alignment::Center),
+            bottom_info_layout[3],
+        );
+    }
+
+    if !editing {
+        let budget = app
+            .active_task_index
+            .and_then(|i| app.tasks.get(i))
+            .and_then(|t| t.time_budget.map(|b| (t.time_spent, b)));
+        // With a time budget the bar is split: segment progress left, budget used right
+        let (segment_area, budget_area) = match budget {
+            Some(_) => {
+                let halves = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .spacing(2)
+                    .split(bottom_info_layout[4]);
+                (halves[0], Some(halves[1]))
+            }
+            None => (bottom_info_layout[4], None),
+        };
+        frame.render_widget(
+            Gauge::default()
+                .gauge_style(accent_style)
+                .ratio(progress_ratio),
+            segment_area,
+        );
+        if let (Some((spent, budget)), Some(area)) = (budget, budget_area) {
+            let used = (spent.as_secs_f64() / budget.as_secs_f64().max(1.0)).clamp(0.0, 1.0);
+            let style = if spent >= budget { paused_style } else { running_style };
+            frame.render_widget(
+                Gauge::default()
+                    .gauge_style(style)
+                    .ratio(used)
+                    .label(format!("Budget {} / {}", format_hm(spent), format_hm(budget))),
+                area,
+            );
+        }
+    }
+
+    let interval = app.settings.long_break_interval.max(1);
+    let in_set = app.sessions_in_current_set();
     frame.render_widget(
-        Gauge::default()
-            .gauge_style(accent_style)
-            .ratio(progress_ratio),
-        bottom_info_layout[3],
+        Paragraph::new(format!(
+            "Set progress: {}/{} {}{}",
+            in_set,
+            interval,
+            "●".repeat(in_set as usize),
+            "○".repeat((interval - in_set) as usize),
+        ))
+        .style(Style::default().fg(theme.help_text_fg))
+        .alignment(Alignment::Center),
+        bottom_info_layout[5],
     );
 
+    let mut session_info = format!(
+        "Total sessions: {} | Set duration: {} ({} left)",
+        app.pomodoros_completed_total,
+        format_hm(app.pomodoro_set_duration()),
+        format_hm(app.estimated_time_to_complete_current_set()),
+    );
+    // Only worth showing once the task being timed has an estimate
+    let has_estimate = app.active_task_index
+        .and_then(|i| app.tasks.get(i))
+        .is_some_and(|t| t.estimated_pomodoros.is_some());
+    if let Some(done) = app.estimated_completion_time().filter(|_| has_estimate) {
+        session_info.push_str(&format!(" | Est. done: {}", done.with_timezone(&Local).format("%H:%M")));
+    }
     frame.render_widget(
-        Paragraph::new(format!("Total Sessions: {}", app.pomodoros_completed_total))
+        Paragraph::new(session_info)
             .style(Style::default().fg(theme.help_text_fg))
             .alignment(Alignment::Center),
-        bottom_info_layout[4],
+        bottom_info_layout[6],
     );
 
-    let help_text = if main_layout[2].width > 80 {
-        " [Tab] Tasks | [o]ptions | [Space] Start/Pause | [r]eset | [n]ext | [q]uit "
+    // A pending mode switch takes over the help bar with a y/n prompt
+    let (help_text, help_style) = if let Some(mode) = ui.pending_mode_switch {
+        (format!(" Switch to {}? [y/n] ", mode.label(&app.settings)), paused_style.add_modifier(Modifier::BOLD))
+    } else if main_layout[2].width > 80 {
+        (
+            " [Tab] Tasks | [o]ptions | [Space] Start/Pause | [r]eset | [n]ew task | [N]ext | [Ctrl+K] Finish | [Ctrl+B] Break | [,/.] ±1m | [p/s/l] Mode | [z]en | [q]uit ".to_string(),
+            Style::default(),
+        )
     } else {
-        " [Tab] [o] [Spc] [r] [n] [q] "
+        (" [Tab] [o] [Spc] [r] [n] [N] [^K] [^B] [,/.] [p/s/l] [z] [q] ".to_string(), Style::default())
     };
     frame.render_widget(
         Paragraph::new(help_text)
+            .style(help_style)
             .block(
                 Block::default()
                     .title("Controls")
                     .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
+                    .border_type(theme.border_type)
                     .style(Style::default().fg(theme.help_text_fg)),
             )
             .alignment(Alignment::Center),
         main_layout[2],
     );
+
+    if app.session_summary_visible {
+        draw_session_summary(frame, app, theme);
+    }
 }
@@ -0,0 +1,25 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::settings::Theme;
+
+/// One-line notification box in the top-right corner.
+pub fn draw_toast(frame: &mut Frame, message: &str, theme: &Theme) {
+    let screen = frame.area();
+    let width = (message.chars().count() as u16 + 4).min(screen.width);
+    let height = 3.min(screen.height);
+    let area = Rect::new(screen.x + screen.width - width, screen.y, width, height);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(message)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(theme.border_type)
+                    .style(Style::default().fg(theme.accent_color).bg(theme.base_bg)),
+            )
+            .style(Style::default().fg(theme.base_fg)),
+        area,
+    );
+}
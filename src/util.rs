@@ -0,0 +1,174 @@
+use std::io;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Coarse "how long ago" label for list rows, e.g. "just now", "5m ago", "3d ago".
+pub fn format_relative(dt: DateTime<Utc>) -> String {
+    format_relative_to(dt, Utc::now())
+}
+
+fn format_relative_to(dt: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now.signed_duration_since(dt);
+    // Clock skew can put a timestamp slightly in the future; treat it as fresh
+    if elapsed.num_minutes() < 1 {
+        "just now".to_string()
+    } else if elapsed.num_hours() < 1 {
+        format!("{}m ago", elapsed.num_minutes())
+    } else if elapsed.num_days() < 1 {
+        format!("{}h ago", elapsed.num_hours())
+    } else if elapsed.num_weeks() < 5 {
+        format!("{}d ago", elapsed.num_days())
+    } else if elapsed.num_days() < 365 {
+        format!("{}mo ago", elapsed.num_days() / 30)
+    } else {
+        format!("{}y ago", elapsed.num_days() / 365)
+    }
+}
+
+/// Whole hours and minutes, e.g. "1h 25m"; seconds are dropped.
+pub fn format_hm(d: Duration) -> String {
+    let mins = d.as_secs() / 60;
+    format!("{}h {}m", mins / 60, mins % 60)
+}
+
+/// Opens `path` in the platform's file manager without waiting for it.
+pub fn open_in_file_manager(path: &Path) -> io::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    spawn_detached(Command::new(opener).arg(path))
+}
+
+/// Whether `target` is an `http://` or `https://` URL, the only external IDs
+/// `open_external` is given; anything else could be a local path or command.
+pub fn is_web_url(target: &str) -> bool {
+    let lower = target.to_ascii_lowercase();
+    ["http://", "https://"].iter().any(|scheme| {
+        lower.strip_prefix(scheme).is_some_and(|rest| !rest.is_empty() && !rest.contains(char::is_whitespace))
+    })
+}
+
+/// Opens a web URL in the default browser, detached like `open_in_file_manager`.
+pub fn open_external(url: &str) -> io::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        // Not `cmd /C start`, which would give the URL to the shell to parse
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    spawn_detached(Command::new(opener).arg(url))
+}
+
+// Output is discarded and the child is waited on from a thread, so it
+// neither scribbles over the TUI nor lingers as a zombie.
+fn spawn_detached(command: &mut Command) -> io::Result<()> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Whether the terminal understands OSC 8 hyperlinks, judged from what the
+/// common ones put in the environment. Unknown terminals get plain text.
+pub fn terminal_supports_hyperlinks() -> bool {
+    let var = |name| std::env::var(name).unwrap_or_default();
+    if matches!(var("TERM_PROGRAM").as_str(), "iTerm.app" | "WezTerm" | "vscode" | "ghostty") {
+        return true;
+    }
+    let term = var("TERM");
+    if ["foot", "kitty", "alacritty", "wezterm", "ghostty"].iter().any(|t| term.contains(t)) {
+        return true;
+    }
+    // GNOME Terminal and other VTE-based terminals since 0.50
+    var("VTE_VERSION").parse::<u32>().is_ok_and(|v| v >= 5000) || !var("WT_SESSION").is_empty()
+}
+
+/// The user's editor command for `Ctrl+E`: `$VISUAL`, falling back to `$EDITOR`.
+pub fn editor_from_env() -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|cmd| !cmd.trim().is_empty())
+}
+
+/// Runs `editor` on `path` and waits for it to exit. The command may carry its
+/// own arguments, e.g. `code --wait`.
+pub fn run_editor(editor: &str, path: &Path) -> io::Result<()> {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "empty editor command"))?;
+    let status = Command::new(program).args(parts).arg(path).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("{} exited with {}", program, status)))
+    }
+}
+
+/// Clipboard text flattened to one line, for pasting into single-line inputs.
+pub fn clipboard_line() -> Result<String, arboard::Error> {
+    let text = arboard::Clipboard::new()?.get_text()?;
+    Ok(single_line(&text))
+}
+
+pub fn copy_to_clipboard(text: &str) -> Result<(), arboard::Error> {
+    arboard::Clipboard::new()?.set_text(text)
+}
+
+fn single_line(text: &str) -> String {
+    text.trim_end_matches(['\r', '\n']).replace("\r\n", " ").replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn relative_labels() {
+        let now = Utc::now();
+        assert_eq!(format_relative_to(now, now), "just now");
+        assert_eq!(format_relative_to(now + Duration::minutes(5), now), "just now");
+        assert_eq!(format_relative_to(now - Duration::seconds(59), now), "just now");
+        assert_eq!(format_relative_to(now - Duration::minutes(5), now), "5m ago");
+        assert_eq!(format_relative_to(now - Duration::hours(3), now), "3h ago");
+        assert_eq!(format_relative_to(now - Duration::days(3), now), "3d ago");
+        assert_eq!(format_relative_to(now - Duration::days(90), now), "3mo ago");
+        assert_eq!(format_relative_to(now - Duration::days(800), now), "2y ago");
+    }
+
+    #[test]
+    fn hours_and_minutes() {
+        assert_eq!(format_hm(std::time::Duration::from_secs(59)), "0h 0m");
+        assert_eq!(format_hm(std::time::Duration::from_secs(25 * 60)), "0h 25m");
+        assert_eq!(format_hm(std::time::Duration::from_secs(85 * 60 + 30)), "1h 25m");
+    }
+
+    #[test]
+    fn only_web_urls_are_opened() {
+        assert!(is_web_url("https://example.com/issues/123"));
+        assert!(is_web_url("HTTP://localhost:8080"));
+        assert!(!is_web_url("PROJ-123"));
+        assert!(!is_web_url("file:///etc/passwd"));
+        assert!(!is_web_url("https://"));
+        assert!(!is_web_url("https://example.com & calc"));
+    }
+
+    #[test]
+    fn pasted_text_becomes_one_line() {
+        assert_eq!(single_line("Fix login\nbug\r\n"), "Fix login bug");
+        assert_eq!(single_line("a\r\nb\rc"), "a b c");
+        assert_eq!(single_line("plain"), "plain");
+    }
+}
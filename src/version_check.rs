@@ -0,0 +1,68 @@
+//! `--version-check`: compares the running version with the latest GitHub release.
+
+use std::time::Duration;
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/ruteckimikolaj/pomodorust/releases/latest";
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Prints "Up to date" or the available update. Network and parse failures
+/// are reported on stderr rather than treated as fatal.
+pub fn run() {
+    match latest_release_tag() {
+        Ok(tag) => println!("{}", describe(env!("CARGO_PKG_VERSION"), &tag)),
+        Err(e) => eprintln!("Could not check for updates: {}", e),
+    }
+}
+
+fn latest_release_tag() -> Result<String, String> {
+    let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+    let body = agent
+        .get(LATEST_RELEASE_URL)
+        // GitHub's API rejects requests without a User-Agent
+        .set("User-Agent", concat!("pomodorust/", env!("CARGO_PKG_VERSION")))
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_string()
+        .map_err(|e| e.to_string())?;
+    let release: serde_json::Value = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+    release["tag_name"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "response has no tag_name".to_string())
+}
+
+/// `1.2.3` from `v1.2.3` / `1.2.3`; missing parts count as 0, anything after
+/// a `-` (pre-release) is ignored.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().trim_start_matches('v').split('-').next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    Some((major, minor, patch))
+}
+
+fn describe(current: &str, latest_tag: &str) -> String {
+    match (parse_version(current), parse_version(latest_tag)) {
+        (Some(cur), Some(latest)) if latest > cur => {
+            format!("Update available: v{} → v{}", current, latest_tag.trim_start_matches('v'))
+        }
+        (Some(_), Some(_)) => "Up to date".to_string(),
+        _ => format!("Could not compare v{} with release {}", current, latest_tag),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_release_tags() {
+        assert_eq!(describe("0.2.1", "v0.3.0"), "Update available: v0.2.1 → v0.3.0");
+        assert_eq!(describe("0.2.1", "v0.2.1"), "Up to date");
+        assert_eq!(describe("0.2.1", "0.2"), "Up to date");
+        assert_eq!(describe("0.2.1", "v0.10.0"), "Update available: v0.2.1 → v0.10.0");
+        assert_eq!(describe("0.2.1", "nightly"), "Could not compare v0.2.1 with release nightly");
+    }
+}